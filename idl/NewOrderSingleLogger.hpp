@@ -34,6 +34,7 @@ public:
 			<< "ddsMsg.Price : " << ddsMsg.Price() << std::endl
 			<< "ddsMsg.StopPx : " << ddsMsg.StopPx() << std::endl
 			<< "ddsMsg.TimeInForce : " << ddsMsg.TimeInForce() << std::endl
+			<< "ddsMsg.ExpireTime : " << ddsMsg.ExpireTime() << std::endl
 			<< "ddsMsg.Text : " << ddsMsg.Text() << std::endl;
 		out << "}";
 		out << std::endl;
@@ -115,6 +115,8 @@ public:
 
                     m_TimeInForce = x.m_TimeInForce;
 
+                    m_ExpireTime = x.m_ExpireTime;
+
                     m_Text = x.m_Text;
 
     }
@@ -142,6 +144,7 @@ public:
         m_Price = x.m_Price;
         m_StopPx = x.m_StopPx;
         m_TimeInForce = x.m_TimeInForce;
+        m_ExpireTime = x.m_ExpireTime;
         m_Text = std::move(x.m_Text);
     }
 
@@ -185,6 +188,8 @@ public:
 
                     m_TimeInForce = x.m_TimeInForce;
 
+                    m_ExpireTime = x.m_ExpireTime;
+
                     m_Text = x.m_Text;
 
         return *this;
@@ -214,6 +219,7 @@ public:
         m_Price = x.m_Price;
         m_StopPx = x.m_StopPx;
         m_TimeInForce = x.m_TimeInForce;
+        m_ExpireTime = x.m_ExpireTime;
         m_Text = std::move(x.m_Text);
         return *this;
     }
@@ -241,6 +247,7 @@ public:
            m_Price == x.m_Price &&
            m_StopPx == x.m_StopPx &&
            m_TimeInForce == x.m_TimeInForce &&
+           m_ExpireTime == x.m_ExpireTime &&
            m_Text == x.m_Text);
     }
 
@@ -808,6 +815,35 @@ public:
     }
 
 
+    /*!
+     * @brief This function sets a value in member ExpireTime
+     * @param _ExpireTime New value for member ExpireTime
+     */
+    eProsima_user_DllExport void ExpireTime(
+            uint64_t _ExpireTime)
+    {
+        m_ExpireTime = _ExpireTime;
+    }
+
+    /*!
+     * @brief This function returns the value of member ExpireTime
+     * @return Value of member ExpireTime
+     */
+    eProsima_user_DllExport uint64_t ExpireTime() const
+    {
+        return m_ExpireTime;
+    }
+
+    /*!
+     * @brief This function returns a reference to member ExpireTime
+     * @return Reference to member ExpireTime
+     */
+    eProsima_user_DllExport uint64_t& ExpireTime()
+    {
+        return m_ExpireTime;
+    }
+
+
     /*!
      * @brief This function copies the value in member Text
      * @param _Text New value to be copied in member Text
@@ -866,6 +902,7 @@ private:
     float m_Price{0.0};
     float m_StopPx{0.0};
     char m_TimeInForce{0};
+    uint64_t m_ExpireTime{0};
     std::string m_Text;
 
 };
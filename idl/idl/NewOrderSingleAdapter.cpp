@@ -48,6 +48,11 @@ void NewOrderSingleAdapter::FIX2DDS(const FIX::Message& fixMsg, DistributedATS_N
 	if (fixMsg.isSetField(FIX::FIELD::TimeInForce) )
 		ddsMsg.TimeInForce ( FIELD_GET_REF( fixMsg,TimeInForce));
 
+	if (fixMsg.isSetField(FIX::FIELD::ExpireTime) )
+		ddsMsg.ExpireTime ( ((FIX::ExpireTime)FIELD_GET_REF( fixMsg,ExpireTime)).getValue().getJulianDate());
+	else 
+		ddsMsg.ExpireTime ( 0 );
+
 	if (fixMsg.isSetField(FIX::FIELD::Text) )
 		ddsMsg.Text ( ((FIX::Text)fixMsg.getField(FIX::FIELD::Text)).getString().c_str());
 
@@ -88,6 +93,8 @@ void NewOrderSingleAdapter::DDS2FIX(const DistributedATS_NewOrderSingle::NewOrde
 	FIX::TimeInForce fixTimeInForce(ddsMsg.TimeInForce());
 	fixMsg.setField(fixTimeInForce);
 
+	DistributedATS::convert_dds_timestamp_to_fix(ddsMsg.ExpireTime(), FIX::FIELD::ExpireTime, fixMsg);
+
 	DistributedATS::convert_dds_string_to_fix(ddsMsg.Text(), FIX::FIELD::Text, fixMsg);
 
 
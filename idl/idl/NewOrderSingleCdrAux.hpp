@@ -24,7 +24,7 @@
 
 #include "NewOrderSingle.hpp"
 
-constexpr uint32_t DistributedATS_NewOrderSingle_NewOrderSingle_max_cdr_typesize {3960UL};
+constexpr uint32_t DistributedATS_NewOrderSingle_NewOrderSingle_max_cdr_typesize {3976UL};
 constexpr uint32_t DistributedATS_NewOrderSingle_NewOrderSingle_max_key_cdr_typesize {0UL};
 
 
@@ -101,6 +101,9 @@ eProsima_user_DllExport size_t calculate_serialized_size(
                 data.TimeInForce(), current_alignment);
 
         calculated_size += calculator.calculate_member_serialized_size(eprosima::fastcdr::MemberId(16),
+                data.ExpireTime(), current_alignment);
+
+        calculated_size += calculator.calculate_member_serialized_size(eprosima::fastcdr::MemberId(17),
                 data.Text(), current_alignment);
 
 
@@ -139,7 +142,8 @@ eProsima_user_DllExport void serialize(
         << eprosima::fastcdr::MemberId(13) << data.Price()
         << eprosima::fastcdr::MemberId(14) << data.StopPx()
         << eprosima::fastcdr::MemberId(15) << data.TimeInForce()
-        << eprosima::fastcdr::MemberId(16) << data.Text()
+        << eprosima::fastcdr::MemberId(16) << data.ExpireTime()
+        << eprosima::fastcdr::MemberId(17) << data.Text()
 ;
     scdr.end_serialize_type(current_state);
 }
@@ -224,6 +228,10 @@ eProsima_user_DllExport void deserialize(
                                             break;
 
                                         case 16:
+                                                dcdr >> data.ExpireTime();
+                                            break;
+
+                                        case 17:
                                                 dcdr >> data.Text();
                                             break;
 
@@ -295,6 +303,8 @@ void serialize_key(
 
                         scdr << data.TimeInForce();
 
+                        scdr << data.ExpireTime();
+
                         scdr << data.Text();
 
 }
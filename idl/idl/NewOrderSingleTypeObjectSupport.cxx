@@ -602,6 +602,36 @@ void register_NewOrderSingle_type_identifier(
             CompleteStructMember member_TimeInForce = TypeObjectUtils::build_complete_struct_member(common_TimeInForce, detail_TimeInForce);
             TypeObjectUtils::add_complete_struct_member(member_seq_NewOrderSingle, member_TimeInForce);
         }
+        {
+            TypeIdentifierPair type_ids_ExpireTime;
+            ReturnCode_t return_code_ExpireTime {eprosima::fastdds::dds::RETCODE_OK};
+            return_code_ExpireTime =
+                eprosima::fastdds::dds::DomainParticipantFactory::get_instance()->type_object_registry().get_type_identifiers(
+                "_uint64_t", type_ids_ExpireTime);
+
+            if (eprosima::fastdds::dds::RETCODE_OK != return_code_ExpireTime)
+            {
+                EPROSIMA_LOG_ERROR(XTYPES_TYPE_REPRESENTATION,
+                        "ExpireTime Structure member TypeIdentifier unknown to TypeObjectRegistry.");
+                return;
+            }
+            StructMemberFlag member_flags_ExpireTime = TypeObjectUtils::build_struct_member_flag(eprosima::fastdds::dds::xtypes::TryConstructFailAction::DISCARD,
+                    false, false, false, false);
+            MemberId member_id_ExpireTime = 0x00000010;
+            bool common_ExpireTime_ec {false};
+            CommonStructMember common_ExpireTime {TypeObjectUtils::build_common_struct_member(member_id_ExpireTime, member_flags_ExpireTime, TypeObjectUtils::retrieve_complete_type_identifier(type_ids_ExpireTime, common_ExpireTime_ec))};
+            if (!common_ExpireTime_ec)
+            {
+                EPROSIMA_LOG_ERROR(XTYPES_TYPE_REPRESENTATION, "Structure ExpireTime member TypeIdentifier inconsistent.");
+                return;
+            }
+            MemberName name_ExpireTime = "ExpireTime";
+            eprosima::fastcdr::optional<AppliedBuiltinMemberAnnotations> member_ann_builtin_ExpireTime;
+            ann_custom_NewOrderSingle.reset();
+            CompleteMemberDetail detail_ExpireTime = TypeObjectUtils::build_complete_member_detail(name_ExpireTime, member_ann_builtin_ExpireTime, ann_custom_NewOrderSingle);
+            CompleteStructMember member_ExpireTime = TypeObjectUtils::build_complete_struct_member(common_ExpireTime, detail_ExpireTime);
+            TypeObjectUtils::add_complete_struct_member(member_seq_NewOrderSingle, member_ExpireTime);
+        }
         {
             TypeIdentifierPair type_ids_Text;
             ReturnCode_t return_code_Text {eprosima::fastdds::dds::RETCODE_OK};
@@ -625,7 +655,7 @@ void register_NewOrderSingle_type_identifier(
             }
             StructMemberFlag member_flags_Text = TypeObjectUtils::build_struct_member_flag(eprosima::fastdds::dds::xtypes::TryConstructFailAction::DISCARD,
                     false, false, false, false);
-            MemberId member_id_Text = 0x00000010;
+            MemberId member_id_Text = 0x00000011;
             bool common_Text_ec {false};
             CommonStructMember common_Text {TypeObjectUtils::build_common_struct_member(member_id_Text, member_flags_Text, TypeObjectUtils::retrieve_complete_type_identifier(type_ids_Text, common_Text_ec))};
             if (!common_Text_ec)
@@ -0,0 +1,196 @@
+// examples/report_latency.rs
+//
+// Measures the delay between writing an ExecutionReport and a listener
+// processing it, for the old polling loops and the stream-driven ReportListener.
+//
+//     cargo run --release --example report_latency [reports]
+use anyhow::{Context, Result};
+use oms_rust_client::report::{
+    ExecutionReport, ExecutionReportListener, ReportDeserializerAdapter, ReportReader,
+};
+use rustdds::no_key::DataWriter;
+use rustdds::{policy, CDRSerializerAdapter, DomainParticipant, QosPolicyBuilder, TopicKind};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::time::sleep;
+
+// Private domain and topic so a running OMS neither sees nor disturbs the benchmark
+const BENCH_DOMAIN_ID: u16 = 42;
+const BENCH_TOPIC_NAME: &str = "REPORT_LATENCY_BENCH_TOPIC";
+const DEFAULT_REPORTS: usize = 500;
+// Gap between reports, not a multiple of the polling periods
+const SEND_INTERVAL: Duration = Duration::from_micros(3_700);
+
+type SendTimes = Arc<Mutex<HashMap<String, Instant>>>;
+
+#[derive(Clone, Copy)]
+enum Mode {
+    /// `poll_once` + 1 ms sleep, as OrderResponseListener::run did
+    Poll1ms,
+    /// `take_next_sample` + 10 ms sleep, as ExecutionReportListener::start_listening did
+    Poll10ms,
+    /// `ReportListener::run` on the async sample stream
+    Stream,
+}
+
+impl Mode {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Poll1ms => "poll + sleep(1ms)",
+            Self::Poll10ms => "poll + sleep(10ms)",
+            Self::Stream => "async sample stream",
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let reports = std::env::args()
+        .nth(1)
+        .map(|n| n.parse().context("reports must be a number"))
+        .transpose()?
+        .unwrap_or(DEFAULT_REPORTS);
+
+    let participant =
+        DomainParticipant::new(BENCH_DOMAIN_ID).context("Failed to create participant")?;
+    let qos = QosPolicyBuilder::new()
+        .reliability(policy::Reliability::Reliable {
+            max_blocking_time: rustdds::Duration::ZERO,
+        })
+        .history(policy::History::KeepAll)
+        .build();
+    let topic = participant
+        .create_topic(
+            BENCH_TOPIC_NAME.to_string(),
+            ExecutionReport::type_name().to_string(),
+            &qos,
+            TopicKind::NoKey,
+        )
+        .context("Failed to create topic")?;
+    let publisher = participant.create_publisher(&qos)?;
+    let subscriber = participant.create_subscriber(&qos)?;
+
+    println!(
+        "{:<22} {:>9} {:>9} {:>9} {:>9}",
+        "mode", "p50", "p99", "max", "mean"
+    );
+    for mode in [Mode::Poll1ms, Mode::Poll10ms, Mode::Stream] {
+        let writer = publisher
+            .create_datawriter_no_key::<ExecutionReport, CDRSerializerAdapter<ExecutionReport>>(
+                &topic, None,
+            )?;
+        let reader = subscriber
+            .create_datareader_no_key::<ExecutionReport, ReportDeserializerAdapter<ExecutionReport>>(
+                &topic, None,
+            )?;
+        // Let discovery match the new writer and reader
+        sleep(Duration::from_millis(500)).await;
+
+        let latencies = measure(mode, &writer, reader, reports).await?;
+        print_row(mode, latencies);
+    }
+    Ok(())
+}
+
+async fn measure(
+    mode: Mode,
+    writer: &DataWriter<ExecutionReport>,
+    reader: ReportReader<ExecutionReport>,
+    reports: usize,
+) -> Result<Vec<Duration>> {
+    let sent: SendTimes = Arc::default();
+    let (tx, mut rx) = mpsc::unbounded_channel();
+
+    let record = {
+        let sent = Arc::clone(&sent);
+        move |report: &ExecutionReport| {
+            let received = Instant::now();
+            if let Some(at) = sent.lock().unwrap().remove(&report.orig_cl_ord_id) {
+                let _ = tx.send(received - at);
+            }
+        }
+    };
+
+    let task = match mode {
+        Mode::Poll1ms => {
+            let listener = Arc::new(ExecutionReportListener::new(reader));
+            listener.register_callback(record).detach();
+            tokio::spawn(async move {
+                loop {
+                    let _ = listener.poll_once().await;
+                    sleep(Duration::from_millis(1)).await;
+                }
+            })
+        }
+        Mode::Poll10ms => tokio::spawn(async move {
+            let mut reader = reader;
+            loop {
+                while let Ok(Some(sample)) = reader.take_next_sample() {
+                    record(sample.value());
+                }
+                sleep(Duration::from_millis(10)).await;
+            }
+        }),
+        Mode::Stream => {
+            let listener = Arc::new(ExecutionReportListener::new(reader));
+            listener.register_callback(record).detach();
+            tokio::spawn(async move { listener.run().await })
+        }
+    };
+
+    for i in 0..reports {
+        let cl_ord_id = format!("BENCH_{}", i);
+        let report = ExecutionReport {
+            orig_cl_ord_id: cl_ord_id.clone(),
+            order_id: cl_ord_id.clone(),
+            exec_id: cl_ord_id.clone(),
+            exec_type: b'0',
+            ord_status: b'0',
+            ..Default::default()
+        };
+        sent.lock().unwrap().insert(cl_ord_id, Instant::now());
+        writer
+            .write(report, None)
+            .context("Failed to write report")?;
+        sleep(SEND_INTERVAL).await;
+    }
+
+    let mut latencies = Vec::with_capacity(reports);
+    while latencies.len() < reports {
+        match tokio::time::timeout(Duration::from_secs(2), rx.recv()).await {
+            Ok(Some(latency)) => latencies.push(latency),
+            _ => break,
+        }
+    }
+    task.abort();
+
+    if latencies.len() < reports {
+        println!(
+            "⚠️ {}: only {} of {} reports received",
+            mode.name(),
+            latencies.len(),
+            reports
+        );
+    }
+    Ok(latencies)
+}
+
+fn print_row(mode: Mode, mut latencies: Vec<Duration>) {
+    if latencies.is_empty() {
+        println!("{:<22} no reports received", mode.name());
+        return;
+    }
+    latencies.sort();
+    let percentile = |p: usize| latencies[(latencies.len() - 1) * p / 100];
+    let mean = latencies.iter().sum::<Duration>() / latencies.len() as u32;
+    println!(
+        "{:<22} {:>9.3?} {:>9.3?} {:>9.3?} {:>9.3?}",
+        mode.name(),
+        percentile(50),
+        percentile(99),
+        latencies[latencies.len() - 1],
+        mean
+    );
+}
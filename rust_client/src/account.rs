@@ -0,0 +1,218 @@
+// src/account.rs
+use crate::blotter::{BlotterQuery, OrderRecord};
+use crate::dds_client::OrderDdsClient;
+use crate::new_order_single::NewOrderSingle;
+use crate::order_builder::OrderBuilder;
+use crate::order_handle::OrderHandle;
+use crate::order_message::{OrderRequest, OrderSide};
+use crate::positions::Position;
+use crate::risk::{UserConfig, UserUsage};
+use anyhow::Result;
+
+/// Identity orders are stamped with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Account {
+    /// `user_id` of OrderRequests to the OMS, also the risk limits key
+    pub user_id: String,
+    /// `DATS_SourceUser` of NewOrderSingles to the matching engine, checked
+    /// against the `user_id` limits
+    pub source_user: String,
+    pub client_id: String,
+}
+
+impl Account {
+    pub fn new(user_id: &str, source_user: &str, client_id: &str) -> Self {
+        Self {
+            user_id: user_id.to_string(),
+            source_user: source_user.to_string(),
+            client_id: client_id.to_string(),
+        }
+    }
+
+    /// Whether an order or report user belongs to this account
+    pub fn owns(&self, user_id: &str) -> bool {
+        user_id == self.user_id || user_id == self.source_user
+    }
+}
+
+impl Default for Account {
+    fn default() -> Self {
+        Self::new("user001", "rust_trader_001", "rust_client")
+    }
+}
+
+/// One account trading through a shared client: stamps outgoing orders with
+/// the account identity and filters order and position views to it
+pub struct AccountContext<'a> {
+    client: &'a OrderDdsClient,
+    account: Account,
+}
+
+impl<'a> AccountContext<'a> {
+    /// Context for an account, aliasing its source user to its OMS user in the
+    /// client's risk engine so its NewOrderSingles count against the user limits
+    pub(crate) fn new(client: &'a OrderDdsClient, account: Account) -> Self {
+        if let Some(risk_engine) = client.risk_engine() {
+            risk_engine.add_alias(&account.source_user, &account.user_id);
+        }
+        Self { client, account }
+    }
+
+    pub fn account(&self) -> &Account {
+        &self.account
+    }
+
+    pub fn user_id(&self) -> &str {
+        &self.account.user_id
+    }
+
+    pub fn source_user(&self) -> &str {
+        &self.account.source_user
+    }
+
+    /// Risk limits configured for the account, if a risk engine is set
+    pub fn limits(&self) -> Option<UserConfig> {
+        self.client
+            .risk_engine()?
+            .user_config(&self.account.user_id)
+            .cloned()
+    }
+
+    /// Running usage of the account's risk limits, NewOrderSingles included
+    pub fn usage(&self) -> Option<UserUsage> {
+        Some(self.client.risk_engine()?.usage(&self.account.user_id))
+    }
+
+    /// Send an OrderRequest on behalf of this account
+    pub async fn send_order(&self, mut order: OrderRequest) -> Result<OrderHandle> {
+        order.user_id = self.account.user_id.clone();
+        order.client_id = self.account.client_id.clone();
+        self.client.send_order(order).await
+    }
+
+    /// Send a NewOrderSingle on behalf of this account
+    pub async fn send_new_order_single(&self, mut order: NewOrderSingle) -> Result<OrderHandle> {
+        order.dats_source_user = self.account.source_user.clone();
+        self.client.send_new_order_single(order).await
+    }
+
+    /// Build and send an OrderRequest, generating its order ID if unset
+    pub async fn send_order_request_from(&self, builder: OrderBuilder) -> Result<OrderHandle> {
+        let order: OrderRequest = self
+            .prepare(builder)?
+            .account(&self.account.user_id)
+            .build()?;
+        self.client.send_order(order).await
+    }
+
+    /// Build and send a NewOrderSingle, generating its ClOrdID if unset
+    pub async fn send_new_order_single_from(&self, builder: OrderBuilder) -> Result<OrderHandle> {
+        let order: NewOrderSingle = self
+            .prepare(builder)?
+            .account(&self.account.source_user)
+            .build()?;
+        self.client.send_new_order_single(order).await
+    }
+
+    fn prepare(&self, builder: OrderBuilder) -> Result<OrderBuilder> {
+        let builder = builder.client_id(&self.account.client_id);
+        if builder.has_cl_ord_id() {
+            return Ok(builder);
+        }
+        Ok(builder.cl_ord_id(&self.client.next_cl_ord_id()?))
+    }
+
+    pub async fn send_market_order(
+        &self,
+        symbol: &str,
+        side: OrderSide,
+        quantity: f64,
+    ) -> Result<OrderHandle> {
+        self.send_order_request_from(OrderBuilder::market(symbol, side, quantity))
+            .await
+    }
+
+    pub async fn send_limit_order(
+        &self,
+        symbol: &str,
+        side: OrderSide,
+        quantity: f64,
+        price: f64,
+    ) -> Result<OrderHandle> {
+        self.send_order_request_from(OrderBuilder::limit(symbol, side, quantity, price))
+            .await
+    }
+
+    pub async fn send_market_new_order_single(
+        &self,
+        symbol: &str,
+        side: OrderSide,
+        quantity: i32,
+    ) -> Result<OrderHandle> {
+        self.send_new_order_single_from(OrderBuilder::market(symbol, side, quantity as f64))
+            .await
+    }
+
+    pub async fn send_limit_new_order_single(
+        &self,
+        symbol: &str,
+        side: OrderSide,
+        quantity: i32,
+        price: f64,
+    ) -> Result<OrderHandle> {
+        self.send_new_order_single_from(OrderBuilder::limit(symbol, side, quantity as f64, price))
+            .await
+    }
+
+    /// Blotter records of this account matching the query; its user filter is ignored
+    pub fn orders(&self, query: &BlotterQuery) -> Vec<OrderRecord> {
+        let blotter = self.client.order_blotter();
+        let mut users = vec![&self.account.user_id];
+        if self.account.source_user != self.account.user_id {
+            users.push(&self.account.source_user);
+        }
+
+        let mut records: Vec<OrderRecord> = users
+            .into_iter()
+            .flat_map(|user| {
+                blotter.query(&BlotterQuery {
+                    user_id: Some(user.clone()),
+                    ..query.clone()
+                })
+            })
+            .collect();
+
+        records.sort_by_key(|record| std::cmp::Reverse(record.updated_at));
+        records
+    }
+
+    /// Working orders of this account
+    pub fn open_orders(&self) -> Vec<OrderRecord> {
+        self.orders(&BlotterQuery {
+            open_only: true,
+            ..BlotterQuery::default()
+        })
+    }
+
+    /// Positions of this account, empty without a position keeper on the client
+    pub fn positions(&self) -> Vec<Position> {
+        let Some(keeper) = self.client.position_keeper() else {
+            return Vec::new();
+        };
+
+        let mut positions = keeper.positions_for_user(&self.account.user_id);
+        if self.account.source_user != self.account.user_id {
+            positions.extend(keeper.positions_for_user(&self.account.source_user));
+        }
+        positions
+    }
+
+    /// Position of this account in one symbol, summed over both identities
+    pub fn position(&self, symbol: &str) -> f64 {
+        self.positions()
+            .iter()
+            .filter(|p| p.symbol == symbol)
+            .map(|p| p.net_qty)
+            .sum()
+    }
+}
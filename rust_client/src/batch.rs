@@ -0,0 +1,111 @@
+// src/batch.rs
+use crate::new_order_single::NewOrderSingle;
+use crate::order_handle::{OrderHandle, OrderUpdate};
+use crate::order_message::OrderRequest;
+use anyhow::Result;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// One order of a batch, to the OMS or straight to the matching engine
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone)]
+pub enum OrderSpec {
+    OrderRequest(OrderRequest),
+    NewOrderSingle(NewOrderSingle),
+}
+
+impl OrderSpec {
+    /// Order ID of an OrderRequest, ClOrdID of a NewOrderSingle
+    pub fn cl_ord_id(&self) -> &str {
+        match self {
+            Self::OrderRequest(order) => &order.order_id,
+            Self::NewOrderSingle(order) => &order.cl_ord_id,
+        }
+    }
+}
+
+impl From<OrderRequest> for OrderSpec {
+    fn from(order: OrderRequest) -> Self {
+        Self::OrderRequest(order)
+    }
+}
+
+impl From<NewOrderSingle> for OrderSpec {
+    fn from(order: NewOrderSingle) -> Self {
+        Self::NewOrderSingle(order)
+    }
+}
+
+/// What a batch does when some of its orders fail validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BatchMode {
+    /// Send nothing unless every order passes and registers its ClOrdID. DDS write
+    /// failures are still reported per order.
+    #[default]
+    AllOrNothing,
+    /// Send the orders that pass, report the others
+    BestEffort,
+}
+
+/// Per-order results of a batch in submission order, and a handle over the sent ones
+pub struct BatchOutcome {
+    pub results: Vec<Result<OrderHandle>>,
+    pub handle: BatchHandle,
+}
+
+impl BatchOutcome {
+    pub fn sent(&self) -> usize {
+        self.handle.len()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.results.len() - self.sent()
+    }
+}
+
+/// Combined handle over the orders of a batch that were sent
+#[derive(Debug, Clone, Default)]
+pub struct BatchHandle {
+    handles: Vec<OrderHandle>,
+}
+
+impl BatchHandle {
+    pub(crate) fn new(handles: Vec<OrderHandle>) -> Self {
+        Self { handles }
+    }
+
+    pub fn handles(&self) -> &[OrderHandle] {
+        &self.handles
+    }
+
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// Resolve once every order is acknowledged; fails on the first rejection or
+    /// when `wait` runs out
+    pub async fn accepted(&self, wait: Duration) -> Result<Vec<OrderUpdate>> {
+        let deadline = Instant::now() + wait;
+        let mut updates = Vec::with_capacity(self.handles.len());
+        for handle in &self.handles {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            updates.push(handle.accepted(remaining).await?);
+        }
+        Ok(updates)
+    }
+
+    /// Resolve with the terminal report of every order once all are done
+    pub async fn done(&self, wait: Duration) -> Result<Vec<OrderUpdate>> {
+        let deadline = Instant::now() + wait;
+        let mut updates = Vec::with_capacity(self.handles.len());
+        for handle in &self.handles {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            updates.push(handle.done(remaining).await?);
+        }
+        Ok(updates)
+    }
+}
@@ -0,0 +1,595 @@
+// src/blotter.rs
+use crate::new_order_single::NewOrderSingle;
+use crate::order_handle::OrderUpdate;
+use crate::order_message::{OrderRequest, OrderSide};
+use crate::order_state::{OrderLifecycle, OrderState, TransitionPolicy};
+use crate::report::{ExecutionReport, OrderResponseReport};
+use chrono::{DateTime, Utc};
+use log::warn;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// Capacity of the change notification channel before slow subscribers lag
+const CHANGE_CHANNEL_CAPACITY: usize = 1024;
+
+/// How long finished orders stay on the blotter by default
+const DEFAULT_FINISHED_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Single execution applied to an order
+#[derive(Debug, Clone)]
+pub struct BlotterFill {
+    pub exec_id: String,
+    pub last_qty: i32,
+    pub last_px: f32,
+    pub received_at: DateTime<Utc>,
+}
+
+/// One row of the blotter: everything known about a single order
+#[derive(Debug, Clone)]
+pub struct OrderRecord {
+    pub cl_ord_id: String,
+    /// Venue-assigned OrderID, empty until the first report arrives
+    pub order_id: String,
+    pub symbol: String,
+    pub side: u8,
+    pub user_id: String,
+    pub order_qty: f64,
+    pub price: f64,
+    pub lifecycle: OrderLifecycle,
+    pub fills: Vec<BlotterFill>,
+    pub last_update: Option<OrderUpdate>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OrderRecord {
+    /// Current state; orders without any report are still pending acknowledgement
+    pub fn state(&self) -> OrderState {
+        self.lifecycle.state().unwrap_or(OrderState::PendingNew)
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.state().is_open()
+    }
+}
+
+/// Kind of change published to blotter subscribers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlotterChangeKind {
+    Added,
+    Updated,
+}
+
+/// Change notification carrying the record after the change
+#[derive(Debug, Clone)]
+pub struct BlotterChange {
+    pub kind: BlotterChangeKind,
+    pub record: OrderRecord,
+}
+
+/// Filter for `OrderBlotter::query`; unset fields match everything
+#[derive(Debug, Clone, Default)]
+pub struct BlotterQuery {
+    pub symbol: Option<String>,
+    pub user_id: Option<String>,
+    pub states: Option<Vec<OrderState>>,
+    pub open_only: bool,
+    /// Only orders updated at or after this time
+    pub updated_since: Option<DateTime<Utc>>,
+}
+
+#[derive(Default)]
+struct BlotterIndexes {
+    records: HashMap<String, OrderRecord>,
+    by_order_id: HashMap<String, String>,
+    by_symbol: HashMap<String, HashSet<String>>,
+    by_state: HashMap<OrderState, HashSet<String>>,
+    by_user: HashMap<String, HashSet<String>>,
+    by_time: BTreeSet<(DateTime<Utc>, String)>,
+}
+
+impl BlotterIndexes {
+    fn resolve(&self, id: &str) -> Option<String> {
+        if self.records.contains_key(id) {
+            return Some(id.to_string());
+        }
+        self.by_order_id.get(id).cloned()
+    }
+
+    fn index(&mut self, record: &OrderRecord) {
+        let key = record.cl_ord_id.clone();
+        if !record.order_id.is_empty() {
+            self.by_order_id
+                .insert(record.order_id.clone(), key.clone());
+        }
+        self.by_symbol
+            .entry(record.symbol.clone())
+            .or_default()
+            .insert(key.clone());
+        self.by_state
+            .entry(record.state())
+            .or_default()
+            .insert(key.clone());
+        self.by_user
+            .entry(record.user_id.clone())
+            .or_default()
+            .insert(key.clone());
+        self.by_time.insert((record.updated_at, key));
+    }
+
+    fn unindex(&mut self, record: &OrderRecord) {
+        let key = &record.cl_ord_id;
+        if let Some(keys) = self.by_symbol.get_mut(&record.symbol) {
+            keys.remove(key);
+        }
+        if let Some(keys) = self.by_state.get_mut(&record.state()) {
+            keys.remove(key);
+        }
+        if let Some(keys) = self.by_user.get_mut(&record.user_id) {
+            keys.remove(key);
+        }
+        self.by_time.remove(&(record.updated_at, key.clone()));
+    }
+}
+
+/// Intersect the candidate set with the keys of one index
+fn narrow<'a>(candidates: &mut Option<HashSet<&'a String>>, keys: HashSet<&'a String>) {
+    *candidates = Some(match candidates.take() {
+        Some(current) => current.intersection(&keys).copied().collect(),
+        None => keys,
+    });
+}
+
+/// Client-side order blotter holding exactly one record per order. Finished
+/// orders are dropped by `evict_finished` once their retention has passed.
+pub struct OrderBlotter {
+    indexes: RwLock<BlotterIndexes>,
+    changes: broadcast::Sender<BlotterChange>,
+    finished_retention: Duration,
+}
+
+impl Default for OrderBlotter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OrderBlotter {
+    pub fn new() -> Self {
+        let (changes, _) = broadcast::channel(CHANGE_CHANNEL_CAPACITY);
+        Self {
+            indexes: RwLock::new(BlotterIndexes::default()),
+            changes,
+            finished_retention: DEFAULT_FINISHED_RETENTION,
+        }
+    }
+
+    /// Keep orders for `retention` after their last update once they are finished
+    pub fn with_finished_retention(mut self, retention: Duration) -> Self {
+        self.finished_retention = retention;
+        self
+    }
+
+    /// Receive a notification for every record added or updated from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<BlotterChange> {
+        self.changes.subscribe()
+    }
+
+    /// Record an OrderRequest sent to the OMS
+    pub fn on_order_request(&self, order: &OrderRequest) {
+        let side = match order.side {
+            OrderSide::BUY => b'1',
+            OrderSide::SELL => b'2',
+        };
+        self.add_order(
+            &order.order_id,
+            &order.symbol,
+            side,
+            &order.user_id,
+            order.quantity,
+            order.price,
+        );
+    }
+
+    /// Record a NewOrderSingle sent directly to the matching engine
+    pub fn on_new_order_single(&self, order: &NewOrderSingle) {
+        self.add_order(
+            &order.cl_ord_id,
+            &order.symbol,
+            order.side,
+            &order.dats_source_user,
+            order.order_qty as f64,
+            order.price as f64,
+        );
+    }
+
+    /// Apply an OrderResponseReport from the OMS
+    pub fn on_order_response(&self, report: &OrderResponseReport) {
+        self.apply(OrderUpdate::from(report));
+    }
+
+    /// Apply an ExecutionReport from the matching engine
+    pub fn on_execution_report(&self, report: &ExecutionReport) {
+        self.apply(OrderUpdate::from(report));
+    }
+
+    fn add_order(
+        &self,
+        cl_ord_id: &str,
+        symbol: &str,
+        side: u8,
+        user_id: &str,
+        order_qty: f64,
+        price: f64,
+    ) {
+        let now = Utc::now();
+        let record = OrderRecord {
+            cl_ord_id: cl_ord_id.to_string(),
+            order_id: String::new(),
+            symbol: symbol.to_string(),
+            side,
+            user_id: user_id.to_string(),
+            order_qty,
+            price,
+            lifecycle: OrderLifecycle::new(TransitionPolicy::Reject),
+            fills: Vec::new(),
+            last_update: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        {
+            let mut indexes = self.indexes.write().unwrap();
+            if indexes.records.contains_key(cl_ord_id) {
+                warn!("⚠️ Blotter already holds order {}", cl_ord_id);
+                return;
+            }
+            indexes.index(&record);
+            indexes
+                .records
+                .insert(record.cl_ord_id.clone(), record.clone());
+        }
+
+        self.notify(BlotterChangeKind::Added, record);
+    }
+
+    /// Drop an order that never reached DDS, e.g. after a failed write
+    pub fn remove(&self, cl_ord_id: &str) -> Option<OrderRecord> {
+        let mut indexes = self.indexes.write().unwrap();
+        Self::remove_record(&mut indexes, cl_ord_id)
+    }
+
+    fn remove_record(indexes: &mut BlotterIndexes, cl_ord_id: &str) -> Option<OrderRecord> {
+        let record = indexes.records.remove(cl_ord_id)?;
+        indexes.unindex(&record);
+        indexes.by_order_id.remove(&record.order_id);
+        Some(record)
+    }
+
+    /// Drop finished orders last updated longer ago than the retention; open
+    /// orders stay however old they are. Returns the number of orders dropped.
+    pub fn evict_finished(&self) -> usize {
+        let cutoff = Utc::now()
+            - chrono::Duration::from_std(self.finished_retention).unwrap_or(chrono::Duration::MAX);
+        let mut indexes = self.indexes.write().unwrap();
+        let expired: Vec<String> = indexes
+            .by_time
+            .iter()
+            .take_while(|(updated_at, _)| *updated_at <= cutoff)
+            .filter(|(_, key)| indexes.records.get(key).is_some_and(|r| !r.is_open()))
+            .map(|(_, key)| key.clone())
+            .collect();
+
+        for key in &expired {
+            Self::remove_record(&mut indexes, key);
+        }
+        expired.len()
+    }
+
+    fn apply(&self, update: OrderUpdate) {
+        let now = Utc::now();
+        let (kind, record, applied) = {
+            let mut indexes = self.indexes.write().unwrap();
+
+            let key = indexes
+                .resolve(update.order_key())
+                .or_else(|| indexes.resolve(&update.order_id));
+
+            let (kind, mut record) = match key.and_then(|key| indexes.records.remove(&key)) {
+                Some(record) => {
+                    indexes.unindex(&record);
+                    (BlotterChangeKind::Updated, record)
+                }
+                // Orders not sent by this client (or sent before a restart)
+                None => (
+                    BlotterChangeKind::Added,
+                    OrderRecord {
+                        cl_ord_id: update.order_key().to_string(),
+                        order_id: String::new(),
+                        symbol: update.symbol.clone(),
+                        side: update.side,
+                        user_id: update.user_id.clone(),
+                        order_qty: update.order_qty as f64,
+                        price: 0.0,
+                        lifecycle: OrderLifecycle::new(TransitionPolicy::Reject),
+                        fills: Vec::new(),
+                        last_update: None,
+                        created_at: now,
+                        updated_at: now,
+                    },
+                ),
+            };
+
+            if record.order_id.is_empty() {
+                record.order_id = update.order_id.clone();
+            }
+
+            let applied = match record.lifecycle.apply(&update) {
+                Ok(_) => {
+                    // A repeated fill leaves CumQty unchanged and passes the lifecycle
+                    let repeated = !update.exec_id.is_empty()
+                        && record.fills.iter().any(|f| f.exec_id == update.exec_id);
+                    if update.is_fill() && !repeated {
+                        record.fills.push(BlotterFill {
+                            exec_id: update.exec_id.clone(),
+                            last_qty: update.last_qty,
+                            last_px: update.last_px,
+                            received_at: now,
+                        });
+                    }
+                    record.last_update = Some(update);
+                    record.updated_at = now;
+                    true
+                }
+                Err(e) => {
+                    warn!("⚠️ Blotter ignored report for {}: {}", record.cl_ord_id, e);
+                    false
+                }
+            };
+
+            indexes.index(&record);
+            indexes
+                .records
+                .insert(record.cl_ord_id.clone(), record.clone());
+            (kind, record, applied)
+        };
+
+        if applied {
+            self.notify(kind, record);
+        }
+    }
+
+    fn notify(&self, kind: BlotterChangeKind, record: OrderRecord) {
+        // Sending only fails when nobody is subscribed
+        let _ = self.changes.send(BlotterChange { kind, record });
+    }
+
+    /// Look up an order by ClOrdID or venue OrderID
+    pub fn get(&self, id: &str) -> Option<OrderRecord> {
+        let indexes = self.indexes.read().unwrap();
+        let key = indexes.resolve(id)?;
+        indexes.records.get(&key).cloned()
+    }
+
+    /// Number of orders on the blotter
+    pub fn len(&self) -> usize {
+        self.indexes.read().unwrap().records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Orders matching every criterion of the query, most recently updated first
+    pub fn query(&self, query: &BlotterQuery) -> Vec<OrderRecord> {
+        let indexes = self.indexes.read().unwrap();
+
+        // Intersect the symbol, user and state indexes that the query constrains
+        let mut candidates: Option<HashSet<&String>> = None;
+
+        if let Some(symbol) = &query.symbol {
+            narrow(
+                &mut candidates,
+                indexes
+                    .by_symbol
+                    .get(symbol)
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+            );
+        }
+        if let Some(user_id) = &query.user_id {
+            narrow(
+                &mut candidates,
+                indexes.by_user.get(user_id).into_iter().flatten().collect(),
+            );
+        }
+        if let Some(states) = &query.states {
+            narrow(
+                &mut candidates,
+                states
+                    .iter()
+                    .filter_map(|state| indexes.by_state.get(state))
+                    .flatten()
+                    .collect(),
+            );
+        }
+
+        let since = query.updated_since.unwrap_or(DateTime::<Utc>::MIN_UTC);
+        indexes
+            .by_time
+            .range((since, String::new())..)
+            .rev()
+            .map(|(_, key)| key)
+            .filter(|key| candidates.as_ref().is_none_or(|c| c.contains(key)))
+            .filter_map(|key| indexes.records.get(key))
+            .filter(|record| !query.open_only || record.is_open())
+            .cloned()
+            .collect()
+    }
+
+    /// Working orders for a symbol
+    pub fn open_orders(&self, symbol: &str) -> Vec<OrderRecord> {
+        self.query(&BlotterQuery {
+            symbol: Some(symbol.to_string()),
+            open_only: true,
+            ..Default::default()
+        })
+    }
+
+    /// Fills received at or after `since`, paired with the order they belong to
+    pub fn fills_since(&self, since: DateTime<Utc>) -> Vec<(OrderRecord, BlotterFill)> {
+        self.query(&BlotterQuery {
+            updated_since: Some(since),
+            ..Default::default()
+        })
+        .into_iter()
+        .flat_map(|record| {
+            record
+                .fills
+                .iter()
+                .filter(|fill| fill.received_at >= since)
+                .cloned()
+                .map(|fill| (record.clone(), fill))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+    }
+
+    /// Orders rejected since the start of the current UTC day
+    pub fn rejects_today(&self) -> Vec<OrderRecord> {
+        let start_of_day = Utc::now()
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .map(|t| t.and_utc())
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+
+        self.query(&BlotterQuery {
+            states: Some(vec![OrderState::Rejected]),
+            updated_since: Some(start_of_day),
+            ..Default::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(cl_ord_id: &str, symbol: &str, user_id: &str) -> NewOrderSingle {
+        NewOrderSingle::limit_order(
+            cl_ord_id.to_string(),
+            symbol.to_string(),
+            "1",
+            10,
+            100.0,
+            user_id.to_string(),
+        )
+    }
+
+    fn update(
+        cl_ord_id: &str,
+        exec_id: &str,
+        ord_status: u8,
+        last_qty: i32,
+        cum_qty: i32,
+    ) -> OrderUpdate {
+        OrderUpdate {
+            order_id: format!("ORD-{}", cl_ord_id),
+            orig_cl_ord_id: cl_ord_id.to_string(),
+            exec_id: exec_id.to_string(),
+            exec_type: if last_qty > 0 { b'F' } else { ord_status },
+            ord_status,
+            ord_rej_reason: 0,
+            user_id: String::new(),
+            symbol: String::new(),
+            side: b'1',
+            order_qty: 10,
+            last_qty,
+            last_px: 100.0,
+            leaves_qty: 10 - cum_qty,
+            cum_qty,
+            avg_px: 100.0,
+            transact_time: 0,
+            text: String::new(),
+        }
+    }
+
+    fn blotter() -> OrderBlotter {
+        let blotter = OrderBlotter::new();
+        blotter.on_new_order_single(&order("CL-1", "BTC-USD", "user001"));
+        blotter.on_new_order_single(&order("CL-2", "BTC-USD", "user002"));
+        blotter.on_new_order_single(&order("CL-3", "ETH-USD", "user001"));
+        blotter.apply(update("CL-1", "E-1", b'0', 0, 0));
+        blotter.apply(update("CL-2", "E-2", b'8', 0, 0));
+        blotter.apply(update("CL-3", "E-3", b'0', 0, 0));
+        blotter.apply(update("CL-3", "E-4", b'2', 10, 10));
+        blotter
+    }
+
+    fn keys(records: Vec<OrderRecord>) -> Vec<String> {
+        let mut keys: Vec<String> = records.into_iter().map(|r| r.cl_ord_id).collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn queries_intersect_symbol_user_and_state() {
+        let blotter = blotter();
+        assert_eq!(blotter.len(), 3);
+        assert_eq!(keys(blotter.open_orders("BTC-USD")), ["CL-1"]);
+        assert_eq!(keys(blotter.rejects_today()), ["CL-2"]);
+
+        let user_orders = blotter.query(&BlotterQuery {
+            user_id: Some("user001".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(keys(user_orders), ["CL-1", "CL-3"]);
+
+        let filled_btc = blotter.query(&BlotterQuery {
+            symbol: Some("BTC-USD".to_string()),
+            states: Some(vec![OrderState::Filled]),
+            ..Default::default()
+        });
+        assert!(filled_btc.is_empty());
+
+        let future = blotter.query(&BlotterQuery {
+            updated_since: Some(Utc::now() + chrono::Duration::minutes(1)),
+            ..Default::default()
+        });
+        assert!(future.is_empty());
+        assert_eq!(blotter.get("ORD-CL-3").unwrap().cl_ord_id, "CL-3");
+    }
+
+    #[test]
+    fn repeated_exec_ids_record_a_single_fill() {
+        let blotter = OrderBlotter::new();
+        let since = Utc::now();
+        blotter.on_new_order_single(&order("CL-1", "BTC-USD", "user001"));
+        blotter.apply(update("CL-1", "E-1", b'0', 0, 0));
+        blotter.apply(update("CL-1", "E-2", b'1', 4, 4));
+        blotter.apply(update("CL-1", "E-2", b'1', 4, 4));
+
+        let record = blotter.get("CL-1").unwrap();
+        assert_eq!(record.fills.len(), 1);
+        assert_eq!(record.state(), OrderState::PartiallyFilled);
+        assert_eq!(blotter.fills_since(since).len(), 1);
+    }
+
+    #[test]
+    fn finished_orders_are_evicted_after_their_retention() {
+        let blotter = blotter();
+        assert_eq!(blotter.evict_finished(), 0);
+
+        let blotter = OrderBlotter {
+            finished_retention: Duration::ZERO,
+            ..blotter
+        };
+        assert_eq!(blotter.evict_finished(), 2);
+        assert_eq!(keys(blotter.query(&BlotterQuery::default())), ["CL-1"]);
+        assert!(blotter.get("ORD-CL-3").is_none());
+        assert!(blotter.rejects_today().is_empty());
+    }
+}
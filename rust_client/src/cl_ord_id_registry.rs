@@ -0,0 +1,219 @@
+// src/cl_ord_id_registry.rs
+use anyhow::{bail, Context, Result};
+use chrono::{NaiveDate, Utc};
+use log::info;
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+struct RegistryState {
+    trading_day: NaiveDate,
+    ids: HashSet<String>,
+    // Append-only file of today's IDs, `None` for an in-memory registry
+    file: Option<File>,
+}
+
+/// ClOrdIDs issued during the current trading day, used to refuse duplicates.
+/// When opened on a directory, IDs are appended to one file per day so they
+/// survive restarts.
+pub struct ClOrdIdRegistry {
+    store_dir: Option<PathBuf>,
+    state: Mutex<RegistryState>,
+}
+
+impl Default for ClOrdIdRegistry {
+    fn default() -> Self {
+        Self::in_memory()
+    }
+}
+
+impl ClOrdIdRegistry {
+    /// Registry that forgets IDs on restart
+    pub fn in_memory() -> Self {
+        Self {
+            store_dir: None,
+            state: Mutex::new(RegistryState {
+                trading_day: Utc::now().date_naive(),
+                ids: HashSet::new(),
+                file: None,
+            }),
+        }
+    }
+
+    /// Registry persisted in `store_dir`, loading IDs already issued today
+    pub fn open<P: AsRef<Path>>(store_dir: P) -> Result<Self> {
+        let store_dir = store_dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&store_dir)
+            .with_context(|| format!("Failed to create ClOrdID store {:?}", store_dir))?;
+
+        let trading_day = Utc::now().date_naive();
+        let (ids, file) = Self::load_day(&store_dir, trading_day)?;
+        info!(
+            "✅ Loaded {} ClOrdIDs issued on {} from {:?}",
+            ids.len(),
+            trading_day,
+            store_dir
+        );
+
+        Ok(Self {
+            store_dir: Some(store_dir),
+            state: Mutex::new(RegistryState {
+                trading_day,
+                ids,
+                file: Some(file),
+            }),
+        })
+    }
+
+    fn day_file(store_dir: &Path, trading_day: NaiveDate) -> PathBuf {
+        store_dir.join(format!("cl_ord_ids_{}.log", trading_day.format("%Y%m%d")))
+    }
+
+    fn load_day(store_dir: &Path, trading_day: NaiveDate) -> Result<(HashSet<String>, File)> {
+        let path = Self::day_file(store_dir, trading_day);
+
+        let mut ids = HashSet::new();
+        if path.exists() {
+            let reader = BufReader::new(
+                File::open(&path).with_context(|| format!("Failed to open {:?}", path))?,
+            );
+            for line in reader.lines() {
+                let line = line.with_context(|| format!("Failed to read {:?}", path))?;
+                let id = line.trim();
+                if !id.is_empty() {
+                    ids.insert(id.to_string());
+                }
+            }
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open {:?} for writing", path))?;
+        Ok((ids, file))
+    }
+
+    /// Start a new trading day when the date has changed
+    fn roll_day(&self, state: &mut RegistryState) -> Result<()> {
+        let today = Utc::now().date_naive();
+        if state.trading_day == today {
+            return Ok(());
+        }
+
+        info!("🔄 New trading day {}: clearing issued ClOrdIDs", today);
+        state.trading_day = today;
+        match &self.store_dir {
+            Some(store_dir) => {
+                let (ids, file) = Self::load_day(store_dir, today)?;
+                state.ids = ids;
+                state.file = Some(file);
+            }
+            None => state.ids.clear(),
+        }
+        Ok(())
+    }
+
+    /// Record a new ClOrdID, failing if it was already issued today
+    pub fn register(&self, cl_ord_id: &str) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        self.roll_day(&mut state)?;
+
+        if state.ids.contains(cl_ord_id) {
+            bail!("Duplicate ClOrdID {}: already sent today", cl_ord_id);
+        }
+
+        if let Some(file) = state.file.as_mut() {
+            writeln!(file, "{}", cl_ord_id)
+                .and_then(|_| file.flush())
+                .with_context(|| format!("Failed to persist ClOrdID {}", cl_ord_id))?;
+        }
+        state.ids.insert(cl_ord_id.to_string());
+        Ok(())
+    }
+
+    /// Record several new ClOrdIDs at once: none is recorded if any of them was
+    /// already issued today or repeats within the set
+    pub fn register_all(&self, cl_ord_ids: &[&str]) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        self.roll_day(&mut state)?;
+
+        let mut batch = HashSet::new();
+        for cl_ord_id in cl_ord_ids {
+            if state.ids.contains(*cl_ord_id) {
+                bail!("Duplicate ClOrdID {}: already sent today", cl_ord_id);
+            }
+            if !batch.insert(*cl_ord_id) {
+                bail!("Duplicate ClOrdID {} within the batch", cl_ord_id);
+            }
+        }
+
+        if let Some(file) = state.file.as_mut() {
+            let lines: String = cl_ord_ids.iter().map(|id| format!("{}\n", id)).collect();
+            file.write_all(lines.as_bytes())
+                .and_then(|_| file.flush())
+                .with_context(|| format!("Failed to persist {} ClOrdIDs", cl_ord_ids.len()))?;
+        }
+        state.ids.extend(cl_ord_ids.iter().map(|id| id.to_string()));
+        Ok(())
+    }
+
+    /// Whether the ClOrdID was issued today
+    pub fn contains(&self, cl_ord_id: &str) -> bool {
+        self.state.lock().unwrap().ids.contains(cl_ord_id)
+    }
+
+    /// Number of ClOrdIDs issued today
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_refuses_ids_issued_today() {
+        let registry = ClOrdIdRegistry::in_memory();
+        registry.register("CL-1").unwrap();
+        assert!(registry.register("CL-1").is_err());
+        assert!(registry.contains("CL-1"));
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn register_all_records_nothing_when_one_id_is_a_duplicate() {
+        let registry = ClOrdIdRegistry::in_memory();
+        registry.register("CL-2").unwrap();
+
+        assert!(registry.register_all(&["CL-1", "CL-2", "CL-3"]).is_err());
+        assert!(!registry.contains("CL-1"));
+        assert!(!registry.contains("CL-3"));
+        assert!(registry.register_all(&["CL-1", "CL-1"]).is_err());
+        assert!(!registry.contains("CL-1"));
+
+        // The batch can be retried once the duplicate is dropped
+        registry.register_all(&["CL-1", "CL-3"]).unwrap();
+        assert_eq!(registry.len(), 3);
+    }
+
+    #[test]
+    fn register_all_persists_the_ids_for_a_restart() {
+        let store_dir =
+            std::env::temp_dir().join(format!("cl_ord_id_registry_{}", uuid::Uuid::new_v4()));
+        let registry = ClOrdIdRegistry::open(&store_dir).unwrap();
+        registry.register_all(&["CL-1", "CL-2"]).unwrap();
+        drop(registry);
+
+        let reopened = ClOrdIdRegistry::open(&store_dir).unwrap();
+        assert!(reopened.contains("CL-1") && reopened.contains("CL-2"));
+        std::fs::remove_dir_all(&store_dir).unwrap();
+    }
+}
@@ -1,43 +1,63 @@
 use anyhow::Context;
 use anyhow::Result;
 use log::info;
-use rustdds::no_key::DataReader;
 use rustdds::no_key::DataWriter;
 use rustdds::policy;
-use rustdds::CDRDeserializerAdapter;
 use rustdds::CDRSerializerAdapter;
 use rustdds::DomainParticipant;
 use rustdds::Publisher;
+use rustdds::QosPolicies;
 use rustdds::QosPolicyBuilder;
+use rustdds::Subscriber;
 use rustdds::TopicKind;
-use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::sleep;
 
-use crate::report::ExecutionReport;
+use crate::logout::Logout;
+use crate::order_cancel_request::{OrderCancelReplaceRequest, OrderCancelRequest};
+use crate::order_mass_cancel_request::OrderMassCancelRequest;
+use crate::order_mass_status_request::OrderMassStatusRequest;
+use crate::report::{
+    ExecutionReport, OrderCancelReject, OrderMassCancelReport, ReportDeserializerAdapter,
+    ReportMessage, ReportReader,
+};
 use crate::NewOrderSingle;
 use crate::OrderRequest;
 use crate::OrderResponseReport;
 
-const EXECUTION_REPORT_TOPIC_NAME: &str = "EXECUTION_REPORT_TOPIC";
-const EXECUTION_REPORT_TOPIC_TYPE_NAME: &str = "DistributedATS_ExecutionReport::ExecutionReport";
 const DEFAULT_DOMAIN_ID: u16 = 0;
 
-// Order Response Topic
-const ORDER_RESPONSE_TOPIC_NAME: &str = "order_response";
-const ORDER_RESPONSE_TOPIC_TYPE: &str = "OMS::OrderResponseReport";
-
 // Order Request Topic
 const ORDER_REQUEST_TOPIC_NAME: &str = "new_order_request";
 const ORDER_REQUEST_TOPIC_TYPE: &str = "OrderRequest";
 const NEW_ORDER_SINGLE_TOPIC_NAME: &str = "NEW_ORDER_SINGLE_TOPIC";
 const NEW_ORDER_SINGLE_TOPIC_TYPE: &str = "DistributedATS_NewOrderSingle::NewOrderSingle";
+const ORDER_CANCEL_REQUEST_TOPIC_NAME: &str = "ORDER_CANCEL_REQUEST_TOPIC";
+const ORDER_CANCEL_REQUEST_TOPIC_TYPE: &str =
+    "DistributedATS_OrderCancelRequest::OrderCancelRequest";
+const ORDER_CANCEL_REPLACE_REQUEST_TOPIC_NAME: &str = "ORDER_CANCEL_REPLACE_REQUEST_TOPIC";
+const ORDER_CANCEL_REPLACE_REQUEST_TOPIC_TYPE: &str =
+    "DistributedATS_OrderCancelReplaceRequest::OrderCancelReplaceRequest";
+const ORDER_MASS_STATUS_REQUEST_TOPIC_NAME: &str = "ORDER_MASS_STATUS_REQUEST_TOPIC";
+const ORDER_MASS_CANCEL_REQUEST_TOPIC_NAME: &str = "ORDER_MASS_CANCEL_REQUEST_TOPIC";
+const LOGOUT_TOPIC_NAME: &str = "LOGOUT_TOPIC";
 
 pub struct DdsInitializer {
-    pub execution_report_data_reader: DataReader<ExecutionReport>,
-    pub order_response_datareader: DataReader<OrderResponseReport>,
+    pub execution_report_data_reader: ReportReader<ExecutionReport>,
+    pub order_response_datareader: ReportReader<OrderResponseReport>,
+    pub order_cancel_reject_datareader: ReportReader<OrderCancelReject>,
+    pub order_mass_cancel_report_datareader: ReportReader<OrderMassCancelReport>,
     pub order_request_data_writer: DataWriter<OrderRequest>,
     pub new_order_single_data_writer: DataWriter<NewOrderSingle>,
+    pub order_cancel_request_data_writer: DataWriter<OrderCancelRequest>,
+    pub order_cancel_replace_request_data_writer: DataWriter<OrderCancelReplaceRequest>,
+    pub order_mass_status_request_data_writer: DataWriter<OrderMassStatusRequest>,
+    pub order_mass_cancel_request_data_writer: DataWriter<OrderMassCancelRequest>,
+    pub logout_data_writer: DataWriter<Logout>,
+    // Kept so shutdown can drop them after the readers and writers above
+    pub publisher: Publisher,
+    pub subscriber: Subscriber,
+    pub participant: DomainParticipant,
 }
 
 impl DdsInitializer {
@@ -65,39 +85,17 @@ impl DdsInitializer {
             .create_publisher(&qos)
             .context("failed to create Publisher")?;
 
-        let execution_report_topic = participant
-            .create_topic(
-                EXECUTION_REPORT_TOPIC_NAME.to_string(),
-                EXECUTION_REPORT_TOPIC_TYPE_NAME.to_string(), // Uses exact C++ type name
-                &qos,
-                TopicKind::NoKey, // Matches C++ implementation
-            )
-            .context("Failed to create ExecutionReport topic following OMS architecture")?;
-
-        let execution_report_data_reader: DataReader<ExecutionReport> = subscriber
-            .create_datareader_no_key::<ExecutionReport, CDRDeserializerAdapter<ExecutionReport>>(
-                &execution_report_topic,
-                None,
-            )
-            .context("Failed to create ExecutionReport reader with FastDDS compatibility")?;
+        let execution_report_data_reader =
+            create_report_reader::<ExecutionReport>(&participant, &subscriber, &qos)?;
 
         sleep(Duration::from_millis(500)).await;
 
-        let order_response_topic = participant
-            .create_topic(
-                ORDER_RESPONSE_TOPIC_NAME.to_string(),
-                ORDER_RESPONSE_TOPIC_TYPE.to_string(), // Uses exact C++ type name
-                &qos,
-                TopicKind::NoKey, // Matches C++ implementation
-            )
-            .context("Failed to create Order Response topic following OMS architecture")?;
-
-        let order_response_reader = subscriber
-            .create_datareader_no_key::<OrderResponseReport, CDRDeserializerAdapter<OrderResponseReport>>(
-                &order_response_topic,
-                None,
-            )
-            .context("Failed to create OrderResponse reader with FastDDS compatibility")?;
+        let order_response_reader =
+            create_report_reader::<OrderResponseReport>(&participant, &subscriber, &qos)?;
+        let order_cancel_reject_reader =
+            create_report_reader::<OrderCancelReject>(&participant, &subscriber, &qos)?;
+        let order_mass_cancel_report_reader =
+            create_report_reader::<OrderMassCancelReport>(&participant, &subscriber, &qos)?;
 
         info!("✅ Created OrderResponse listener components following OMS guidelines");
 
@@ -137,11 +135,132 @@ impl DdsInitializer {
             )
             .context("Failed to create NewOrderSingle writer")?;
 
+        // Cancel and cancel/replace requests to the matching engine
+        let order_cancel_request_topic = participant
+            .create_topic(
+                ORDER_CANCEL_REQUEST_TOPIC_NAME.to_string(),
+                ORDER_CANCEL_REQUEST_TOPIC_TYPE.to_string(),
+                &qos,
+                TopicKind::NoKey,
+            )
+            .context("Failed to create OrderCancelRequest topic")?;
+
+        let order_cancel_request_writer = publisher
+            .create_datawriter_no_key::<OrderCancelRequest, CDRSerializerAdapter<OrderCancelRequest>>(
+                &order_cancel_request_topic,
+                Some(qos.clone()),
+            )
+            .context("Failed to create OrderCancelRequest writer")?;
+
+        let order_cancel_replace_request_topic = participant
+            .create_topic(
+                ORDER_CANCEL_REPLACE_REQUEST_TOPIC_NAME.to_string(),
+                ORDER_CANCEL_REPLACE_REQUEST_TOPIC_TYPE.to_string(),
+                &qos,
+                TopicKind::NoKey,
+            )
+            .context("Failed to create OrderCancelReplaceRequest topic")?;
+
+        let order_cancel_replace_request_writer = publisher
+            .create_datawriter_no_key::<
+                OrderCancelReplaceRequest,
+                CDRSerializerAdapter<OrderCancelReplaceRequest>,
+            >(&order_cancel_replace_request_topic, Some(qos.clone()))
+            .context("Failed to create OrderCancelReplaceRequest writer")?;
+
+        // Status requests filling inbound sequence gaps
+        let order_mass_status_request_topic = participant
+            .create_topic(
+                ORDER_MASS_STATUS_REQUEST_TOPIC_NAME.to_string(),
+                OrderMassStatusRequest::type_name().to_string(),
+                &qos,
+                TopicKind::NoKey,
+            )
+            .context("Failed to create OrderMassStatusRequest topic")?;
+
+        let order_mass_status_request_writer = publisher
+            .create_datawriter_no_key::<
+                OrderMassStatusRequest,
+                CDRSerializerAdapter<OrderMassStatusRequest>,
+            >(&order_mass_status_request_topic, Some(qos.clone()))
+            .context("Failed to create OrderMassStatusRequest writer")?;
+
+        // Mass cancel and logout sent on shutdown
+        let order_mass_cancel_request_topic = participant
+            .create_topic(
+                ORDER_MASS_CANCEL_REQUEST_TOPIC_NAME.to_string(),
+                OrderMassCancelRequest::type_name().to_string(),
+                &qos,
+                TopicKind::NoKey,
+            )
+            .context("Failed to create OrderMassCancelRequest topic")?;
+
+        let order_mass_cancel_request_writer = publisher
+            .create_datawriter_no_key::<
+                OrderMassCancelRequest,
+                CDRSerializerAdapter<OrderMassCancelRequest>,
+            >(&order_mass_cancel_request_topic, Some(qos.clone()))
+            .context("Failed to create OrderMassCancelRequest writer")?;
+
+        let logout_topic = participant
+            .create_topic(
+                LOGOUT_TOPIC_NAME.to_string(),
+                Logout::type_name().to_string(),
+                &qos,
+                TopicKind::NoKey,
+            )
+            .context("Failed to create Logout topic")?;
+
+        let logout_writer = publisher
+            .create_datawriter_no_key::<Logout, CDRSerializerAdapter<Logout>>(
+                &logout_topic,
+                Some(qos.clone()),
+            )
+            .context("Failed to create Logout writer")?;
+
         Ok(Self {
-            execution_report_data_reader: execution_report_data_reader,
+            execution_report_data_reader,
             order_response_datareader: order_response_reader,
+            order_cancel_reject_datareader: order_cancel_reject_reader,
+            order_mass_cancel_report_datareader: order_mass_cancel_report_reader,
             order_request_data_writer: order_request_to_oms_writer,
             new_order_single_data_writer: new_order_single_writer,
+            order_cancel_request_data_writer: order_cancel_request_writer,
+            order_cancel_replace_request_data_writer: order_cancel_replace_request_writer,
+            order_mass_status_request_data_writer: order_mass_status_request_writer,
+            order_mass_cancel_request_data_writer: order_mass_cancel_request_writer,
+            logout_data_writer: logout_writer,
+            publisher,
+            subscriber,
+            participant,
         })
     }
 }
+
+/// Create the topic and no-key reader of a report type, named as in the C++ OMS
+fn create_report_reader<T: ReportMessage>(
+    participant: &DomainParticipant,
+    subscriber: &Subscriber,
+    qos: &QosPolicies,
+) -> Result<ReportReader<T>> {
+    let topic = participant
+        .create_topic(
+            T::TOPIC_NAME.to_string(),
+            T::TYPE_NAME.to_string(), // Uses exact C++ type name
+            qos,
+            TopicKind::NoKey, // Matches C++ implementation
+        )
+        .with_context(|| format!("Failed to create {} topic", T::NAME))?;
+
+    let reader = subscriber
+        .create_datareader_no_key::<T, ReportDeserializerAdapter<T>>(&topic, None)
+        .with_context(|| {
+            format!(
+                "Failed to create {} reader with FastDDS compatibility",
+                T::NAME
+            )
+        })?;
+
+    info!("✅ Created {} reader on topic '{}'", T::NAME, T::TOPIC_NAME);
+    Ok(reader)
+}
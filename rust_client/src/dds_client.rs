@@ -1,15 +1,64 @@
 // src/dds_client.rs
-use crate::new_order_single::{generate_unique_cl_ord_id, NewOrderSingle};
-use crate::order_message::{generate_unique_order_id, OrderRequest, OrderSide, OrderType};
-use anyhow::{Context, Result};
-use log::info;
+use crate::account::{Account, AccountContext};
+use crate::batch::{BatchHandle, BatchMode, BatchOutcome, OrderSpec};
+use crate::blotter::{BlotterQuery, OrderBlotter};
+use crate::cl_ord_id_registry::ClOrdIdRegistry;
+use crate::gateway::Amendment;
+use crate::logout::Logout;
+use crate::new_order_single::{Header, NewOrderSingle};
+use crate::order_cancel_request::{OrderCancelReplaceRequest, OrderCancelRequest};
+use crate::order_event::OrderEvent;
+use crate::order_handle::{OrderHandle, OrderTracker};
+use crate::order_mass_cancel_request::OrderMassCancelRequest;
+use crate::order_message::{OrderRequest, OrderSide};
+use crate::positions::PositionKeeper;
+use crate::replace_book::ReplaceBook;
+use crate::risk::{validate_order_parameters, RiskEngine, RiskOrder};
+use crate::sequence::{SequenceStore, SessionId};
+use crate::throttle::{MessageKind, RateLimiter};
+use crate::utils::order_id_generator::OrderIdGenerator;
+use anyhow::{bail, Context, Result};
+use log::{info, warn};
 use rustdds::no_key::DataWriter;
-use serde_json;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::broadcast;
 
 /// Rust DDS client for communicating with Order Management Service using RustDDS
 pub struct OrderDdsClient {
     order_request_writer: DataWriter<OrderRequest>,
     new_order_single_writer: DataWriter<NewOrderSingle>,
+    order_cancel_request_writer: DataWriter<OrderCancelRequest>,
+    order_cancel_replace_request_writer: DataWriter<OrderCancelReplaceRequest>,
+    // Shutdown messages, individual cancels and no logout when unset
+    order_mass_cancel_request_writer: Option<DataWriter<OrderMassCancelRequest>>,
+    logout_writer: Option<DataWriter<Logout>>,
+    // Cleared on shutdown: new orders are refused, cancels still go out
+    accepting: AtomicBool,
+    // Correlates reports from the listeners with in-flight orders
+    order_tracker: Arc<OrderTracker>,
+    // Single record per order sent by this client
+    order_blotter: Arc<OrderBlotter>,
+    // Pre-trade checks mirroring the OMS RiskValidator, parameter checks only when unset
+    risk_engine: Option<Arc<RiskEngine>>,
+    // Outbound token buckets, no throttling when unset
+    rate_limiter: Option<Arc<RateLimiter>>,
+    // ClOrdIDs issued today, duplicates are refused
+    cl_ord_id_registry: Arc<ClOrdIdRegistry>,
+    // IDs for orders created by the convenience methods
+    id_generator: Arc<OrderIdGenerator>,
+    // Outbound MsgSeqNum per FIX session
+    sequence_store: Arc<SequenceStore>,
+    // Account used by the convenience send methods
+    default_account: Account,
+    // Fills per account, for the position views of account contexts
+    position_keeper: Option<Arc<PositionKeeper>>,
+    // Messages sent today by ClOrdID for `resend`, with whether a write succeeded
+    sent_messages: Mutex<HashMap<String, (OrderSpec, bool)>>,
+    // Pending and confirmed replacements of amended orders
+    replace_book: Arc<ReplaceBook>,
 }
 
 impl OrderDdsClient {
@@ -17,20 +66,183 @@ impl OrderDdsClient {
     pub async fn new(
         order_request_writer: DataWriter<OrderRequest>,
         new_order_single_writer: DataWriter<NewOrderSingle>,
+        order_cancel_request_writer: DataWriter<OrderCancelRequest>,
+        order_cancel_replace_request_writer: DataWriter<OrderCancelReplaceRequest>,
     ) -> Result<Self> {
         Ok(Self {
             order_request_writer,
-
             new_order_single_writer,
+            order_cancel_request_writer,
+            order_cancel_replace_request_writer,
+            order_mass_cancel_request_writer: None,
+            logout_writer: None,
+            accepting: AtomicBool::new(true),
+            order_tracker: Arc::new(OrderTracker::new()),
+            order_blotter: Arc::new(OrderBlotter::new()),
+            risk_engine: None,
+            rate_limiter: None,
+            cl_ord_id_registry: Arc::new(ClOrdIdRegistry::in_memory()),
+            id_generator: Arc::new(OrderIdGenerator::default()),
+            sequence_store: Arc::new(SequenceStore::default()),
+            default_account: Account::default(),
+            position_keeper: None,
+            sent_messages: Mutex::new(HashMap::new()),
+            replace_book: Arc::new(ReplaceBook::new()),
         })
     }
 
+    /// Run every outgoing order through the given risk engine before it is published.
+    /// NewOrderSingles of the default account are checked under its OMS user.
+    pub fn with_risk_engine(mut self, risk_engine: Arc<RiskEngine>) -> Self {
+        risk_engine.add_alias(
+            &self.default_account.source_user,
+            &self.default_account.user_id,
+        );
+        self.risk_engine = Some(risk_engine);
+        self
+    }
+
+    /// Throttle outgoing messages with the given rate limiter
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Check ClOrdIDs against the given registry, e.g. one persisted across restarts
+    pub fn with_cl_ord_id_registry(mut self, registry: Arc<ClOrdIdRegistry>) -> Self {
+        self.cl_ord_id_registry = registry;
+        self
+    }
+
+    /// Generate ClOrdIDs and OrderIDs with the given generator
+    pub fn with_id_generator(mut self, id_generator: Arc<OrderIdGenerator>) -> Self {
+        self.id_generator = id_generator;
+        self
+    }
+
+    /// Generator used for ClOrdIDs and OrderIDs of new orders
+    pub fn id_generator(&self) -> Arc<OrderIdGenerator> {
+        Arc::clone(&self.id_generator)
+    }
+
+    /// Allocate outbound MsgSeqNums from the given store, e.g. one persisted on disk
+    pub fn with_sequence_store(mut self, sequence_store: Arc<SequenceStore>) -> Self {
+        self.sequence_store = sequence_store;
+        self
+    }
+
+    /// Sequence store to be fed with inbound reports for gap tracking
+    pub fn sequence_store(&self) -> Arc<SequenceStore> {
+        Arc::clone(&self.sequence_store)
+    }
+
+    /// Cancel open orders on shutdown with one OrderMassCancelRequest per session
+    pub fn with_mass_cancel_writer(mut self, writer: DataWriter<OrderMassCancelRequest>) -> Self {
+        self.order_mass_cancel_request_writer = Some(writer);
+        self
+    }
+
+    /// Log out of the matching engine sessions on shutdown
+    pub fn with_logout_writer(mut self, writer: DataWriter<Logout>) -> Self {
+        self.logout_writer = Some(writer);
+        self
+    }
+
+    /// Refuse new orders, resends and amends from now on; cancels still go out
+    pub fn stop_accepting(&self) {
+        if self.accepting.swap(false, Ordering::SeqCst) {
+            info!("🚧 Order client no longer accepting new orders");
+        }
+    }
+
+    pub fn is_accepting(&self) -> bool {
+        self.accepting.load(Ordering::SeqCst)
+    }
+
+    fn ensure_accepting(&self) -> Result<()> {
+        if !self.is_accepting() {
+            bail!("Order client is shutting down, no new orders accepted");
+        }
+        Ok(())
+    }
+
+    /// Account the convenience send methods trade for
+    pub fn with_default_account(mut self, account: Account) -> Self {
+        if let Some(risk_engine) = &self.risk_engine {
+            risk_engine.add_alias(&account.source_user, &account.user_id);
+        }
+        self.default_account = account;
+        self
+    }
+
+    /// Position keeper backing the position views of account contexts
+    pub fn with_position_keeper(mut self, position_keeper: Arc<PositionKeeper>) -> Self {
+        self.position_keeper = Some(position_keeper);
+        self
+    }
+
+    pub fn position_keeper(&self) -> Option<Arc<PositionKeeper>> {
+        self.position_keeper.clone()
+    }
+
+    /// Trading context for one account sharing this client
+    pub fn account(&self, account: Account) -> AccountContext<'_> {
+        AccountContext::new(self, account)
+    }
+
+    /// Trading context of the default account
+    pub fn default_account(&self) -> AccountContext<'_> {
+        self.account(self.default_account.clone())
+    }
+
+    /// Next ID from the client's generator, e.g. for an `OrderBuilder`
+    pub fn next_cl_ord_id(&self) -> Result<String> {
+        self.id_generator.next_id()
+    }
+
+    /// Rate limiter used for outgoing messages, e.g. for its throttling stats
+    pub fn rate_limiter(&self) -> Option<Arc<RateLimiter>> {
+        self.rate_limiter.clone()
+    }
+
+    /// Risk engine to be fed by the report listeners so usage is released
+    pub fn risk_engine(&self) -> Option<Arc<RiskEngine>> {
+        self.risk_engine.clone()
+    }
+
+    /// Merged stream of OMS and matching engine reports fed in by the listeners
+    pub fn order_events(&self) -> broadcast::Receiver<OrderEvent> {
+        self.order_tracker.subscribe()
+    }
+
+    /// Tracker to be fed by the report listeners so order handles resolve
+    pub fn order_tracker(&self) -> Arc<OrderTracker> {
+        Arc::clone(&self.order_tracker)
+    }
+
+    /// Blotter to be fed by the report listeners for order queries
+    pub fn order_blotter(&self) -> Arc<OrderBlotter> {
+        Arc::clone(&self.order_blotter)
+    }
+
+    /// Replacements of amended orders, to be fed by the report listeners so a
+    /// replacement goes live once it is reported Replaced
+    pub fn replace_book(&self) -> Arc<ReplaceBook> {
+        Arc::clone(&self.replace_book)
+    }
+
     /// Send order request to OMS with delivery confirmation
-    pub async fn send_order(&self, order: OrderRequest) -> Result<()> {
+    pub async fn send_order(&self, order: OrderRequest) -> Result<OrderHandle> {
+        self.ensure_accepting()?;
+
         // Check for active OMS subscribers
 
+        self.throttle(MessageKind::Order, &order.user_id, &order.symbol)
+            .await?;
+
         // Validate order data before sending
-        self.validate_order(&order)?;
+        self.validate_order(&RiskOrder::from(&order))?;
+        self.register_cl_ord_id(&order.order_id)?;
 
         // Serialize order to JSON for RustDDS
         let order_json =
@@ -38,121 +250,567 @@ impl OrderDdsClient {
 
         info!("📝 Order JSON: {}", order_json);
 
-        let order_clone = order.clone();
+        self.publish(OrderSpec::OrderRequest(order), true)
+    }
+
+    /// Send a basket of orders. Every order is throttled and checked up front; in
+    /// `AllOrNothing` mode nothing is written unless all of them pass and all their
+    /// ClOrdIDs register. The orders are then written back to back, without the
+    /// per-order JSON log of `send_order`. Write failures are per order in both modes:
+    /// they show in `results` while the orders written before them stay sent.
+    pub async fn send_batch(
+        &self,
+        orders: Vec<OrderSpec>,
+        mode: BatchMode,
+    ) -> Result<BatchOutcome> {
+        self.ensure_accepting()?;
+        let started = Instant::now();
+        let mut seen = HashSet::new();
+        let mut checked = Vec::with_capacity(orders.len());
+        for order in orders {
+            checked.push(self.check_batch_order(order, &mut seen).await);
+        }
+
+        let failures: Vec<String> = checked
+            .iter()
+            .filter_map(|result| result.as_ref().err())
+            .map(|e| format!("{:#}", e))
+            .collect();
+        if mode == BatchMode::AllOrNothing && !failures.is_empty() {
+            for order in checked.iter().flatten() {
+                self.release_order(order.cl_ord_id());
+            }
+            warn!(
+                "❌ Batch of {} orders rejected: {} failed validation",
+                checked.len(),
+                failures.len()
+            );
+            bail!(
+                "Batch of {} orders rejected, {} failed validation: {}",
+                checked.len(),
+                failures.len(),
+                failures.join("; ")
+            );
+        }
+
+        // Register every ClOrdID before the first write so a duplicate cannot
+        // leave the batch half sent
+        if mode == BatchMode::AllOrNothing {
+            let cl_ord_ids: Vec<&str> = checked
+                .iter()
+                .flatten()
+                .map(|order| order.cl_ord_id())
+                .collect();
+            if let Err(e) = self.cl_ord_id_registry.register_all(&cl_ord_ids) {
+                warn!("❌ {:#}", e);
+                for cl_ord_id in &cl_ord_ids {
+                    self.release_order(cl_ord_id);
+                }
+                bail!(
+                    "Batch of {} orders rejected before sending: {:#}",
+                    checked.len(),
+                    e
+                );
+            }
+        }
+
+        let mut results = Vec::with_capacity(checked.len());
+        let mut handles = Vec::new();
+        for order in checked {
+            let result = order.and_then(|order| {
+                if mode == BatchMode::BestEffort {
+                    self.register_cl_ord_id(order.cl_ord_id())?;
+                }
+                self.publish(order, true)
+            });
+            if let Ok(handle) = &result {
+                handles.push(handle.clone());
+            }
+            results.push(result);
+        }
 
-        // Publish order to OMS using RustDDS
-        self.order_request_writer
-            .write(order_clone, None)
-            .context("Failed to write order to DDS topic")?;
+        info!(
+            "📦 Batch sent: {}/{} orders in {:?}",
+            handles.len(),
+            results.len(),
+            started.elapsed()
+        );
+        Ok(BatchOutcome {
+            results,
+            handle: BatchHandle::new(handles),
+        })
+    }
 
+    /// Throttle and pre-trade check one order of a batch, refusing ClOrdIDs issued
+    /// today or earlier in the same batch
+    async fn check_batch_order(
+        &self,
+        order: OrderSpec,
+        seen: &mut HashSet<String>,
+    ) -> Result<OrderSpec> {
+        let cl_ord_id = order.cl_ord_id().to_string();
+        if !seen.insert(cl_ord_id.clone()) {
+            bail!("Duplicate ClOrdID {} within the batch", cl_ord_id);
+        }
+        if self.cl_ord_id_registry.contains(&cl_ord_id) {
+            bail!("Duplicate ClOrdID {}: already sent today", cl_ord_id);
+        }
+
+        let risk_order = match &order {
+            OrderSpec::OrderRequest(order) => RiskOrder::from(order),
+            OrderSpec::NewOrderSingle(order) => RiskOrder::from(order),
+        };
+        self.throttle(MessageKind::Order, &risk_order.user_id, &risk_order.symbol)
+            .await?;
+        self.validate_order(&risk_order)?;
+        Ok(order)
+    }
+
+    /// Refuse a ClOrdID already issued today, giving back its risk reservation
+    fn register_cl_ord_id(&self, cl_ord_id: &str) -> Result<()> {
+        if let Err(e) = self.cl_ord_id_registry.register(cl_ord_id) {
+            warn!("❌ {:#}", e);
+            self.release_order(cl_ord_id);
+            return Err(e);
+        }
         Ok(())
     }
 
-    /// Validate order parameters following financial trading rules
-    fn validate_order(&self, order: &OrderRequest) -> Result<()> {
-        if order.order_id.is_empty() {
-            return Err(anyhow::anyhow!("Order ID cannot be empty"));
+    /// Write a message to its DDS topic. On first transmission the order is tracked,
+    /// added to the blotter and kept for `resend`; all of that is undone if the write fails
+    /// except the stored copy, so the order can still be resent.
+    fn publish(&self, mut message: OrderSpec, first_transmission: bool) -> Result<OrderHandle> {
+        let cl_ord_id = message.cl_ord_id().to_string();
+
+        // Retransmissions of a published message keep its MsgSeqNum
+        if let (OrderSpec::NewOrderSingle(order), true) = (&mut message, first_transmission) {
+            match self.next_outbound_seq_num(&order.fix_header) {
+                Ok(msg_seq_num) => order.fix_header.msg_seq_num = msg_seq_num,
+                Err(e) => {
+                    self.release_order(&cl_ord_id);
+                    return Err(e);
+                }
+            }
+        }
+
+        // Register before publishing so an early report cannot be missed; a
+        // retransmission keeps the existing registration and its updates
+        let handle = if first_transmission {
+            self.order_tracker.track(&cl_ord_id)
+        } else {
+            self.order_tracker.watch(&cl_ord_id)
+        };
+        if first_transmission {
+            match &message {
+                OrderSpec::OrderRequest(order) => self.order_blotter.on_order_request(order),
+                OrderSpec::NewOrderSingle(order) => self.order_blotter.on_new_order_single(order),
+            }
+            self.sent_messages
+                .lock()
+                .unwrap()
+                .insert(cl_ord_id.clone(), (message.clone(), false));
+        }
+
+        // Publish order using RustDDS
+        let result = match message {
+            OrderSpec::OrderRequest(order) => self
+                .order_request_writer
+                .write(order, None)
+                .context("Failed to write order to DDS topic"),
+            OrderSpec::NewOrderSingle(order) => self
+                .new_order_single_writer
+                .write(order, None)
+                .context("Failed to transmit NewOrderSingle to matching engine"),
+        };
+
+        if let Err(e) = result {
+            if first_transmission {
+                self.order_tracker.untrack(&cl_ord_id);
+                self.order_blotter.remove(&cl_ord_id);
+                self.release_order(&cl_ord_id);
+            }
+            return Err(e);
+        }
+
+        if let Some((_, published)) = self.sent_messages.lock().unwrap().get_mut(&cl_ord_id) {
+            *published = true;
+        }
+        Ok(handle)
+    }
+
+    /// Republish an order sent earlier today, e.g. after a failed write. A published
+    /// NewOrderSingle goes out again unchanged under its original MsgSeqNum, so
+    /// downstream dedupes it by sequence number; the shared header has no PossDupFlag.
+    /// OrderRequest carries no header to mark a duplicate with, so it is only resent
+    /// if its first write never went out. Returns a handle for the order.
+    pub async fn resend(&self, cl_ord_id: &str) -> Result<OrderHandle> {
+        self.ensure_accepting()?;
+        let (message, published) = self
+            .sent_messages
+            .lock()
+            .unwrap()
+            .get(cl_ord_id)
+            .cloned()
+            .with_context(|| format!("Unknown ClOrdID {}: nothing to resend", cl_ord_id))?;
+
+        let risk_order = match &message {
+            OrderSpec::OrderRequest(_) if published => {
+                bail!(
+                    "Order {} was already published: an OrderRequest resend would book it twice",
+                    cl_ord_id
+                );
+            }
+            OrderSpec::OrderRequest(order) => RiskOrder::from(order),
+            OrderSpec::NewOrderSingle(order) => RiskOrder::from(order),
+        };
+
+        self.throttle(MessageKind::Order, &risk_order.user_id, &risk_order.symbol)
+            .await?;
+
+        // An order whose first write failed had its risk usage given back
+        if !published {
+            self.validate_order(&risk_order)?;
         }
 
-        if order.symbol.is_empty() {
-            return Err(anyhow::anyhow!("Symbol cannot be empty"));
+        info!(
+            "🔁 Resending order {} (previously published: {})",
+            cl_ord_id, published
+        );
+        if let OrderSpec::NewOrderSingle(order) = &message {
+            self.log_new_order_single_transmission(order);
+        }
+
+        self.publish(message, !published)
+    }
+
+    /// Request cancellation of a NewOrderSingle sent earlier today. Returns the
+    /// handle of the original order, which resolves with the Cancelled report.
+    pub async fn cancel(&self, orig_cl_ord_id: &str) -> Result<OrderHandle> {
+        let order = self.sent_new_order_single(orig_cl_ord_id)?;
+        self.throttle(MessageKind::Cancel, &order.dats_source_user, &order.symbol)
+            .await?;
+        self.send_cancel(orig_cl_ord_id, &order)
+    }
+
+    /// Write the OrderCancelRequest of an order, without throttling
+    fn send_cancel(&self, orig_cl_ord_id: &str, order: &NewOrderSingle) -> Result<OrderHandle> {
+        let cl_ord_id = self.id_generator.next_id()?;
+        self.cl_ord_id_registry.register(&cl_ord_id)?;
+
+        let mut request = OrderCancelRequest::for_order(cl_ord_id, order);
+        request.fix_header.msg_seq_num = self.next_outbound_seq_num(&request.fix_header)?;
+
+        let handle = self.order_tracker.watch(orig_cl_ord_id);
+        info!(
+            "🚫 Cancelling order {}: ClOrdID={}, Symbol={}",
+            orig_cl_ord_id, request.cl_ord_id, request.symbol
+        );
+        self.order_cancel_request_writer
+            .write(request, None)
+            .context("Failed to transmit OrderCancelRequest to matching engine")?;
+        Ok(handle)
+    }
+
+    /// Request a change of quantity, price, stop price or text of a NewOrderSingle
+    /// sent earlier today. The amended terms get parameter checks only: pre-trade
+    /// limits were reserved for the original order. The order keeps its terms until
+    /// the replacement is reported Replaced, and one replace may be pending at a time.
+    /// Returns the handle of the original order; once replaced, later cancels and
+    /// amends may use either ClOrdID.
+    pub async fn amend(&self, orig_cl_ord_id: &str, amendment: &Amendment) -> Result<OrderHandle> {
+        if amendment.is_empty() {
+            bail!("Amendment of order {} changes nothing", orig_cl_ord_id);
         }
+        self.ensure_accepting()?;
+
+        let order = self.sent_new_order_single(orig_cl_ord_id)?;
+        self.throttle(MessageKind::Cancel, &order.dats_source_user, &order.symbol)
+            .await?;
+
+        let cl_ord_id = self.id_generator.next_id()?;
+        let mut replacement = order.clone();
+        replacement.cl_ord_id = cl_ord_id.clone();
+        amendment.apply_to(&mut replacement);
+        validate_order_parameters(&RiskOrder::from(&replacement)).map_err(|reason| {
+            warn!(
+                "❌ Amendment of order {} rejected: {}",
+                orig_cl_ord_id, reason
+            );
+            anyhow::anyhow!(reason)
+        })?;
+        self.cl_ord_id_registry.register(&cl_ord_id)?;
+
+        let mut request = OrderCancelReplaceRequest::for_order(cl_ord_id.clone(), &order);
+        request.order_qty = replacement.order_qty;
+        request.price = replacement.price;
+        request.stop_px = replacement.stop_px;
+        request.text = replacement.text.clone();
+        request.fix_header.msg_seq_num = self.next_outbound_seq_num(&request.fix_header)?;
+
+        // The order keeps its current terms until the replacement is reported Replaced
+        let handle = self.order_tracker.watch(orig_cl_ord_id);
+        self.replace_book
+            .add_pending(&order.cl_ord_id, replacement)?;
+        info!(
+            "✏️ Amending order {}: ClOrdID={}, Qty={}, Price={}, StopPx={}",
+            orig_cl_ord_id, cl_ord_id, request.order_qty, request.price, request.stop_px
+        );
+        if let Err(e) = self
+            .order_cancel_replace_request_writer
+            .write(request, None)
+            .context("Failed to transmit OrderCancelReplaceRequest to matching engine")
+        {
+            self.replace_book.remove_pending(&order.cl_ord_id);
+            return Err(e);
+        }
+        Ok(handle)
+    }
 
-        if order.quantity <= 0.0 {
-            return Err(anyhow::anyhow!(
-                "Quantity must be positive: {}",
-                order.quantity
-            ));
+    /// Request cancellation of every open NewOrderSingle, unthrottled as this runs
+    /// on shutdown. Sends one OrderMassCancelRequest per session, or individual
+    /// cancels without a mass cancel writer. A failed request is logged and the
+    /// others still go out. Returns the handles of the orders.
+    pub async fn cancel_open_orders(&self) -> Result<Vec<OrderHandle>> {
+        let open_orders = self.order_blotter.query(&BlotterQuery {
+            open_only: true,
+            ..Default::default()
+        });
+
+        let mut orders = Vec::new();
+        for record in &open_orders {
+            match self.sent_new_order_single(&record.cl_ord_id) {
+                Ok(order) => orders.push((record.cl_ord_id.clone(), order)),
+                Err(e) => warn!("⚠️ Open order {} not cancelled: {}", record.cl_ord_id, e),
+            }
         }
 
-        if order.order_type == OrderType::LIMIT && order.price <= 0.0 {
-            return Err(anyhow::anyhow!(
-                "Limit orders must have positive price: {}",
-                order.price
-            ));
+        let Some(writer) = &self.order_mass_cancel_request_writer else {
+            let mut handles = Vec::new();
+            for (cl_ord_id, order) in &orders {
+                match self.send_cancel(cl_ord_id, order) {
+                    Ok(handle) => handles.push(handle),
+                    Err(e) => warn!("⚠️ Failed to cancel open order {}: {:#}", cl_ord_id, e),
+                }
+            }
+            return Ok(handles);
+        };
+
+        let mut sessions = HashSet::new();
+        for (_, order) in &orders {
+            let route = Self::session_route(order);
+            if !sessions.insert(route) {
+                continue;
+            }
+            if let Err(e) = self.send_mass_cancel(writer, order) {
+                warn!(
+                    "⚠️ Failed to mass cancel orders of {} -> {}: {:#}",
+                    order.dats_source_user, order.dats_destination, e
+                );
+            }
         }
 
-        if order.user_id.is_empty() {
-            return Err(anyhow::anyhow!("User ID cannot be empty"));
+        Ok(orders
+            .iter()
+            .map(|(cl_ord_id, _)| self.order_tracker.watch(cl_ord_id))
+            .collect())
+    }
+
+    /// Write an OrderMassCancelRequest on the session of an order
+    fn send_mass_cancel(
+        &self,
+        writer: &DataWriter<OrderMassCancelRequest>,
+        order: &NewOrderSingle,
+    ) -> Result<()> {
+        let cl_ord_id = self.id_generator.next_id()?;
+        self.cl_ord_id_registry.register(&cl_ord_id)?;
+
+        let mut request = OrderMassCancelRequest::all_orders(cl_ord_id);
+        request.dats_source = order.dats_source.clone();
+        request.dats_destination = order.dats_destination.clone();
+        request.dats_source_user = order.dats_source_user.clone();
+        request.dats_destination_user = order.dats_destination_user.clone();
+        request.fix_header.sender_comp_id = order.fix_header.sender_comp_id.clone();
+        request.fix_header.target_comp_id = order.fix_header.target_comp_id.clone();
+        request.fix_header.msg_seq_num = self.next_outbound_seq_num(&request.fix_header)?;
+
+        info!(
+            "🧹 Mass cancelling orders of {} -> {}: ClOrdID={}",
+            request.dats_source_user, request.dats_destination, request.cl_ord_id
+        );
+        writer
+            .write(request, None)
+            .context("Failed to transmit OrderMassCancelRequest to matching engine")
+    }
+
+    /// Send a Logout on every session a NewOrderSingle went out on today.
+    /// Returns the number of sessions logged out of.
+    pub fn logout(&self, text: &str) -> Result<usize> {
+        let Some(writer) = &self.logout_writer else {
+            bail!("No Logout writer configured");
+        };
+
+        let orders: Vec<NewOrderSingle> = self
+            .sent_messages
+            .lock()
+            .unwrap()
+            .values()
+            .filter_map(|(message, published)| match message {
+                OrderSpec::NewOrderSingle(order) if *published => Some(order.clone()),
+                _ => None,
+            })
+            .collect();
+
+        let mut sessions = HashSet::new();
+        for order in &orders {
+            if !sessions.insert(Self::session_route(order)) {
+                continue;
+            }
+
+            let mut logout = Logout::new(text);
+            logout.dats_source = order.dats_source.clone();
+            logout.dats_destination = order.dats_destination.clone();
+            logout.dats_source_user = order.dats_source_user.clone();
+            logout.dats_destination_user = order.dats_destination_user.clone();
+            logout.fix_header.sender_comp_id = order.fix_header.sender_comp_id.clone();
+            logout.fix_header.target_comp_id = order.fix_header.target_comp_id.clone();
+            logout.fix_header.msg_seq_num = self.next_outbound_seq_num(&logout.fix_header)?;
+
+            info!(
+                "👋 Logging out {} -> {}",
+                logout.dats_source_user, logout.dats_destination
+            );
+            writer
+                .write(logout, None)
+                .context("Failed to transmit Logout to matching engine")?;
         }
+        Ok(sessions.len())
+    }
 
-        // Financial system validations
-        if order.quantity > 1_000_000.0 {
-            return Err(anyhow::anyhow!("Quantity exceeds maximum limit"));
+    /// DATS routing and FIX session of an order, shared by its session-level messages
+    fn session_route(order: &NewOrderSingle) -> [String; 6] {
+        [
+            order.dats_source.clone(),
+            order.dats_destination.clone(),
+            order.dats_source_user.clone(),
+            order.dats_destination_user.clone(),
+            order.fix_header.sender_comp_id.clone(),
+            order.fix_header.target_comp_id.clone(),
+        ]
+    }
+
+    /// Live terms of a published NewOrderSingle, following amendments, as a cancel
+    /// or amend target
+    fn sent_new_order_single(&self, cl_ord_id: &str) -> Result<NewOrderSingle> {
+        if let Some(order) = self.replace_book.live_order(cl_ord_id) {
+            return Ok(order);
         }
 
-        if order.price > 0.0 && order.price > 1_000_000.0 {
-            return Err(anyhow::anyhow!("Price exceeds maximum limit"));
+        match self.sent_messages.lock().unwrap().get(cl_ord_id) {
+            Some((OrderSpec::NewOrderSingle(order), true)) => Ok(order.clone()),
+            Some((OrderSpec::NewOrderSingle(_), false)) => {
+                bail!("Order {} was never published, resend it first", cl_ord_id)
+            }
+            Some((OrderSpec::OrderRequest(_), _)) => bail!(
+                "Order {} was sent to the OMS, which takes no cancel or replace requests",
+                cl_ord_id
+            ),
+            None => bail!("Unknown ClOrdID {}: no order sent today", cl_ord_id),
         }
+    }
+
+    /// Allocate the MsgSeqNum of an outbound message on its header's session
+    fn next_outbound_seq_num(&self, header: &Header) -> Result<i32> {
+        self.sequence_store.next_outbound(&SessionId::new(
+            &header.sender_comp_id,
+            &header.target_comp_id,
+        ))
+    }
 
+    /// Wait for or fail on the outbound rate limits of a message
+    async fn throttle(&self, kind: MessageKind, user_id: &str, symbol: &str) -> Result<()> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire(kind, user_id, symbol).await?;
+        }
         Ok(())
     }
 
-    /// Send a market order (convenience method)
+    /// Apply the same pre-trade checks as the OMS, reserving usage on success
+    fn validate_order(&self, order: &RiskOrder) -> Result<()> {
+        let result = match &self.risk_engine {
+            Some(risk_engine) => risk_engine.check_and_reserve(order),
+            None => validate_order_parameters(order),
+        };
+
+        result.map_err(|reason| {
+            warn!(
+                "❌ Order {} rejected by pre-trade risk: {}",
+                order.order_id, reason
+            );
+            anyhow::anyhow!(reason)
+        })
+    }
+
+    /// Give back the risk usage of an order that could not be published
+    fn release_order(&self, order_id: &str) {
+        if let Some(risk_engine) = &self.risk_engine {
+            risk_engine.release(order_id);
+        }
+    }
+
+    /// Send a market order for the default account (convenience method)
     pub async fn send_market_order(
         &self,
         symbol: &str,
         side: OrderSide,
         quantity: f64,
-    ) -> Result<String> {
-        let order_id = generate_unique_order_id();
-        let order = OrderRequest::market_order(
-            "rust_client".to_string(),
-            order_id.clone(),
-            symbol.to_string(),
-            side,
-            quantity,
-            "user001".to_string(),
-        );
-
-        self.send_order(order).await?;
-        Ok(order_id)
+    ) -> Result<OrderHandle> {
+        self.default_account()
+            .send_market_order(symbol, side, quantity)
+            .await
     }
 
-    /// Send a limit order (convenience method)
+    /// Send a limit order for the default account (convenience method)
     pub async fn send_limit_order(
         &self,
         symbol: &str,
         side: OrderSide,
         quantity: f64,
         price: f64,
-    ) -> Result<String> {
-        let order_id = generate_unique_order_id();
-        let order = OrderRequest::limit_order(
-            "rust_client".to_string(),
-            order_id.clone(),
-            symbol.to_string(),
-            side,
-            quantity,
-            price,
-            "user001".to_string(),
-        );
-
-        self.send_order(order).await?;
-        Ok(order_id)
+    ) -> Result<OrderHandle> {
+        self.default_account()
+            .send_limit_order(symbol, side, quantity, price)
+            .await
     }
 
-    pub async fn send_new_order_single(&self, new_order: NewOrderSingle) -> Result<()> {
+    pub async fn send_new_order_single(&self, new_order: NewOrderSingle) -> Result<OrderHandle> {
+        self.ensure_accepting()?;
+        self.throttle(
+            MessageKind::Order,
+            &new_order.dats_source_user,
+            &new_order.symbol,
+        )
+        .await?;
+
         // Validate NewOrderSingle before transmission
+        self.validate_order(&RiskOrder::from(&new_order))?;
+        self.register_cl_ord_id(&new_order.cl_ord_id)?;
 
         // Log for financial audit trail
         self.log_new_order_single_transmission(&new_order);
 
         // Transmit to matching engine
-        self.new_order_single_writer
-            .write(new_order.clone(), None)
-            .context("Failed to transmit NewOrderSingle to matching engine")?;
+        let handle = self.publish(OrderSpec::NewOrderSingle(new_order.clone()), true)?;
 
         info!(
             "✅ NewOrderSingle transmitted: ClOrdID={}, Symbol={}, Side={}, Qty={}, Type={}",
             new_order.cl_ord_id,
             new_order.symbol,
-            new_order.side,
+            new_order.side as char,
             new_order.order_qty,
-            new_order.ord_type
+            new_order.ord_type as char
         );
 
-        Ok(())
+        Ok(handle)
     }
 
     fn log_new_order_single_transmission(&self, order: &NewOrderSingle) {
@@ -160,7 +818,7 @@ impl OrderDdsClient {
             "📋 NEWORDERSINGLE AUDIT: ClOrdID={}, Symbol={}, Side={}, Qty={}, Price={}, Exchange={}, Source={}, Dest={}",
             order.cl_ord_id,
             order.symbol,
-            order.side,
+            order.side as char,
             order.order_qty,
             order.price,
             order.security_exchange,
@@ -174,18 +832,29 @@ impl OrderDdsClient {
         symbol: &str,
         side: &str,
         quantity: i32,
-    ) -> Result<String> {
-        let cl_ord_id = generate_unique_cl_ord_id();
+    ) -> Result<OrderHandle> {
+        let cl_ord_id = self.id_generator.next_id()?;
         let new_order = NewOrderSingle::market_order(
-            cl_ord_id.clone(),
+            cl_ord_id,
             symbol.to_string(),
             side,
             quantity,
-            "rust_trader_001".to_string(),
+            self.default_account.source_user.clone(),
         );
 
-        self.send_new_order_single(new_order).await?;
-        Ok(cl_ord_id)
+        self.send_new_order_single(new_order).await
+    }
+
+    pub async fn send_limit_new_order_single(
+        &self,
+        symbol: &str,
+        side: OrderSide,
+        quantity: i32,
+        price: f64,
+    ) -> Result<OrderHandle> {
+        self.default_account()
+            .send_limit_new_order_single(symbol, side, quantity, price)
+            .await
     }
 }
 
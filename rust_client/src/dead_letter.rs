@@ -0,0 +1,217 @@
+// src/dead_letter.rs
+use crate::journal::OrderJournal;
+use crate::report::report_decoder::split_payload;
+use crate::utils::panics::panic_message;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use log::error;
+use metrics::counter;
+use serde::Serialize;
+use std::any::Any;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Why a sample was set aside instead of processed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum DeadLetterKind {
+    /// The sample bytes could not be decoded into the report type
+    Undecodable,
+    /// A callback panicked while handling the decoded report
+    CallbackPanic,
+}
+
+impl DeadLetterKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Undecodable => "undecodable",
+            Self::CallbackPanic => "callback_panic",
+        }
+    }
+}
+
+impl std::fmt::Display for DeadLetterKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Sample set aside by a listener, as written to the dead-letter file
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadLetter {
+    pub recorded_at: DateTime<Utc>,
+    pub kind: DeadLetterKind,
+    pub message: &'static str,
+    pub topic: &'static str,
+    pub error: String,
+    /// Raw sample bytes in hex, when the sample could not be decoded
+    pub payload_hex: Option<String>,
+    /// Decoded report, when a callback panicked on it
+    pub report: Option<serde_json::Value>,
+}
+
+/// Per-day JSON-lines file of dead letters shared by all listeners
+pub struct DeadLetterQueue {
+    journal: OrderJournal,
+}
+
+impl DeadLetterQueue {
+    /// Dead-letter file in `store_dir`, appending to today's file
+    pub fn open<P: AsRef<Path>>(store_dir: P) -> Result<Self> {
+        Ok(Self {
+            journal: OrderJournal::open_with_prefix(store_dir, "dead_letters")?,
+        })
+    }
+
+    /// Append a dead letter and write it to disk straight away
+    pub fn append(&self, letter: &DeadLetter) -> Result<()> {
+        self.journal.append(letter)?;
+        self.journal.flush()
+    }
+
+    /// Dead letters appended since the file was opened
+    pub fn records(&self) -> u64 {
+        self.journal.records()
+    }
+}
+
+/// Samples a listener has dead-lettered so far
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeadLetterCounts {
+    pub undecodable: u64,
+    pub callback_panics: u64,
+}
+
+impl DeadLetterCounts {
+    pub fn total(&self) -> u64 {
+        self.undecodable + self.callback_panics
+    }
+}
+
+/// Counts, logs and stores the dead letters of one listener
+pub(crate) struct DeadLetterSink {
+    message: &'static str,
+    topic: &'static str,
+    queue: Option<Arc<DeadLetterQueue>>,
+    undecodable: AtomicU64,
+    callback_panics: AtomicU64,
+}
+
+impl DeadLetterSink {
+    pub(crate) fn new(
+        message: &'static str,
+        topic: &'static str,
+        queue: Option<Arc<DeadLetterQueue>>,
+    ) -> Self {
+        Self {
+            message,
+            topic,
+            queue,
+            undecodable: AtomicU64::new(0),
+            callback_panics: AtomicU64::new(0),
+        }
+    }
+
+    /// Record a sample that failed to decode, from the reader's error reason
+    pub(crate) fn undecodable(&self, reason: &str) {
+        let (error, payload_hex) = split_payload(reason);
+        self.undecodable.fetch_add(1, Ordering::Relaxed);
+        self.record(DeadLetter {
+            recorded_at: Utc::now(),
+            kind: DeadLetterKind::Undecodable,
+            message: self.message,
+            topic: self.topic,
+            error,
+            payload_hex,
+            report: None,
+        });
+    }
+
+    /// Record a report a callback panicked on
+    pub(crate) fn callback_panic<T: Serialize>(&self, report: &T, panic: Box<dyn Any + Send>) {
+        self.callback_panics.fetch_add(1, Ordering::Relaxed);
+        self.record(DeadLetter {
+            recorded_at: Utc::now(),
+            kind: DeadLetterKind::CallbackPanic,
+            message: self.message,
+            topic: self.topic,
+            error: format!("callback panicked: {}", panic_message(panic.as_ref())),
+            payload_hex: None,
+            report: serde_json::to_value(report).ok(),
+        });
+    }
+
+    fn record(&self, letter: DeadLetter) {
+        error!(
+            "☠️ {} dead-lettered ({}): {}",
+            letter.message, letter.kind, letter.error
+        );
+        counter!("process.dead_letter", "topic" => self.topic, "kind" => letter.kind.as_str())
+            .increment(1);
+
+        if let Some(queue) = &self.queue {
+            if let Err(e) = queue.append(&letter) {
+                error!("❌ Failed to write {} dead letter: {:#}", letter.message, e);
+            }
+        }
+    }
+
+    pub(crate) fn counts(&self) -> DeadLetterCounts {
+        DeadLetterCounts {
+            undecodable: self.undecodable.load(Ordering::Relaxed),
+            callback_panics: self.callback_panics.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sink_counts_and_stores_both_kinds_of_dead_letter() {
+        let store_dir = std::env::temp_dir().join(format!("dead_letters_{}", uuid::Uuid::new_v4()));
+        let queue = Arc::new(DeadLetterQueue::open(&store_dir).unwrap());
+        let sink = DeadLetterSink::new("ExecutionReport", "EXECUTION_REPORT_TOPIC", Some(queue));
+
+        sink.undecodable("Invalid CDR (CDR_LE) payload=0a0b");
+        sink.callback_panic(&serde_json::json!({ "ExecID": "E1" }), Box::new("boom"));
+        assert_eq!(
+            sink.counts(),
+            DeadLetterCounts {
+                undecodable: 1,
+                callback_panics: 1,
+            }
+        );
+        assert_eq!(sink.counts().total(), 2);
+
+        let file = std::fs::read_dir(&store_dir)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        let letters: Vec<serde_json::Value> = std::fs::read_to_string(file)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(letters.len(), 2);
+        assert_eq!(letters[0]["kind"], "Undecodable");
+        assert_eq!(letters[0]["error"], "Invalid CDR (CDR_LE)");
+        assert_eq!(letters[0]["payload_hex"], "0a0b");
+        assert_eq!(letters[1]["kind"], "CallbackPanic");
+        assert_eq!(letters[1]["error"], "callback panicked: boom");
+        assert_eq!(letters[1]["report"]["ExecID"], "E1");
+        assert_eq!(letters[1]["topic"], "EXECUTION_REPORT_TOPIC");
+        std::fs::remove_dir_all(&store_dir).unwrap();
+    }
+
+    #[test]
+    fn sink_without_a_queue_still_counts() {
+        let sink = DeadLetterSink::new("ExecutionReport", "EXECUTION_REPORT_TOPIC", None);
+        sink.undecodable("truncated sample");
+        assert_eq!(sink.counts().undecodable, 1);
+        assert_eq!(sink.counts().callback_panics, 0);
+    }
+}
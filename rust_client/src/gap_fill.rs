@@ -0,0 +1,474 @@
+// src/gap_fill.rs
+use crate::order_mass_status_request::OrderMassStatusRequest;
+use crate::report::execution_report::Header;
+use crate::sequence::{InboundSequence, SequenceStore, SessionId};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use log::{error, info, warn};
+use metrics::counter;
+use rustdds::no_key::DataWriter;
+use std::collections::{BTreeSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Closed gaps kept for reporting, oldest dropped first
+const MAX_CLOSED_GAPS: usize = 256;
+
+/// Routing and MsgSeqNum of an inbound message, as seen from our side
+#[derive(Debug, Clone)]
+pub struct InboundMessage {
+    pub session: SessionId,
+    pub msg_seq_num: i32,
+    /// Component that sent the message, where status requests go
+    pub dats_source: String,
+    pub dats_destination: String,
+    pub dats_source_user: String,
+    pub dats_destination_user: String,
+}
+
+impl InboundMessage {
+    pub fn new(
+        dats_source: &str,
+        dats_destination: &str,
+        dats_source_user: &str,
+        dats_destination_user: &str,
+        fix_header: &Header,
+    ) -> Self {
+        Self {
+            session: SessionId::new(&fix_header.target_comp_id, &fix_header.sender_comp_id),
+            msg_seq_num: fix_header.msg_seq_num,
+            dats_source: dats_source.to_string(),
+            dats_destination: dats_destination.to_string(),
+            dats_source_user: dats_source_user.to_string(),
+            dats_destination_user: dats_destination_user.to_string(),
+        }
+    }
+}
+
+/// Retry behaviour of gap fill requests
+#[derive(Debug, Clone, Copy)]
+pub struct GapFillConfig {
+    /// How long a status request may go unanswered before it is sent again
+    pub request_timeout: Duration,
+    /// Requests per gap before it is given up
+    pub max_requests: u32,
+}
+
+impl Default for GapFillConfig {
+    fn default() -> Self {
+        Self {
+            request_timeout: Duration::from_secs(5),
+            max_requests: 3,
+        }
+    }
+}
+
+/// Inbound gap waiting to be filled
+#[derive(Debug, Clone)]
+pub struct OpenGap {
+    pub session: SessionId,
+    pub expected: i32,
+    pub received: i32,
+    /// MsgSeqNums not seen yet
+    pub missing: BTreeSet<i32>,
+    pub detected_at: DateTime<Utc>,
+    pub mass_status_req_id: String,
+    pub requested_at: DateTime<Utc>,
+    pub requests: u32,
+    // Message that revealed the gap, for routing repeated requests
+    origin: InboundMessage,
+}
+
+/// How a gap was closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GapResolution {
+    /// Every missing MsgSeqNum arrived late or was resent
+    Refilled,
+    /// No answer after the last request
+    Abandoned,
+}
+
+impl std::fmt::Display for GapResolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Refilled => "refilled",
+            Self::Abandoned => "abandoned",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Gap that is no longer open
+#[derive(Debug, Clone)]
+pub struct ClosedGap {
+    pub session: SessionId,
+    pub expected: i32,
+    pub received: i32,
+    pub detected_at: DateTime<Utc>,
+    pub closed_at: DateTime<Utc>,
+    pub requests: u32,
+    pub resolution: GapResolution,
+}
+
+/// Detects inbound MsgSeqNum gaps per sender and fills them with
+/// OrderMassStatusRequests, reporting when each gap is closed. Status reports
+/// carry no MassStatusReqID to match them to a request, so they refresh order
+/// state without closing gaps: a gap closes once every missing MsgSeqNum has
+/// arrived, or is abandoned after the last request.
+pub struct GapFiller {
+    sequence_store: Arc<SequenceStore>,
+    writer: DataWriter<OrderMassStatusRequest>,
+    config: GapFillConfig,
+    open_gaps: Mutex<Vec<OpenGap>>,
+    // Latest closed gaps, up to MAX_CLOSED_GAPS
+    closed_gaps: Mutex<VecDeque<ClosedGap>>,
+    next_request: AtomicU64,
+}
+
+impl GapFiller {
+    pub fn new(
+        sequence_store: Arc<SequenceStore>,
+        writer: DataWriter<OrderMassStatusRequest>,
+    ) -> Self {
+        Self {
+            sequence_store,
+            writer,
+            config: GapFillConfig::default(),
+            open_gaps: Mutex::new(Vec::new()),
+            closed_gaps: Mutex::new(VecDeque::new()),
+            next_request: AtomicU64::new(1),
+        }
+    }
+
+    pub fn with_config(mut self, config: GapFillConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Check the MsgSeqNum of an inbound message, requesting status on a gap
+    /// and closing gaps the message fills. Messages without one are ignored.
+    pub fn on_inbound(&self, message: &InboundMessage) {
+        if message.msg_seq_num <= 0 {
+            return;
+        }
+
+        match self
+            .sequence_store
+            .on_inbound(&message.session, message.msg_seq_num)
+        {
+            InboundSequence::Gap { expected, received } => {
+                self.open_gap(message, expected, received)
+            }
+            InboundSequence::Duplicate { received, .. } => {
+                self.close_gaps(&message.session, GapResolution::Refilled, |gap| {
+                    gap.missing.remove(&received);
+                    gap.missing.is_empty()
+                });
+            }
+            InboundSequence::InOrder => {}
+        }
+    }
+
+    fn open_gap(&self, message: &InboundMessage, expected: i32, received: i32) {
+        error!(
+            "🚨 ALERT: {} messages missing from {} on session {} (MsgSeqNum {}..{}), requesting status",
+            received - expected,
+            message.dats_source,
+            message.session,
+            expected,
+            received - 1
+        );
+        counter!("process.inbound_gap", "session" => message.session.to_string()).increment(1);
+
+        let now = Utc::now();
+        let mut gap = OpenGap {
+            session: message.session.clone(),
+            expected,
+            received,
+            missing: (expected..received).collect(),
+            detected_at: now,
+            mass_status_req_id: String::new(),
+            requested_at: now,
+            requests: 0,
+            origin: message.clone(),
+        };
+        self.request_status(&mut gap);
+        self.open_gaps.lock().unwrap().push(gap);
+    }
+
+    /// Send an OrderMassStatusRequest to the sender of a gap
+    fn request_status(&self, gap: &mut OpenGap) {
+        let id = self.next_request.fetch_add(1, Ordering::Relaxed);
+        gap.mass_status_req_id = format!("GAP_{}_{}", Utc::now().timestamp_millis(), id);
+        gap.requested_at = Utc::now();
+        gap.requests += 1;
+
+        if let Err(e) = self.send_request(gap) {
+            warn!(
+                "❌ Failed to request status for gap {}..{} on {}: {:#}",
+                gap.expected,
+                gap.received - 1,
+                gap.session,
+                e
+            );
+            return;
+        }
+        info!(
+            "📨 OrderMassStatusRequest {} sent to {} for gap {}..{} (attempt {}/{})",
+            gap.mass_status_req_id,
+            gap.origin.dats_source,
+            gap.expected,
+            gap.received - 1,
+            gap.requests,
+            self.config.max_requests
+        );
+    }
+
+    fn send_request(&self, gap: &OpenGap) -> Result<()> {
+        let origin = &gap.origin;
+        let mut request = OrderMassStatusRequest::all_orders(gap.mass_status_req_id.clone());
+        request.dats_source = origin.dats_destination.clone();
+        request.dats_destination = origin.dats_source.clone();
+        request.dats_source_user = origin.dats_destination_user.clone();
+        request.dats_destination_user = origin.dats_source_user.clone();
+        request.fix_header.sender_comp_id = gap.session.sender_comp_id.clone();
+        request.fix_header.target_comp_id = gap.session.target_comp_id.clone();
+        request.fix_header.msg_seq_num = self.sequence_store.next_outbound(&gap.session)?;
+
+        self.writer
+            .write(request, None)
+            .context("Failed to write OrderMassStatusRequest")
+    }
+
+    /// Close the open gaps of a session for which `done` returns true
+    fn close_gaps<F>(&self, session: &SessionId, resolution: GapResolution, mut done: F)
+    where
+        F: FnMut(&mut OpenGap) -> bool,
+    {
+        let mut open_gaps = self.open_gaps.lock().unwrap();
+        let mut closed = Vec::new();
+        open_gaps.retain_mut(|gap| {
+            if &gap.session == session && done(gap) {
+                closed.push(Self::closed(gap, resolution));
+                false
+            } else {
+                true
+            }
+        });
+        drop(open_gaps);
+
+        for gap in &closed {
+            info!(
+                "✅ Gap {}..{} on session {} closed ({}) after {} ms",
+                gap.expected,
+                gap.received - 1,
+                gap.session,
+                gap.resolution,
+                (gap.closed_at - gap.detected_at).num_milliseconds()
+            );
+        }
+        self.record_closed(closed);
+    }
+
+    /// Keep closed gaps for reporting, dropping the oldest past the bound
+    fn record_closed(&self, closed: Vec<ClosedGap>) {
+        let mut closed_gaps = self.closed_gaps.lock().unwrap();
+        closed_gaps.extend(closed);
+        while closed_gaps.len() > MAX_CLOSED_GAPS {
+            closed_gaps.pop_front();
+        }
+    }
+
+    fn closed(gap: &OpenGap, resolution: GapResolution) -> ClosedGap {
+        ClosedGap {
+            session: gap.session.clone(),
+            expected: gap.expected,
+            received: gap.received,
+            detected_at: gap.detected_at,
+            closed_at: Utc::now(),
+            requests: gap.requests,
+            resolution,
+        }
+    }
+
+    /// Repeat unanswered requests and give up on gaps out of attempts
+    pub fn check_timeouts(&self) {
+        let timeout = chrono::Duration::from_std(self.config.request_timeout)
+            .unwrap_or(chrono::Duration::MAX);
+        let now = Utc::now();
+
+        let mut abandoned = Vec::new();
+        {
+            let mut open_gaps = self.open_gaps.lock().unwrap();
+            open_gaps.retain_mut(|gap| {
+                if now - gap.requested_at < timeout {
+                    return true;
+                }
+                if gap.requests < self.config.max_requests {
+                    self.request_status(gap);
+                    return true;
+                }
+                abandoned.push(Self::closed(gap, GapResolution::Abandoned));
+                false
+            });
+        }
+
+        for gap in &abandoned {
+            error!(
+                "🚨 ALERT: Gap {}..{} on session {} still open after {} status requests, \
+                 check for missing orders manually",
+                gap.expected,
+                gap.received - 1,
+                gap.session,
+                gap.requests
+            );
+        }
+        self.record_closed(abandoned);
+    }
+
+    /// Check request timeouts every `interval` in the background
+    pub fn spawn_timeout_checker(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                self.check_timeouts();
+            }
+        })
+    }
+
+    /// Gaps still waiting to be filled, oldest first
+    pub fn open_gaps(&self) -> Vec<OpenGap> {
+        self.open_gaps.lock().unwrap().clone()
+    }
+
+    /// Latest gaps closed, oldest first
+    pub fn closed_gaps(&self) -> Vec<ClosedGap> {
+        self.closed_gaps.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Take the closed gaps reported so far, oldest first
+    pub fn drain_closed_gaps(&self) -> Vec<ClosedGap> {
+        self.closed_gaps.lock().unwrap().drain(..).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sequence::ResetSchedule;
+    use rustdds::{CDRSerializerAdapter, DomainParticipant, QosPolicyBuilder, TopicKind};
+
+    // Domain of its own so status requests don't reach a running OMS
+    const TEST_DOMAIN_ID: u16 = 98;
+
+    /// Gap filler writing to a private participant, returned to keep it alive
+    fn gap_filler(config: GapFillConfig) -> (GapFiller, Arc<SequenceStore>, DomainParticipant) {
+        let participant = DomainParticipant::new(TEST_DOMAIN_ID).unwrap();
+        let qos = QosPolicyBuilder::new().build();
+        let topic = participant
+            .create_topic(
+                "GAP_FILL_TEST_TOPIC".to_string(),
+                "DistributedATS_OrderMassStatusRequest::OrderMassStatusRequest".to_string(),
+                &qos,
+                TopicKind::NoKey,
+            )
+            .unwrap();
+        let writer = participant
+            .create_publisher(&qos)
+            .unwrap()
+            .create_datawriter_no_key::<OrderMassStatusRequest, CDRSerializerAdapter<OrderMassStatusRequest>>(
+                &topic, None,
+            )
+            .unwrap();
+
+        let sequence_store = Arc::new(SequenceStore::in_memory(ResetSchedule::Never));
+        let gap_filler = GapFiller::new(Arc::clone(&sequence_store), writer).with_config(config);
+        (gap_filler, sequence_store, participant)
+    }
+
+    fn message(msg_seq_num: i32) -> InboundMessage {
+        InboundMessage {
+            session: SessionId::new("CLIENT", "OMS"),
+            msg_seq_num,
+            dats_source: "OMS".to_string(),
+            dats_destination: "CLIENT".to_string(),
+            dats_source_user: "oms_user".to_string(),
+            dats_destination_user: "rust_trader_001".to_string(),
+        }
+    }
+
+    /// Config under which every request has timed out by the next check
+    fn expired(max_requests: u32) -> GapFillConfig {
+        GapFillConfig {
+            request_timeout: Duration::ZERO,
+            max_requests,
+        }
+    }
+
+    #[test]
+    fn gap_closes_once_every_missing_message_arrives() {
+        let (gap_filler, sequence_store, _participant) = gap_filler(GapFillConfig::default());
+        gap_filler.on_inbound(&message(1));
+        gap_filler.on_inbound(&message(4));
+
+        let open = gap_filler.open_gaps();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].missing, BTreeSet::from([2, 3]));
+        assert_eq!(open[0].requests, 1);
+        assert!(open[0].mass_status_req_id.starts_with("GAP_"));
+        // The status request used the session's first outbound MsgSeqNum
+        let session = SessionId::new("CLIENT", "OMS");
+        assert_eq!(sequence_store.next_outbound(&session).unwrap(), 2);
+
+        gap_filler.on_inbound(&message(3));
+        assert_eq!(gap_filler.open_gaps()[0].missing, BTreeSet::from([2]));
+        gap_filler.on_inbound(&message(2));
+        assert!(gap_filler.open_gaps().is_empty());
+
+        let closed = gap_filler.drain_closed_gaps();
+        assert_eq!(closed.len(), 1);
+        assert_eq!((closed[0].expected, closed[0].received), (2, 4));
+        assert_eq!(closed[0].resolution, GapResolution::Refilled);
+        assert!(gap_filler.closed_gaps().is_empty());
+    }
+
+    #[test]
+    fn unanswered_gap_is_requested_again_then_abandoned() {
+        let (gap_filler, _sequence_store, _participant) = gap_filler(expired(2));
+        gap_filler.on_inbound(&message(1));
+        gap_filler.on_inbound(&message(3));
+        // Messages from other sessions don't touch the gap
+        gap_filler.on_inbound(&InboundMessage {
+            session: SessionId::new("CLIENT", "ME"),
+            ..message(2)
+        });
+
+        gap_filler.check_timeouts();
+        assert_eq!(gap_filler.open_gaps()[0].requests, 2);
+
+        gap_filler.check_timeouts();
+        assert!(gap_filler.open_gaps().is_empty());
+        let closed = gap_filler.closed_gaps();
+        assert_eq!(closed[0].resolution, GapResolution::Abandoned);
+        assert_eq!(closed[0].requests, 2);
+    }
+
+    #[test]
+    fn closed_gap_history_is_bounded() {
+        let (gap_filler, _sequence_store, _participant) = gap_filler(expired(1));
+        let gaps = MAX_CLOSED_GAPS as i32 + 10;
+        for gap in 0..gaps {
+            gap_filler.on_inbound(&message(2 * gap + 2));
+        }
+        assert_eq!(gap_filler.open_gaps().len(), gaps as usize);
+
+        gap_filler.check_timeouts();
+        let closed = gap_filler.closed_gaps();
+        assert_eq!(closed.len(), MAX_CLOSED_GAPS);
+        // The oldest gaps were dropped first
+        assert_eq!(closed[0].expected, 21);
+    }
+}
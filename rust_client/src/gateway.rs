@@ -0,0 +1,596 @@
+// src/gateway.rs
+use crate::dds_client::OrderDdsClient;
+use crate::new_order_single::NewOrderSingle;
+use crate::order_event::{OrderEvent, ReportSource};
+use crate::order_handle::{OrderHandle, OrderTracker, OrderUpdate};
+use crate::risk::{validate_order_parameters, RiskOrder};
+use crate::utils::order_id_generator::OrderIdGenerator;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use log::info;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+/// Order entry independent of the transport, so strategy code can run against
+/// DDS, the in-memory `LoopbackGateway` or a `RecordingGateway` alike
+pub trait OrderGateway: Send + Sync {
+    /// Send a new order; the handle resolves with its reports
+    fn send(&self, order: NewOrderSingle) -> impl Future<Output = Result<OrderHandle>> + Send;
+
+    /// Request cancellation of an order; the handle is the original order's
+    fn cancel(&self, cl_ord_id: &str) -> impl Future<Output = Result<OrderHandle>> + Send;
+
+    /// Request a change of an order's terms; the handle is the original order's
+    fn amend(
+        &self,
+        cl_ord_id: &str,
+        amendment: Amendment,
+    ) -> impl Future<Output = Result<OrderHandle>> + Send;
+
+    /// Next ClOrdID for a new order
+    fn next_cl_ord_id(&self) -> Result<String>;
+
+    /// Every report received from now on, for any order
+    fn events(&self) -> broadcast::Receiver<OrderEvent>;
+}
+
+/// Changes to a working order; unset fields keep their current value
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Amendment {
+    pub quantity: Option<i32>,
+    pub price: Option<f64>,
+    pub stop_px: Option<f64>,
+    pub text: Option<String>,
+}
+
+impl Amendment {
+    pub fn quantity(mut self, quantity: i32) -> Self {
+        self.quantity = Some(quantity);
+        self
+    }
+
+    pub fn price(mut self, price: f64) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn stop_px(mut self, stop_px: f64) -> Self {
+        self.stop_px = Some(stop_px);
+        self
+    }
+
+    pub fn text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.quantity.is_none()
+            && self.price.is_none()
+            && self.stop_px.is_none()
+            && self.text.is_none()
+    }
+
+    /// Apply the changes to an order's terms
+    pub fn apply_to(&self, order: &mut NewOrderSingle) {
+        if let Some(quantity) = self.quantity {
+            order.order_qty = quantity;
+        }
+        if let Some(price) = self.price {
+            order.price = price as f32;
+        }
+        if let Some(stop_px) = self.stop_px {
+            order.stop_px = stop_px as f32;
+        }
+        if let Some(text) = &self.text {
+            order.text = text.clone();
+        }
+    }
+}
+
+impl OrderGateway for OrderDdsClient {
+    async fn send(&self, order: NewOrderSingle) -> Result<OrderHandle> {
+        self.send_new_order_single(order).await
+    }
+
+    async fn cancel(&self, cl_ord_id: &str) -> Result<OrderHandle> {
+        OrderDdsClient::cancel(self, cl_ord_id).await
+    }
+
+    async fn amend(&self, cl_ord_id: &str, amendment: Amendment) -> Result<OrderHandle> {
+        OrderDdsClient::amend(self, cl_ord_id, &amendment).await
+    }
+
+    fn next_cl_ord_id(&self) -> Result<String> {
+        OrderDdsClient::next_cl_ord_id(self)
+    }
+
+    fn events(&self) -> broadcast::Receiver<OrderEvent> {
+        self.order_events()
+    }
+}
+
+impl<G: OrderGateway> OrderGateway for Arc<G> {
+    fn send(&self, order: NewOrderSingle) -> impl Future<Output = Result<OrderHandle>> + Send {
+        G::send(self, order)
+    }
+
+    fn cancel(&self, cl_ord_id: &str) -> impl Future<Output = Result<OrderHandle>> + Send {
+        G::cancel(self, cl_ord_id)
+    }
+
+    fn amend(
+        &self,
+        cl_ord_id: &str,
+        amendment: Amendment,
+    ) -> impl Future<Output = Result<OrderHandle>> + Send {
+        G::amend(self, cl_ord_id, amendment)
+    }
+
+    fn next_cl_ord_id(&self) -> Result<String> {
+        G::next_cl_ord_id(self)
+    }
+
+    fn events(&self) -> broadcast::Receiver<OrderEvent> {
+        G::events(self)
+    }
+}
+
+/// Order held by the loopback gateway
+struct LoopbackOrder {
+    order: NewOrderSingle,
+    order_id: String,
+    cum_qty: i32,
+    /// Notional of the fills so far, for AvgPx
+    filled_value: f64,
+    ord_status: u8,
+}
+
+impl LoopbackOrder {
+    fn is_open(&self) -> bool {
+        matches!(self.ord_status, b'0' | b'1')
+    }
+}
+
+/// In-memory venue for tests and dry runs: acknowledges every valid order,
+/// fills market orders at the mark price of their symbol when one is set and
+/// fills other orders on `fill`. Cancels and amends of open orders always succeed.
+pub struct LoopbackGateway {
+    tracker: OrderTracker,
+    id_generator: OrderIdGenerator,
+    orders: Mutex<HashMap<String, LoopbackOrder>>,
+    marks: Mutex<HashMap<String, f64>>,
+    next_exec_id: Mutex<u64>,
+}
+
+impl Default for LoopbackGateway {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoopbackGateway {
+    pub fn new() -> Self {
+        Self {
+            tracker: OrderTracker::new(),
+            id_generator: OrderIdGenerator::default(),
+            orders: Mutex::new(HashMap::new()),
+            marks: Mutex::new(HashMap::new()),
+            next_exec_id: Mutex::new(1),
+        }
+    }
+
+    /// Price market orders for `symbol` are filled at from now on
+    pub fn set_mark(&self, symbol: &str, price: f64) {
+        self.marks.lock().unwrap().insert(symbol.to_string(), price);
+    }
+
+    /// Execute `quantity` of an open order at `price`
+    pub fn fill(&self, cl_ord_id: &str, quantity: i32, price: f64) -> Result<()> {
+        let update = {
+            let mut orders = self.orders.lock().unwrap();
+            let order = Self::open_order(&mut orders, cl_ord_id)?;
+            let leaves_qty = order.order.order_qty - order.cum_qty;
+            if quantity <= 0 || quantity > leaves_qty {
+                bail!(
+                    "Fill of {} for order {} exceeds its leaves quantity {}",
+                    quantity,
+                    cl_ord_id,
+                    leaves_qty
+                );
+            }
+
+            order.cum_qty += quantity;
+            order.filled_value += quantity as f64 * price;
+            order.ord_status = if order.cum_qty == order.order.order_qty {
+                b'2'
+            } else {
+                b'1'
+            };
+            let mut update = self.report(order, b'F');
+            update.last_qty = quantity;
+            update.last_px = price as f32;
+            update
+        };
+
+        self.tracker.on_update(ReportSource::Simulated, update);
+        Ok(())
+    }
+
+    /// ExecutionReport-equivalent update for the current state of an order
+    fn report(&self, order: &LoopbackOrder, exec_type: u8) -> OrderUpdate {
+        let mut next_exec_id = self.next_exec_id.lock().unwrap();
+        let exec_id = format!("LB-EXEC-{}", *next_exec_id);
+        *next_exec_id += 1;
+
+        OrderUpdate {
+            order_id: order.order_id.clone(),
+            orig_cl_ord_id: order.order.cl_ord_id.clone(),
+            exec_id,
+            exec_type,
+            ord_status: order.ord_status,
+            ord_rej_reason: 0,
+            user_id: order.order.dats_source_user.clone(),
+            symbol: order.order.symbol.clone(),
+            side: order.order.side,
+            order_qty: order.order.order_qty,
+            last_qty: 0,
+            last_px: 0.0,
+            leaves_qty: if order.is_open() {
+                order.order.order_qty - order.cum_qty
+            } else {
+                0
+            },
+            cum_qty: order.cum_qty,
+            avg_px: if order.cum_qty > 0 {
+                (order.filled_value / order.cum_qty as f64) as f32
+            } else {
+                0.0
+            },
+            transact_time: Utc::now().timestamp_millis() as u64,
+            text: String::new(),
+        }
+    }
+
+    /// Resolve a ClOrdID, and reject the call unless the order is open
+    fn open_order<'a>(
+        orders: &'a mut HashMap<String, LoopbackOrder>,
+        cl_ord_id: &str,
+    ) -> Result<&'a mut LoopbackOrder> {
+        let order = orders
+            .get_mut(cl_ord_id)
+            .with_context(|| format!("Unknown order {}", cl_ord_id))?;
+        if !order.is_open() {
+            bail!("Order {} is not open", cl_ord_id);
+        }
+        Ok(order)
+    }
+}
+
+impl OrderGateway for LoopbackGateway {
+    async fn send(&self, order: NewOrderSingle) -> Result<OrderHandle> {
+        let cl_ord_id = order.cl_ord_id.clone();
+        let rejection = validate_order_parameters(&RiskOrder::from(&order)).err();
+        let mark = (order.ord_type == b'1')
+            .then(|| self.marks.lock().unwrap().get(&order.symbol).copied())
+            .flatten();
+
+        let (handle, update) = {
+            let mut orders = self.orders.lock().unwrap();
+            if orders.contains_key(&cl_ord_id) {
+                bail!("Duplicate ClOrdID {} already sent today", cl_ord_id);
+            }
+
+            let handle = self.tracker.track(&cl_ord_id);
+            let mut entry = LoopbackOrder {
+                order,
+                order_id: format!("LB-{}", self.id_generator.next_id()?),
+                cum_qty: 0,
+                filled_value: 0.0,
+                ord_status: b'0',
+            };
+            let update = match &rejection {
+                Some(reason) => {
+                    entry.ord_status = b'8';
+                    let mut update = self.report(&entry, b'8');
+                    update.text = reason.clone();
+                    update
+                }
+                None => self.report(&entry, b'0'),
+            };
+            orders.insert(cl_ord_id.clone(), entry);
+            (handle, update)
+        };
+
+        info!("🔁 Loopback accepted order {}", cl_ord_id);
+        self.tracker.on_update(ReportSource::Simulated, update);
+
+        if let (None, Some(price)) = (rejection, mark) {
+            let quantity = self.orders.lock().unwrap()[&cl_ord_id].order.order_qty;
+            self.fill(&cl_ord_id, quantity, price)?;
+        }
+        Ok(handle)
+    }
+
+    async fn cancel(&self, cl_ord_id: &str) -> Result<OrderHandle> {
+        let handle = self.tracker.watch(cl_ord_id);
+        let update = {
+            let mut orders = self.orders.lock().unwrap();
+            let order = Self::open_order(&mut orders, cl_ord_id)?;
+            order.ord_status = b'4';
+            self.report(order, b'4')
+        };
+
+        self.tracker.on_update(ReportSource::Simulated, update);
+        Ok(handle)
+    }
+
+    async fn amend(&self, cl_ord_id: &str, amendment: Amendment) -> Result<OrderHandle> {
+        if amendment.is_empty() {
+            bail!("Amendment of order {} changes nothing", cl_ord_id);
+        }
+
+        let handle = self.tracker.watch(cl_ord_id);
+        let update = {
+            let mut orders = self.orders.lock().unwrap();
+            let order = Self::open_order(&mut orders, cl_ord_id)?;
+
+            let mut replacement = order.order.clone();
+            amendment.apply_to(&mut replacement);
+            validate_order_parameters(&RiskOrder::from(&replacement))
+                .map_err(|reason| anyhow::anyhow!(reason))?;
+            if replacement.order_qty < order.cum_qty {
+                bail!(
+                    "Order {} already has {} filled, more than the amended quantity {}",
+                    cl_ord_id,
+                    order.cum_qty,
+                    replacement.order_qty
+                );
+            }
+
+            order.order = replacement;
+            // FIX 4.4: a replaced order reports ExecType Replaced with its fill status
+            order.ord_status = match order.cum_qty {
+                0 => b'0',
+                cum_qty if cum_qty == order.order.order_qty => b'2',
+                _ => b'1',
+            };
+            self.report(order, b'5')
+        };
+
+        self.tracker.on_update(ReportSource::Simulated, update);
+        Ok(handle)
+    }
+
+    fn next_cl_ord_id(&self) -> Result<String> {
+        self.id_generator.next_id()
+    }
+
+    fn events(&self) -> broadcast::Receiver<OrderEvent> {
+        self.tracker.subscribe()
+    }
+}
+
+/// Gateway call captured by a `RecordingGateway`
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize)]
+pub enum GatewayCall {
+    Send(NewOrderSingle),
+    Cancel {
+        cl_ord_id: String,
+    },
+    Amend {
+        cl_ord_id: String,
+        amendment: Amendment,
+    },
+}
+
+/// One call with its outcome: the handle's ClOrdID or the error message
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordedCall {
+    pub at: DateTime<Utc>,
+    pub call: GatewayCall,
+    pub result: Result<String, String>,
+}
+
+/// Wraps another gateway and records every call made through it
+pub struct RecordingGateway<G> {
+    inner: G,
+    calls: Mutex<Vec<RecordedCall>>,
+}
+
+impl<G: OrderGateway> RecordingGateway<G> {
+    pub fn new(inner: G) -> Self {
+        Self {
+            inner,
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn inner(&self) -> &G {
+        &self.inner
+    }
+
+    /// Calls made so far, oldest first
+    pub fn calls(&self) -> Vec<RecordedCall> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    pub fn clear(&self) {
+        self.calls.lock().unwrap().clear();
+    }
+
+    fn record(&self, call: GatewayCall, result: &Result<OrderHandle>) {
+        let result = match result {
+            Ok(handle) => Ok(handle.cl_ord_id().to_string()),
+            Err(e) => Err(format!("{:#}", e)),
+        };
+        self.calls.lock().unwrap().push(RecordedCall {
+            at: Utc::now(),
+            call,
+            result,
+        });
+    }
+}
+
+impl<G: OrderGateway> OrderGateway for RecordingGateway<G> {
+    async fn send(&self, order: NewOrderSingle) -> Result<OrderHandle> {
+        let call = GatewayCall::Send(order.clone());
+        let result = self.inner.send(order).await;
+        self.record(call, &result);
+        result
+    }
+
+    async fn cancel(&self, cl_ord_id: &str) -> Result<OrderHandle> {
+        let result = self.inner.cancel(cl_ord_id).await;
+        self.record(
+            GatewayCall::Cancel {
+                cl_ord_id: cl_ord_id.to_string(),
+            },
+            &result,
+        );
+        result
+    }
+
+    async fn amend(&self, cl_ord_id: &str, amendment: Amendment) -> Result<OrderHandle> {
+        let call = GatewayCall::Amend {
+            cl_ord_id: cl_ord_id.to_string(),
+            amendment: amendment.clone(),
+        };
+        let result = self.inner.amend(cl_ord_id, amendment).await;
+        self.record(call, &result);
+        result
+    }
+
+    fn next_cl_ord_id(&self) -> Result<String> {
+        self.inner.next_cl_ord_id()
+    }
+
+    fn events(&self) -> broadcast::Receiver<OrderEvent> {
+        self.inner.events()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const WAIT: Duration = Duration::from_secs(1);
+
+    fn limit_order(cl_ord_id: &str, quantity: i32, price: f64) -> NewOrderSingle {
+        NewOrderSingle::limit_order(
+            cl_ord_id.to_string(),
+            "BTC-USD".to_string(),
+            "1",
+            quantity,
+            price,
+            "rust_trader_001".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn loopback_reports_send_fill_replace_and_cancel() {
+        let gateway = LoopbackGateway::new();
+        let mut events = gateway.events();
+
+        let handle = gateway.send(limit_order("CL-1", 10, 100.0)).await.unwrap();
+        let ack = handle.accepted(WAIT).await.unwrap();
+        assert_eq!((ack.exec_type, ack.ord_status), (b'0', b'0'));
+        assert!(ack.order_id.starts_with("LB-"));
+        assert_eq!(events.recv().await.unwrap().order_key(), "CL-1");
+
+        gateway.fill("CL-1", 4, 101.0).unwrap();
+        let fill = handle.latest().unwrap();
+        assert_eq!((fill.exec_type, fill.ord_status), (b'F', b'1'));
+        assert_eq!((fill.last_qty, fill.cum_qty, fill.leaves_qty), (4, 4, 6));
+        assert_eq!(fill.last_px, 101.0);
+
+        let amendment = Amendment::default().quantity(8).price(99.5);
+        gateway.amend("CL-1", amendment).await.unwrap();
+        let replaced = handle.latest().unwrap();
+        assert_eq!((replaced.exec_type, replaced.ord_status), (b'5', b'1'));
+        assert_eq!((replaced.order_qty, replaced.leaves_qty), (8, 4));
+
+        gateway.cancel("CL-1").await.unwrap();
+        let done = handle.done(WAIT).await.unwrap();
+        assert_eq!((done.exec_type, done.ord_status), (b'4', b'4'));
+        assert_eq!((done.cum_qty, done.leaves_qty), (4, 0));
+        assert!(gateway.cancel("CL-1").await.is_err());
+        assert!(gateway.fill("CL-1", 1, 100.0).is_err());
+    }
+
+    #[tokio::test]
+    async fn loopback_fills_market_orders_at_the_mark() {
+        let gateway = LoopbackGateway::new();
+        gateway.set_mark("BTC-USD", 50_000.0);
+        let order = NewOrderSingle::market_order(
+            "CL-1".to_string(),
+            "BTC-USD".to_string(),
+            "2",
+            3,
+            "rust_trader_001".to_string(),
+        );
+
+        let filled = gateway
+            .send(order)
+            .await
+            .unwrap()
+            .filled(WAIT)
+            .await
+            .unwrap();
+        assert_eq!((filled.cum_qty, filled.avg_px), (3, 50_000.0));
+        assert_eq!(filled.side, b'2');
+    }
+
+    #[tokio::test]
+    async fn loopback_rejects_invalid_orders_and_amendments() {
+        let gateway = LoopbackGateway::new();
+
+        let rejected = gateway.send(limit_order("CL-1", 10, 0.0)).await.unwrap();
+        assert!(rejected.accepted(WAIT).await.is_err());
+        assert_eq!(rejected.latest().unwrap().ord_status, b'8');
+        assert!(gateway.send(limit_order("CL-1", 10, 100.0)).await.is_err());
+
+        gateway.send(limit_order("CL-2", 10, 100.0)).await.unwrap();
+        gateway.fill("CL-2", 6, 100.0).unwrap();
+        assert!(gateway.fill("CL-2", 5, 100.0).is_err());
+        assert!(gateway.amend("CL-2", Amendment::default()).await.is_err());
+        assert!(gateway
+            .amend("CL-2", Amendment::default().quantity(5))
+            .await
+            .is_err());
+        assert!(gateway.cancel("CL-3").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn recording_gateway_keeps_every_call_and_outcome() {
+        let gateway = RecordingGateway::new(LoopbackGateway::new());
+        gateway.send(limit_order("CL-1", 10, 100.0)).await.unwrap();
+        gateway
+            .amend("CL-1", Amendment::default().text("tighter"))
+            .await
+            .unwrap();
+        gateway.cancel("CL-1").await.unwrap();
+        assert!(gateway.cancel("CL-1").await.is_err());
+
+        let calls = gateway.calls();
+        assert_eq!(calls.len(), 4);
+        assert!(matches!(&calls[0].call, GatewayCall::Send(order) if order.cl_ord_id == "CL-1"));
+        assert!(
+            matches!(&calls[1].call, GatewayCall::Amend { amendment, .. }
+            if amendment.text.as_deref() == Some("tighter"))
+        );
+        assert_eq!(calls[2].result, Ok("CL-1".to_string()));
+        assert!(calls[3]
+            .result
+            .as_ref()
+            .is_err_and(|e| e.contains("not open")));
+
+        gateway.clear();
+        assert!(gateway.calls().is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+// src/journal.rs
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, Utc};
+use log::info;
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+struct JournalState {
+    trading_day: NaiveDate,
+    writer: BufWriter<File>,
+    records: u64,
+}
+
+/// Append-only JSON-lines journal with one file per trading day, used to
+/// archive orders dropped from in-memory caches
+pub struct OrderJournal {
+    store_dir: PathBuf,
+    // Day files are named `<prefix>_YYYYMMDD.jsonl`
+    prefix: &'static str,
+    state: Mutex<JournalState>,
+}
+
+impl OrderJournal {
+    /// Journal in `store_dir`, appending to today's file
+    pub fn open<P: AsRef<Path>>(store_dir: P) -> Result<Self> {
+        Self::open_with_prefix(store_dir, "journal")
+    }
+
+    /// Journal in `store_dir` whose day files start with `prefix`
+    pub fn open_with_prefix<P: AsRef<Path>>(store_dir: P, prefix: &'static str) -> Result<Self> {
+        let store_dir = store_dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&store_dir)
+            .with_context(|| format!("Failed to create journal {:?}", store_dir))?;
+
+        let trading_day = Utc::now().date_naive();
+        let writer = Self::open_day(&store_dir, prefix, trading_day)?;
+        info!("✅ Journal '{}' open in {:?}", prefix, store_dir);
+
+        Ok(Self {
+            store_dir,
+            prefix,
+            state: Mutex::new(JournalState {
+                trading_day,
+                writer,
+                records: 0,
+            }),
+        })
+    }
+
+    fn day_file(store_dir: &Path, prefix: &str, trading_day: NaiveDate) -> PathBuf {
+        store_dir.join(format!("{}_{}.jsonl", prefix, trading_day.format("%Y%m%d")))
+    }
+
+    fn open_day(store_dir: &Path, prefix: &str, trading_day: NaiveDate) -> Result<BufWriter<File>> {
+        let path = Self::day_file(store_dir, prefix, trading_day);
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open journal file {:?}", path))?;
+        Ok(BufWriter::new(file))
+    }
+
+    /// Append one record as a JSON line, switching files at midnight UTC
+    pub fn append<R: Serialize>(&self, record: &R) -> Result<()> {
+        let line = serde_json::to_string(record).context("Failed to serialize journal record")?;
+
+        let mut state = self.state.lock().unwrap();
+        let today = Utc::now().date_naive();
+        if today != state.trading_day {
+            state.writer.flush().context("Failed to flush journal")?;
+            state.writer = Self::open_day(&self.store_dir, self.prefix, today)?;
+            state.trading_day = today;
+        }
+        writeln!(state.writer, "{}", line).context("Failed to write journal record")?;
+        state.records += 1;
+        Ok(())
+    }
+
+    /// Write buffered records to disk
+    pub fn flush(&self) -> Result<()> {
+        self.state
+            .lock()
+            .unwrap()
+            .writer
+            .flush()
+            .context("Failed to flush journal")
+    }
+
+    /// Records appended since the journal was opened
+    pub fn records(&self) -> u64 {
+        self.state.lock().unwrap().records
+    }
+}
+
+impl Drop for OrderJournal {
+    fn drop(&mut self) {
+        if let Ok(state) = self.state.get_mut() {
+            let _ = state.writer.flush();
+        }
+    }
+}
@@ -1,12 +1,67 @@
+pub mod account;
+pub mod batch;
+pub mod blotter;
+pub mod cl_ord_id_registry;
 pub mod dds_client;
+pub mod dead_letter;
+pub mod gap_fill;
+pub mod gateway;
+pub mod journal;
+pub mod logout;
 pub mod report;
 
 pub mod common;
 pub mod new_order_single;
+pub mod order_builder;
+pub mod order_cancel_request;
+pub mod order_event;
+pub mod order_handle;
+pub mod order_mass_cancel_request;
+pub mod order_mass_status_request;
 pub mod order_message;
+pub mod order_state;
+pub mod pnl;
+pub mod positions;
+pub mod replace_book;
+pub mod risk;
+pub mod sequence;
+pub mod shutdown;
+pub mod supervisor;
+pub mod throttle;
+pub mod utils;
 
 // Re-export key types for easier usage following OMS architecture patterns
+pub use account::{Account, AccountContext};
+pub use batch::{BatchHandle, BatchMode, BatchOutcome, OrderSpec};
+pub use blotter::{BlotterChange, BlotterQuery, OrderBlotter, OrderRecord};
+pub use cl_ord_id_registry::ClOrdIdRegistry;
 pub use dds_client::{ConnectionStatus, OrderDdsClient};
-pub use new_order_single::{generate_unique_cl_ord_id, Header, NewOrderSingle};
-pub use order_message::{generate_unique_order_id, OrderRequest, OrderSide, OrderType};
+pub use dead_letter::{DeadLetter, DeadLetterCounts, DeadLetterKind, DeadLetterQueue};
+pub use gap_fill::{ClosedGap, GapFillConfig, GapFiller, GapResolution, InboundMessage, OpenGap};
+pub use gateway::{
+    Amendment, GatewayCall, LoopbackGateway, OrderGateway, RecordedCall, RecordingGateway,
+};
+pub use journal::OrderJournal;
+pub use logout::Logout;
+#[allow(deprecated)]
+pub use new_order_single::generate_unique_cl_ord_id;
+pub use new_order_single::{Header, NewOrderSingle};
+pub use order_builder::{OrderBuilder, OrderTarget, TimeInForce};
+pub use order_cancel_request::{OrderCancelReplaceRequest, OrderCancelRequest};
+pub use order_event::{OrderEvent, OrderEventData, ReportSource};
+pub use order_handle::{OrderEvents, OrderHandle, OrderTracker, OrderUpdate};
+pub use order_mass_cancel_request::OrderMassCancelRequest;
+pub use order_mass_status_request::OrderMassStatusRequest;
+#[allow(deprecated)]
+pub use order_message::generate_unique_order_id;
+pub use order_message::{OrderRequest, OrderSide, OrderType};
+pub use order_state::{OrderLifecycle, OrderState, TransitionPolicy};
+pub use positions::{Position, PositionChange, PositionKeeper};
+pub use replace_book::ReplaceBook;
 pub use report::OrderResponseReport;
+pub use risk::{RiskEngine, RiskOrder, SymbolConfig, UserConfig};
+pub use sequence::{ResetSchedule, SequenceStore, SessionId};
+pub use shutdown::{OrderDrain, Shutdown, ShutdownOptions, ShutdownTrigger};
+pub use supervisor::{CancellationToken, RestartBackoff, Supervisor, TaskHealth, TaskStatus};
+pub use throttle::{RateLimit, RateLimiter, RateLimiterConfig, RateLimits, ThrottlePolicy};
+pub use utils::order_id_generator::{IdGenerator, IdGeneratorConfig, IdStrategy, OrderIdGenerator};
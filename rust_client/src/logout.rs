@@ -0,0 +1,55 @@
+// src/logout.rs - Session logout sent on shutdown
+use crate::new_order_single::Header;
+use serde::{Deserialize, Serialize};
+
+/// Module matching C++ DistributedATS_Logout namespace
+pub mod distributed_ats_logout {
+    use super::*;
+
+    /// Logout (MsgType 5) matching C++ DistributedATS_Logout::Logout
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct Logout {
+        #[serde(rename = "DATS_Source")]
+        pub dats_source: String,
+
+        #[serde(rename = "DATS_Destination")]
+        pub dats_destination: String,
+
+        #[serde(rename = "DATS_SourceUser")]
+        pub dats_source_user: String,
+
+        #[serde(rename = "DATS_DestinationUser")]
+        pub dats_destination_user: String,
+
+        #[serde(rename = "fix_header")]
+        pub fix_header: Header,
+
+        #[serde(rename = "Text")]
+        pub text: String,
+    }
+
+    impl Logout {
+        /// Logout with the given reason, with routing and header left to the caller
+        pub fn new(text: &str) -> Self {
+            Self {
+                dats_source: String::new(),
+                dats_destination: String::new(),
+                dats_source_user: String::new(),
+                dats_destination_user: String::new(),
+                fix_header: Header {
+                    msg_type: "5".to_string(),
+                    ..Header::default()
+                },
+                text: text.to_string(),
+            }
+        }
+
+        /// Get the full type name exactly matching C++ namespace
+        pub const fn type_name() -> &'static str {
+            "DistributedATS_Logout::Logout"
+        }
+    }
+}
+
+// Re-export for easier usage
+pub use distributed_ats_logout::*;
@@ -1,18 +1,91 @@
 use anyhow::Result;
-use log::info;
+use chrono::NaiveTime;
+use log::{info, warn};
 use metrics::{counter, histogram};
 use oms_rust_client::common::DdsInitializer;
-use oms_rust_client::report::ExecutionReport;
-use oms_rust_client::report::ExecutionReportListener;
-use oms_rust_client::report::OrderResponseListener;
-use oms_rust_client::{OrderDdsClient, OrderResponseReport, OrderSide};
+use oms_rust_client::pnl::{CostMethod, MarkSource, PnlEngine};
+use oms_rust_client::report::{
+    ExecutionReport, ExecutionReportListener, OrderCancelReject, OrderCancelRejectListener,
+    OrderMassCancelReport, OrderMassCancelReportListener, OrderResponseListener, ReportListener,
+    ReportMessage,
+};
+use oms_rust_client::risk::load_user_configs;
+use oms_rust_client::shutdown::drain_orders;
+use oms_rust_client::{
+    Amendment, BatchMode, BlotterQuery, ClOrdIdRegistry, DeadLetterQueue, GapFiller,
+    IdGeneratorConfig, IdStrategy, NewOrderSingle, OrderBuilder, OrderDdsClient, OrderGateway,
+    OrderIdGenerator, OrderJournal, OrderRequest, OrderResponseReport, OrderSide, PositionKeeper,
+    RateLimit, RateLimiter, RateLimiterConfig, RateLimits, ResetSchedule, RiskEngine,
+    SequenceStore, Shutdown, ShutdownOptions, ShutdownTrigger, Supervisor, ThrottlePolicy,
+};
 use std::collections::HashSet;
 use std::fs::OpenOptions;
 use std::io::{self, Write};
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::oneshot;
 use tokio::time::sleep;
 use tokio::time::Instant;
+
+/// Optional start-of-day positions used to seed the position keeper
+const START_OF_DAY_POSITIONS_FILE: &str = "config/start_of_day_positions.json";
+/// Currency PnL is reported in
+const ACCOUNT_CURRENCY: &str = "USD";
+/// How often the PnL snapshot is written to the log
+const PNL_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(60);
+/// User and symbol limits shared with the OMS RiskValidator
+const USER_CONFIG_FILE: &str = "../config/users.json";
+const SYMBOL_CONFIG_FILE: &str = "../config/symbols.json";
+/// Limits of client-only accounts the OMS configuration does not list
+const CLIENT_USER_CONFIG_FILE: &str = "config/client_users.json";
+/// Per-day files of issued ClOrdIDs, so duplicates are refused across restarts
+const CL_ORD_ID_STORE_DIR: &str = "state/cl_ord_ids";
+/// Persisted counter of the session ClOrdID generator
+const ID_GENERATOR_STATE_FILE: &str = "state/order_id_generator.json";
+/// Session prefix of generated ClOrdIDs
+const CL_ORD_ID_PREFIX: &str = "RC1-";
+/// Longest ClOrdID accepted by the venue
+const MAX_CL_ORD_ID_LENGTH: usize = 20;
+/// Per-session FIX sequence numbers, reset daily at midnight UTC
+const SEQUENCE_STORE_DIR: &str = "state/sequences";
+/// How often unanswered gap fill requests are retried
+const GAP_FILL_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// Per-day journal of orders archived from the listener caches
+const ORDER_JOURNAL_DIR: &str = "state/journal";
+/// Per-day files of undecodable samples and reports callbacks panicked on
+const DEAD_LETTER_DIR: &str = "state/dead_letters";
+/// How often terminal orders past their TTL are archived when no reports arrive
+const CACHE_EXPIRY_INTERVAL: Duration = Duration::from_secs(60);
+/// How long listeners get to process reports already received when stopping
+const LISTENER_DRAIN_DEADLINE: Duration = Duration::from_secs(5);
+/// Longest an order waits in the outbound queue for a rate limit token
+const THROTTLE_MAX_WAIT: Duration = Duration::from_secs(2);
+/// How long shutdown waits for open orders to be acknowledged or cancelled
+const SHUTDOWN_ACK_DEADLINE: Duration = Duration::from_secs(5);
+/// Command line flag mass-cancelling open orders on exit
+const CANCEL_ON_EXIT_FLAG: &str = "--cancel-on-exit";
+
+/// Outbound rate limits: orders queue behind the global and per-user buckets,
+/// per-symbol bursts and excess cancels are rejected
+fn rate_limiter_config() -> RateLimiterConfig {
+    let queue = ThrottlePolicy::Queue {
+        max_wait: THROTTLE_MAX_WAIT,
+    };
+    RateLimiterConfig {
+        orders: RateLimits {
+            global: Some(RateLimit::new(200.0, 50.0, queue)),
+            per_user: Some(RateLimit::new(100.0, 20.0, queue)),
+            per_symbol: Some(RateLimit::new(100.0, 50.0, ThrottlePolicy::Reject)),
+            ..RateLimits::default()
+        },
+        cancels: RateLimits {
+            global: Some(RateLimit::new(50.0, 50.0, ThrottlePolicy::Reject)),
+            ..RateLimits::default()
+        },
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logging following OMS patterns
@@ -40,27 +113,223 @@ async fn main() -> Result<()> {
 
     info!("🚀 OMS Rust Client starting - Financial trading system initialized");
 
+    let shutdown_options = ShutdownOptions {
+        cancel_open_orders: std::env::args().any(|arg| arg == CANCEL_ON_EXIT_FLAG),
+        ack_deadline: SHUTDOWN_ACK_DEADLINE,
+        ..ShutdownOptions::default()
+    };
+
+    // Exit command, SIGINT/SIGTERM and uncaught panics all shut down the same way
+    let shutdown = Arc::new(Shutdown::new());
+    shutdown.listen_for_signals()?;
+    shutdown.install_panic_hook();
+
     println!("=== Enhanced OMS Rust Client with ExecutionReport Listener ===");
     println!("Following OMS architecture patterns for financial trading");
 
     // Initialize order client following OMS architecture
     let dds_initialzer = DdsInitializer::initialze().await?;
-    let order_client = OrderDdsClient::new(
+    let mut order_client = OrderDdsClient::new(
         dds_initialzer.order_request_data_writer,
         dds_initialzer.new_order_single_data_writer,
+        dds_initialzer.order_cancel_request_data_writer,
+        dds_initialzer.order_cancel_replace_request_data_writer,
     )
-    .await?;
+    .await?
+    .with_rate_limiter(Arc::new(RateLimiter::new(rate_limiter_config())))
+    .with_mass_cancel_writer(dds_initialzer.order_mass_cancel_request_data_writer)
+    .with_logout_writer(dds_initialzer.logout_data_writer);
+
+    // Session prefix + counter IDs, unique across restarts
+    let id_generator = OrderIdGenerator::from_config(&IdGeneratorConfig {
+        strategy: IdStrategy::SessionCounter {
+            prefix: CL_ORD_ID_PREFIX.to_string(),
+        },
+        state_file: Some(ID_GENERATOR_STATE_FILE.into()),
+        max_length: MAX_CL_ORD_ID_LENGTH,
+    })?;
+    order_client = order_client.with_id_generator(Arc::new(id_generator));
+
+    // Outbound MsgSeqNum per session, kept across restarts
+    let sequence_store =
+        SequenceStore::open(SEQUENCE_STORE_DIR, ResetSchedule::DailyAt(NaiveTime::MIN))?;
+    order_client = order_client.with_sequence_store(Arc::new(sequence_store));
+
+    // Duplicate ClOrdID protection for the current trading day
+    match ClOrdIdRegistry::open(CL_ORD_ID_STORE_DIR) {
+        Ok(registry) => order_client = order_client.with_cl_ord_id_registry(Arc::new(registry)),
+        Err(e) => {
+            eprintln!(
+                "❌ Failed to open ClOrdID store, duplicates only checked in memory: {:#}",
+                e
+            );
+            warn!("❌ Failed to open ClOrdID store: {:#}", e);
+        }
+    }
+
+    // Pre-trade risk with the same limits the OMS enforces; NewOrderSingles of an
+    // account count against its OMS user
+    match RiskEngine::load(USER_CONFIG_FILE, SYMBOL_CONFIG_FILE) {
+        Ok(mut risk_engine) => {
+            if Path::new(CLIENT_USER_CONFIG_FILE).exists() {
+                match load_user_configs(CLIENT_USER_CONFIG_FILE) {
+                    Ok(users) => risk_engine = risk_engine.with_users(users),
+                    Err(e) => eprintln!("❌ Failed to load client accounts: {:#}", e),
+                }
+            }
+            order_client = order_client.with_risk_engine(Arc::new(risk_engine));
+        }
+        Err(e) => {
+            eprintln!(
+                "❌ Failed to load risk limits, only parameter checks apply: {:#}",
+                e
+            );
+            warn!("❌ Failed to load risk limits: {:#}", e);
+        }
+    }
+
+    // Inbound MsgSeqNum gaps from the matching engine, filled with status requests
+    let gap_filler = Arc::new(GapFiller::new(
+        order_client.sequence_store(),
+        dds_initialzer.order_mass_status_request_data_writer,
+    ));
+    let gap_fill_checker = Arc::clone(&gap_filler).spawn_timeout_checker(GAP_FILL_CHECK_INTERVAL);
+
+    // Orders leaving the bounded listener caches are archived here
+    let order_journal = match OrderJournal::open(ORDER_JOURNAL_DIR) {
+        Ok(journal) => Some(Arc::new(journal)),
+        Err(e) => {
+            eprintln!(
+                "❌ Failed to open order journal, evicted orders are not archived: {:#}",
+                e
+            );
+            warn!("❌ Failed to open order journal: {:#}", e);
+            None
+        }
+    };
+
+    // Samples listeners cannot process are kept here for inspection
+    let dead_letters = match DeadLetterQueue::open(DEAD_LETTER_DIR) {
+        Ok(queue) => Some(Arc::new(queue)),
+        Err(e) => {
+            eprintln!(
+                "❌ Failed to open dead-letter file, failed samples are only logged: {:#}",
+                e
+            );
+            warn!("❌ Failed to open dead-letter file: {:#}", e);
+            None
+        }
+    };
 
     // Initialize execution report listener following OMS real-time processing patterns
-    let order_response_listener: Arc<OrderResponseListener> =
-        Arc::new(OrderResponseListener::new(dds_initialzer.order_response_datareader).await?);
-    let execution_report_listener =
-        ExecutionReportListener::new(dds_initialzer.execution_report_data_reader).await?;
+    let order_response_listener = Arc::new(with_storage(
+        OrderResponseListener::new(dds_initialzer.order_response_datareader),
+        &order_journal,
+        &dead_letters,
+    ));
+    let execution_report_listener = Arc::new(with_storage(
+        ExecutionReportListener::new(dds_initialzer.execution_report_data_reader)
+            .with_gap_filler(Arc::clone(&gap_filler)),
+        &order_journal,
+        &dead_letters,
+    ));
+    let cancel_reject_listener = Arc::new(with_storage(
+        OrderCancelRejectListener::new(dds_initialzer.order_cancel_reject_datareader)
+            .with_gap_filler(Arc::clone(&gap_filler)),
+        &order_journal,
+        &dead_letters,
+    ));
+    let mass_cancel_report_listener = Arc::new(with_storage(
+        OrderMassCancelReportListener::new(dds_initialzer.order_mass_cancel_report_datareader)
+            .with_gap_filler(Arc::clone(&gap_filler)),
+        &order_journal,
+        &dead_letters,
+    ));
+    let cache_expiry = {
+        let order_responses = Arc::clone(&order_response_listener);
+        let execution_reports = Arc::clone(&execution_report_listener);
+        let cancel_rejects = Arc::clone(&cancel_reject_listener);
+        let mass_cancel_reports = Arc::clone(&mass_cancel_report_listener);
+        let order_blotter = order_client.order_blotter();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(CACHE_EXPIRY_INTERVAL);
+            loop {
+                ticker.tick().await;
+                order_responses.evict_expired();
+                execution_reports.evict_expired();
+                cancel_rejects.evict_expired();
+                mass_cancel_reports.evict_expired();
+                order_blotter.evict_finished();
+            }
+        })
+    };
+
+    // Net positions from fills, seeded from the start-of-day file when present
+    let position_keeper = Arc::new(PositionKeeper::new());
+    if Path::new(START_OF_DAY_POSITIONS_FILE).exists() {
+        if let Err(e) = position_keeper.load_start_of_day(START_OF_DAY_POSITIONS_FILE) {
+            eprintln!("❌ Failed to load start-of-day positions: {:#}", e);
+        }
+    }
+    let order_client = order_client.with_position_keeper(Arc::clone(&position_keeper));
+
+    // Realized/unrealized PnL marked to manually entered prices
+    let pnl_engine = Arc::new(PnlEngine::new(
+        CostMethod::Fifo,
+        MarkSource::Manual,
+        ACCOUNT_CURRENCY,
+    ));
+    // Start-of-day positions carry their cost basis into PnL
+    for position in position_keeper.positions() {
+        pnl_engine.seed(&position);
+    }
+    Arc::clone(&pnl_engine).spawn_snapshot_logger(PNL_SNAPSHOT_INTERVAL);
+
+    // Feed both report streams into the order tracker, blotter, positions and PnL
+    let order_blotter = order_client.order_blotter();
+    {
+        let tracker = order_client.order_tracker();
+        let blotter = order_client.order_blotter();
+        let positions = Arc::clone(&position_keeper);
+        let pnl = Arc::clone(&pnl_engine);
+        let risk = order_client.risk_engine();
+        order_response_listener
+            .register_callback(move |report: &OrderResponseReport| {
+                tracker.on_order_response(report);
+                blotter.on_order_response(report);
+                positions.on_order_response(report);
+                pnl.on_order_response(report);
+                if let Some(risk) = &risk {
+                    risk.on_order_response(report);
+                }
+            })
+            .detach();
+    }
+    {
+        let tracker = order_client.order_tracker();
+        let blotter = order_client.order_blotter();
+        let positions = Arc::clone(&position_keeper);
+        let pnl = Arc::clone(&pnl_engine);
+        let risk = order_client.risk_engine();
+        let replace_book = order_client.replace_book();
+        execution_report_listener
+            .register_callback(move |report: &ExecutionReport| {
+                tracker.on_execution_report(report);
+                replace_book.on_execution_report(report);
+                blotter.on_execution_report(report);
+                positions.on_execution_report(report);
+                pnl.on_execution_report(report);
+                if let Some(risk) = &risk {
+                    risk.on_execution_report(report);
+                }
+            })
+            .detach();
+    }
 
     // Register callback for real-time execution processing following OMS requirements
     order_response_listener
-        .register_execution_callback(|report: &OrderResponseReport| {
-            match report.get_execution_status() {
+        .register_callback(
+            |report: &OrderResponseReport| match report.get_execution_status() {
                 "Filled" => info!(
                     "🎉 Order FILLED: {} - {} shares @ {} (Exchange: {})",
                     report.order_id, report.cum_qty, report.avg_px, report.security_exchange
@@ -82,43 +351,82 @@ async fn main() -> Result<()> {
                     report.get_execution_status(),
                     report.get_execution_type()
                 ),
-            }
-        })
-        .await;
+            },
+        )
+        .detach();
 
-    execution_report_listener.register_execution_callback(|report: &ExecutionReport| match report
-        .get_execution_status()
-    {
-        "Filled" => info!(
-            "🎉 Order FILLED: {} - {} shares @ {} (Exchange: {})",
-            report.order_id, report.cum_qty, report.avg_px, report.security_exchange
-        ),
-        "Rejected" => info!(
-            "❌ Order REJECTED: {} - Reason: {} (RejReason: {})",
-            report.order_id, report.text, report.ord_rej_reason
-        ),
-        "Partially Filled" => info!(
-            "📊 Order PARTIALLY FILLED: {} - {} of {} shares @ avg {}",
-            report.order_id, report.cum_qty, report.order_qty, report.avg_px
-        ),
-        "Pending New" => info!("⏳ Order ACCEPTED: {} - Pending execution", report.order_id),
-        _ => info!(
-            "📈 Order Status Update: {} - {} (ExecType: {})",
-            report.order_id,
-            report.get_execution_status(),
-            report.get_execution_type()
-        ),
-    });
+    execution_report_listener
+        .register_callback(
+            |report: &ExecutionReport| match report.get_execution_status() {
+                "Filled" => info!(
+                    "🎉 Order FILLED: {} - {} shares @ {} (Exchange: {})",
+                    report.order_id, report.cum_qty, report.avg_px, report.security_exchange
+                ),
+                "Rejected" => info!(
+                    "❌ Order REJECTED: {} - Reason: {} (RejReason: {})",
+                    report.order_id, report.text, report.ord_rej_reason
+                ),
+                "Partially Filled" => info!(
+                    "📊 Order PARTIALLY FILLED: {} - {} of {} shares @ avg {}",
+                    report.order_id, report.cum_qty, report.order_qty, report.avg_px
+                ),
+                "Pending New" => {
+                    info!("⏳ Order ACCEPTED: {} - Pending execution", report.order_id)
+                }
+                _ => info!(
+                    "📈 Order Status Update: {} - {} (ExecType: {})",
+                    report.order_id,
+                    report.get_execution_status(),
+                    report.get_execution_type()
+                ),
+            },
+        )
+        .detach();
 
     // Wait for discovery following OMS connection patterns
     println!("⏳ Discovering OMS components...");
 
-    {
-        let l = Arc::clone(&order_response_listener);
-        tokio::spawn(async move {
-            l.run().await;
-        });
-    }
+    let replace_book = order_client.replace_book();
+    cancel_reject_listener
+        .register_callback(move |reject: &OrderCancelReject| {
+            replace_book.on_cancel_reject(reject);
+            warn!(
+                "❌ {} REJECTED for order {}: {}",
+                reject.get_response_to(),
+                reject.orig_cl_ord_id,
+                reject.text
+            );
+            println!(
+                "❌ {} of order {} rejected: {}",
+                reject.get_response_to(),
+                reject.orig_cl_ord_id,
+                reject.text
+            );
+        })
+        .detach();
+    mass_cancel_report_listener
+        .register_callback(|report: &OrderMassCancelReport| {
+            info!(
+                "🧹 Mass cancel {} for {}: {}",
+                if report.is_rejected() {
+                    "REJECTED"
+                } else {
+                    "accepted"
+                },
+                report.symbol,
+                report.text
+            );
+        })
+        .detach();
+
+    // Listener tasks start together, are restarted on failure and stop on exit
+    let supervisor = Supervisor::new().with_drain_deadline(LISTENER_DRAIN_DEADLINE);
+    supervise_listener(&supervisor, &order_response_listener);
+    supervise_listener(&supervisor, &execution_report_listener);
+    supervise_listener(&supervisor, &cancel_reject_listener);
+    supervise_listener(&supervisor, &mass_cancel_report_listener);
+    supervisor.start();
+
     println!("\n🚀 Enhanced Financial Trading Client Ready!");
     println!("Commands following OMS architecture:");
     println!("  1 - Send OrderRequest to OMS (BTC market buy)");
@@ -127,18 +435,34 @@ async fn main() -> Result<()> {
     println!("  4 - Test order with execution monitoring");
     println!("  5 - View execution report cache");
     println!("  6 - Interactive order creation");
+    println!("  p - Show positions, currency exposure and PnL");
+    println!("  m - Set manual mark price");
+    println!("  r - Resend an order");
+    println!("  b - Send a basket of 20 OrderRequests in one batch");
+    println!("  c - Cancel an order");
+    println!("  a - Amend an order's quantity or price");
     println!("  s - Show connection status");
     println!("  0 - Exit");
+    if shutdown_options.cancel_open_orders {
+        println!("Open orders are cancelled on exit");
+    }
     let mut sent_order_ids: HashSet<String> = HashSet::new();
 
     loop {
-        print!("\nEnter command (0-6,s): ");
+        print!("\nEnter command (0-6,p,m,r,b,c,a,s): ");
         io::stdout().flush()?;
 
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        let input = tokio::select! {
+            input = read_command() => input?,
+            trigger = shutdown.triggered() => {
+                println!("\n👋 Shutting down on {}...", trigger);
+                break;
+            }
+        };
 
-        match input.trim() {
+        // End of input exits like the command
+        let command = if input.is_empty() { "0" } else { input.trim() };
+        match command {
             "1" => {
                 // Send OrderRequest to OMS following architecture patterns
                 let mut count = 0;
@@ -150,11 +474,11 @@ async fn main() -> Result<()> {
                         .send_market_order("BTC-USD", OrderSide::BUY, 1000.0)
                         .await
                     {
-                        Ok(order_id) => {
-                            println!("✅ Sent OrderRequest to OMS: {}", order_id);
+                        Ok(handle) => {
+                            println!("✅ Sent OrderRequest to OMS: {}", handle.cl_ord_id());
                             println!("   Monitor execution reports for status updates");
                             messages_count += 1;
-                            sent_order_ids.insert(order_id);
+                            sent_order_ids.insert(handle.cl_ord_id().to_string());
                         }
                         Err(e) => eprintln!("❌ Failed to send OrderRequest: {}", e),
                     }
@@ -185,67 +509,71 @@ async fn main() -> Result<()> {
                     .send_market_new_order_single("BTC-USD", "1", 1000)
                     .await
                 {
-                    Ok(cl_ord_id) => {
+                    Ok(handle) => {
                         println!(
                             "✅ Order sent: {} - Monitoring for 10 seconds...",
-                            cl_ord_id
+                            handle.cl_ord_id()
                         );
 
-                        // Monitor for execution reports following OMS tracking patterns
-                        for _ in 1..=10 {
-                            if let Some(status) =
-                                order_response_listener.get_order_status(&cl_ord_id).await
-                            {
-                                println!(
-                                    "📊 Order {} status: {} (CumQty: {}/{})",
-                                    cl_ord_id,
-                                    status.get_execution_status(),
-                                    status.cum_qty,
-                                    status.order_qty
-                                );
-
-                                if status.is_completely_filled() || status.is_order_rejected() {
-                                    break;
-                                }
-                            }
-
-                            print!(".");
-                            io::stdout().flush()?;
-                            sleep(Duration::from_secs(1)).await;
+                        // Print every report as it arrives until the order is done
+                        let mut events = handle.events(Duration::from_secs(10));
+                        while let Some(status) = events.next().await {
+                            println!(
+                                "📊 Order {} status: {} (CumQty: {}/{})",
+                                handle.cl_ord_id(),
+                                status.get_execution_status(),
+                                status.cum_qty,
+                                status.order_qty
+                            );
+                        }
+
+                        match handle.done(Duration::ZERO).await {
+                            Ok(status) => println!(
+                                "\n✅ Monitoring complete: {}",
+                                status.get_execution_status()
+                            ),
+                            Err(e) => println!("\n⚠️ Monitoring complete: {}", e),
                         }
-                        println!("\n✅ Monitoring complete");
                     }
                     Err(e) => eprintln!("❌ Failed to send test order: {}", e),
                 }
             }
             "5" => {
-                // View execution report cache following OMS monitoring patterns
-                println!("📊 Execution Report Cache:");
+                // View order blotter following OMS monitoring patterns
+                println!("📊 Order Blotter:");
                 let mut missing_orders: Vec<String> = Vec::new();
-                let statuses = order_response_listener.get_all_order_statuses().await;
+                let records = order_blotter.query(&BlotterQuery::default());
+                let reported = records
+                    .iter()
+                    .filter(|record| record.last_update.is_some())
+                    .count();
 
-                if statuses.is_empty() {
+                if reported == 0 {
                     println!("   No execution reports cached yet");
                 } else {
-                    for (order_id, report) in statuses.iter() {
+                    for record in records.iter().filter(|r| r.last_update.is_some()) {
+                        let avg_px = record.last_update.as_ref().map_or(0.0, |u| u.avg_px);
                         println!(
                             "   Order {}: {} - {} shares @ {} ({})",
-                            order_id,
-                            report.get_execution_status(),
-                            report.cum_qty,
-                            report.avg_px,
-                            report.symbol
+                            record.cl_ord_id,
+                            record.state(),
+                            record.lifecycle.cum_qty(),
+                            avg_px,
+                            record.symbol
                         );
                     }
 
                     println!(
                         "Sent Orders {}, Recieved reports {},",
                         sent_order_ids.len(),
-                        statuses.len()
+                        reported
                     );
 
                     for order_id in &sent_order_ids {
-                        if !statuses.contains_key(order_id) {
+                        let has_report = order_blotter
+                            .get(order_id)
+                            .is_some_and(|record| record.last_update.is_some());
+                        if !has_report {
                             missing_orders.push(order_id.clone());
                         }
                     }
@@ -256,42 +584,179 @@ async fn main() -> Result<()> {
                             println!(".    - {}", order_id);
                         }
                     } else {
-                        println!(
-                            "✅ All orders recieved execution report count{}",
-                            statuses.len()
-                        );
+                        println!("✅ All orders recieved execution report count{}", reported);
                     }
                 }
             }
             "6" => {
-                if let Err(e) = create_interactive_order(&order_client).await {
+                let account = order_client.default_account().source_user().to_string();
+                if let Err(e) = create_interactive_order(&order_client, &account).await {
                     eprintln!("❌ Failed to create interactive order: {}", e);
                 }
             }
+            "p" => {
+                println!("📊 Positions:");
+                let positions = position_keeper.positions();
+                if positions.is_empty() {
+                    println!("   No positions yet");
+                }
+                for position in positions {
+                    println!(
+                        "   {} {}: {} @ avg {}",
+                        position.user_id, position.symbol, position.net_qty, position.avg_cost
+                    );
+                }
+                println!("💱 Currency exposure:");
+                for (currency, amount) in position_keeper.exposures() {
+                    println!("   {}: {}", currency, amount);
+                }
+                println!("💰 PnL ({}):", pnl_engine.account_currency());
+                for pnl in pnl_engine.all_pnl() {
+                    println!(
+                        "   {} {}: realized {} {}, unrealized {:?} {} (mark {:?})",
+                        pnl.user_id,
+                        pnl.symbol,
+                        pnl.realized,
+                        pnl.currency,
+                        pnl.unrealized,
+                        pnl.currency,
+                        pnl.mark
+                    );
+                }
+                for user_id in pnl_engine.users() {
+                    let total = pnl_engine.user_pnl(&user_id);
+                    println!(
+                        "   {} total: {} {} (unpriced: {:?})",
+                        user_id,
+                        total.total(),
+                        total.currency,
+                        total.unpriced_symbols
+                    );
+                }
+            }
+            "m" => {
+                if let Err(e) = set_manual_mark(&pnl_engine) {
+                    eprintln!("❌ Failed to set mark price: {}", e);
+                }
+            }
+            "r" => {
+                print!("ClOrdID to resend: ");
+                io::stdout().flush()?;
+                let mut cl_ord_id = String::new();
+                io::stdin().read_line(&mut cl_ord_id)?;
+
+                match order_client.resend(cl_ord_id.trim()).await {
+                    Ok(handle) => println!("🔁 Resent order {}", handle.cl_ord_id()),
+                    Err(e) => eprintln!("❌ Failed to resend order: {:#}", e),
+                }
+            }
+            "b" => {
+                let account = order_client.default_account();
+                let mut basket = Vec::new();
+                for _ in 0..20 {
+                    let order: OrderRequest =
+                        OrderBuilder::market("BTC-USD", OrderSide::BUY, 1000.0)
+                            .cl_ord_id(&order_client.next_cl_ord_id()?)
+                            .account(account.user_id())
+                            .build()?;
+                    basket.push(order.into());
+                }
+
+                match order_client.send_batch(basket, BatchMode::BestEffort).await {
+                    Ok(outcome) => {
+                        println!(
+                            "📦 Basket sent: {} orders, {} failed",
+                            outcome.sent(),
+                            outcome.failed()
+                        );
+                        for handle in outcome.handle.handles() {
+                            sent_order_ids.insert(handle.cl_ord_id().to_string());
+                        }
+                        match outcome.handle.accepted(Duration::from_secs(5)).await {
+                            Ok(acks) => println!("✅ All {} orders acknowledged", acks.len()),
+                            Err(e) => eprintln!("⚠️ Basket not fully acknowledged: {:#}", e),
+                        }
+                    }
+                    Err(e) => eprintln!("❌ Failed to send basket: {:#}", e),
+                }
+            }
+            "c" => {
+                print!("ClOrdID to cancel: ");
+                io::stdout().flush()?;
+                let mut cl_ord_id = String::new();
+                io::stdin().read_line(&mut cl_ord_id)?;
+
+                match order_client.cancel(cl_ord_id.trim()).await {
+                    Ok(handle) => println!("🚫 Cancel requested for order {}", handle.cl_ord_id()),
+                    Err(e) => eprintln!("❌ Failed to cancel order: {:#}", e),
+                }
+            }
+            "a" => {
+                if let Err(e) = amend_interactive_order(&order_client).await {
+                    eprintln!("❌ Failed to amend order: {:#}", e);
+                }
+            }
             "s" => {
                 // Show comprehensive connection status following OMS monitoring patterns
                 println!("📊 Enhanced Connection Status:");
-
-                // order_response_listener
-                //     .lock()
-                //     .await
-                //     .unwrap()
-                //     .get_connection_status();
-                // println!(
-                //     "   {}",
-                //     order_response_listener
-                //         .lock()
-                //         .await
-                //         .unwrap()
-                //         .get_connection_status()
-                // );
+                for session in order_client.sequence_store().sessions() {
+                    println!(
+                        "   Session {}: next outbound {}, next inbound {}",
+                        session.session, session.next_outbound, session.next_inbound
+                    );
+                }
+                for gap in gap_filler.open_gaps() {
+                    println!(
+                        "   ⚠️ Open gap on {}: {} of {}..{} missing, status requested {} times",
+                        gap.session,
+                        gap.missing.len(),
+                        gap.expected,
+                        gap.received - 1,
+                        gap.requests
+                    );
+                }
+                for gap in gap_filler.closed_gaps() {
+                    println!(
+                        "   ✅ Gap on {}: {}..{} {} at {}",
+                        gap.session,
+                        gap.expected,
+                        gap.received - 1,
+                        gap.resolution,
+                        gap.closed_at
+                    );
+                }
+                if let Some(rate_limiter) = order_client.rate_limiter() {
+                    let stats = rate_limiter.stats();
+                    println!(
+                        "   Throttling: passed={}, queued={}, rejected={}",
+                        stats.passed, stats.queued, stats.rejected
+                    );
+                }
+                for task in supervisor.health() {
+                    println!(
+                        "   Task {}: {}, {} restarts{}",
+                        task.name,
+                        task.health,
+                        task.restarts,
+                        task.last_error
+                            .map(|e| format!(", last error: {}", e))
+                            .unwrap_or_default()
+                    );
+                }
+                println!("   {}", order_response_listener.get_connection_status());
+                println!("   {}", execution_report_listener.get_connection_status());
+                println!("   {}", cancel_reject_listener.get_connection_status());
+                println!("   {}", mass_cancel_report_listener.get_connection_status());
             }
             "0" => {
                 println!("👋 Shutting down enhanced client following OMS patterns...");
+                shutdown.trigger(ShutdownTrigger::Command);
                 break;
             }
             _ => {
-                println!("❌ Invalid command. Please enter 0-6 or 's'.");
+                println!(
+                    "❌ Invalid command. Please enter 0-6, 'p', 'm', 'r', 'b', 'c', 'a' or 's'."
+                );
             }
         }
 
@@ -299,12 +764,91 @@ async fn main() -> Result<()> {
         sleep(Duration::from_millis(100)).await;
     }
 
+    // Orders are settled while the listeners still deliver their reports
+    let drain = drain_orders(&order_client, &shutdown_options).await;
+    println!(
+        "📦 Orders settled: {}, unsettled: {}, sessions logged out: {}",
+        drain.settled,
+        drain.unsettled.len(),
+        drain.logouts
+    );
+
+    if let Err(e) = supervisor.shutdown().await {
+        warn!("⚠️ Listeners did not stop cleanly: {:#}", e);
+    }
+    gap_fill_checker.abort();
+    cache_expiry.abort();
+    let _ = gap_fill_checker.await;
+    let _ = cache_expiry.await;
+
+    if let Some(journal) = &order_journal {
+        if let Err(e) = journal.flush() {
+            warn!("⚠️ Failed to flush order journal: {:#}", e);
+        }
+    }
+
+    // Readers, then writers, then the entities that created them
+    drop(supervisor);
+    drop(order_response_listener);
+    drop(execution_report_listener);
+    drop(cancel_reject_listener);
+    drop(mass_cancel_report_listener);
+    drop(order_client);
+    drop(gap_filler);
+    drop(dds_initialzer.publisher);
+    drop(dds_initialzer.subscriber);
+    drop(dds_initialzer.participant);
+
+    info!(
+        "👋 OMS Rust Client stopped on {}",
+        shutdown.reason().unwrap_or(ShutdownTrigger::Command)
+    );
+    log::logger().flush();
     println!("✅ Enhanced client shutdown complete");
     Ok(())
 }
 
+/// Read one command line off the runtime, so a shutdown can interrupt the
+/// prompt; empty at end of input
+async fn read_command() -> Result<String> {
+    let (sender, receiver) = oneshot::channel();
+    std::thread::spawn(move || {
+        let mut input = String::new();
+        let _ = sender.send(io::stdin().read_line(&mut input).map(|_| input));
+    });
+    Ok(receiver.await??)
+}
+
+/// Archive orders evicted from a listener's cache to the journal and failed
+/// samples to the dead-letter file, for those that are open
+fn with_storage<T: ReportMessage>(
+    mut listener: ReportListener<T>,
+    journal: &Option<Arc<OrderJournal>>,
+    dead_letters: &Option<Arc<DeadLetterQueue>>,
+) -> ReportListener<T> {
+    if let Some(journal) = journal {
+        listener = listener.with_journal(Arc::clone(journal));
+    }
+    if let Some(dead_letters) = dead_letters {
+        listener = listener.with_dead_letters(Arc::clone(dead_letters));
+    }
+    listener
+}
+
+/// Run a listener under the supervisor, restarting it if it fails
+fn supervise_listener<T: ReportMessage>(
+    supervisor: &Supervisor,
+    listener: &Arc<ReportListener<T>>,
+) {
+    let listener = Arc::clone(listener);
+    supervisor.supervise(T::NAME, move |token| {
+        let listener = Arc::clone(&listener);
+        async move { listener.run_until(token).await }
+    });
+}
+
 /// Interactive order creation following OMS user interface patterns
-async fn create_interactive_order(client: &OrderDdsClient) -> Result<()> {
+async fn create_interactive_order<G: OrderGateway>(gateway: &G, account: &str) -> Result<()> {
     println!("\n📝 Creating interactive order following OMS patterns...");
 
     // Get symbol following financial instrument standards
@@ -319,7 +863,11 @@ async fn create_interactive_order(client: &OrderDdsClient) -> Result<()> {
     io::stdout().flush()?;
     let mut side_input = String::new();
     io::stdin().read_line(&mut side_input)?;
-    let side = side_input.trim();
+    let side = if side_input.trim() == "2" {
+        OrderSide::SELL
+    } else {
+        OrderSide::BUY
+    };
 
     // Get quantity following risk management patterns
     print!("Quantity (integer): ");
@@ -334,22 +882,77 @@ async fn create_interactive_order(client: &OrderDdsClient) -> Result<()> {
     let mut type_input = String::new();
     io::stdin().read_line(&mut type_input)?;
 
-    let cl_ord_id = match type_input.trim() {
-        "1" => {
-            client
-                .send_market_new_order_single(&symbol, side, quantity)
-                .await?
-        }
-        _ => {
-            client
-                .send_market_new_order_single(&symbol, side, quantity)
-                .await?
+    let builder = match type_input.trim() {
+        "2" => {
+            print!("Limit price: ");
+            io::stdout().flush()?;
+            let mut price_input = String::new();
+            io::stdin().read_line(&mut price_input)?;
+            let price: f64 = price_input.trim().parse()?;
+
+            OrderBuilder::limit(&symbol, side, quantity as f64, price)
         }
+        _ => OrderBuilder::market(&symbol, side, quantity as f64),
     };
 
+    let order: NewOrderSingle = builder
+        .cl_ord_id(&gateway.next_cl_ord_id()?)
+        .account(account)
+        .build()?;
+    let handle = gateway.send(order).await?;
+
     println!(
         "✅ Interactive order sent following OMS patterns: {}",
-        cl_ord_id
+        handle.cl_ord_id()
     );
     Ok(())
 }
+
+/// Prompt for an order and its new quantity and price; empty input keeps a value
+async fn amend_interactive_order<G: OrderGateway>(gateway: &G) -> Result<()> {
+    print!("ClOrdID to amend: ");
+    io::stdout().flush()?;
+    let mut cl_ord_id = String::new();
+    io::stdin().read_line(&mut cl_ord_id)?;
+
+    print!("New quantity (empty to keep): ");
+    io::stdout().flush()?;
+    let mut quantity_input = String::new();
+    io::stdin().read_line(&mut quantity_input)?;
+
+    print!("New price (empty to keep): ");
+    io::stdout().flush()?;
+    let mut price_input = String::new();
+    io::stdin().read_line(&mut price_input)?;
+
+    let mut amendment = Amendment::default();
+    if !quantity_input.trim().is_empty() {
+        amendment = amendment.quantity(quantity_input.trim().parse()?);
+    }
+    if !price_input.trim().is_empty() {
+        amendment = amendment.price(price_input.trim().parse()?);
+    }
+
+    let handle = gateway.amend(cl_ord_id.trim(), amendment).await?;
+    println!("✏️ Amend requested for order {}", handle.cl_ord_id());
+    Ok(())
+}
+
+/// Prompt for a symbol and mark price used for unrealized PnL
+fn set_manual_mark(pnl_engine: &PnlEngine) -> Result<()> {
+    print!("Symbol (e.g., BTC-USD, EURUSD): ");
+    io::stdout().flush()?;
+    let mut symbol = String::new();
+    io::stdin().read_line(&mut symbol)?;
+    let symbol = symbol.trim().to_uppercase();
+
+    print!("Mark price: ");
+    io::stdout().flush()?;
+    let mut price_input = String::new();
+    io::stdin().read_line(&mut price_input)?;
+    let price: f64 = price_input.trim().parse()?;
+
+    pnl_engine.set_manual_mark(&symbol, price);
+    println!("✅ Mark for {} set to {}", symbol, price);
+    Ok(())
+}
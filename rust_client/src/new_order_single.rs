@@ -1,6 +1,5 @@
 // src/distributed_ats_new_order_single.rs - Create new module to match C++ namespace
 use chrono::Utc;
-use rustdds::*;
 use serde::{Deserialize, Serialize};
 
 /// Module matching C++ DistributedATS_NewOrderSingle namespace
@@ -16,8 +15,9 @@ pub mod distributed_ats_new_order_single {
         pub sender_comp_id: String,
         pub target_comp_id: String,
         pub msg_seq_num: i32,
-        pub sending_time: String,
-        pub checksum: String,
+        pub sender_sub_id: String,
+        pub target_sub_id: String,
+        pub sending_time: u64,
     }
 
     impl Default for Header {
@@ -29,8 +29,9 @@ pub mod distributed_ats_new_order_single {
                 sender_comp_id: "RUST_CLIENT".to_string(),
                 target_comp_id: "OMS".to_string(),
                 msg_seq_num: 1,
-                sending_time: Utc::now().format("%Y%m%d-%H:%M:%S%.3f").to_string(),
-                checksum: "000".to_string(),
+                sender_sub_id: String::new(),
+                target_sub_id: String::new(),
+                sending_time: Utc::now().timestamp_millis() as u64,
             }
         }
     }
@@ -51,36 +52,48 @@ pub mod distributed_ats_new_order_single {
         #[serde(rename = "DATS_DestinationUser")]
         pub dats_destination_user: String,
 
-        // Security Exchange
-        #[serde(rename = "SecurityExchange")]
-        pub security_exchange: String,
         #[serde(rename = "fix_header")]
         pub fix_header: Header,
 
         #[serde(rename = "ClOrdID")]
         pub cl_ord_id: String,
 
-        // Additional FIX fields for complete order representation
+        #[serde(rename = "ExecInst")]
+        pub exec_inst: String,
+
         #[serde(rename = "Symbol")]
         pub symbol: String,
 
+        #[serde(rename = "SecurityExchange")]
+        pub security_exchange: String,
+
         #[serde(rename = "Side")]
-        pub side: String, // Use String to match C++ char representation
+        pub side: u8, // C++ char maps to Rust u8
+
+        #[serde(rename = "TransactTime")]
+        pub transact_time: u64,
 
         #[serde(rename = "OrderQty")]
         pub order_qty: i32,
 
         #[serde(rename = "OrdType")]
-        pub ord_type: String, // Use String to match C++ char representation
+        pub ord_type: u8, // C++ char maps to Rust u8
 
         #[serde(rename = "Price")]
-        pub price: f64,
+        pub price: f32,
+
+        #[serde(rename = "StopPx")]
+        pub stop_px: f32,
 
         #[serde(rename = "TimeInForce")]
-        pub time_in_force: String,
+        pub time_in_force: u8, // C++ char maps to Rust u8
 
-        #[serde(rename = "TransactTime")]
-        pub transact_time: u64,
+        /// ExpireTime (126) of GTD orders in epoch milliseconds, 0 otherwise
+        #[serde(rename = "ExpireTime")]
+        pub expire_time: u64,
+
+        #[serde(rename = "Text")]
+        pub text: String,
     }
 
     impl NewOrderSingle {
@@ -92,9 +105,8 @@ pub mod distributed_ats_new_order_single {
             quantity: i32,
             source_user: String,
         ) -> Self {
-            let mut header = Header::default();
-            header.msg_seq_num = Self::generate_sequence_number();
-            header.sending_time = Utc::now().format("%Y%m%d-%H:%M:%S%.3f").to_string();
+            // MsgSeqNum is allocated per session by the client when the order is sent
+            let header = Header::default();
 
             Self {
                 dats_source: "RUST_CLIENT".to_string(),
@@ -103,14 +115,18 @@ pub mod distributed_ats_new_order_single {
                 dats_destination_user: "DATA_SERVICE_A".to_string(),
                 fix_header: header,
                 cl_ord_id,
+                exec_inst: String::new(),
                 symbol,
-                side: side.to_string(),
-                order_qty: quantity,
-                ord_type: "1".to_string(), // Market order
-                price: 0.0,
-                security_exchange: "BTC_MARKET".to_string(), // Market orders have no price
-                time_in_force: "0".to_string(),              // Day order
+                security_exchange: "BTC_MARKET".to_string(),
+                side: side.bytes().next().unwrap_or(0),
                 transact_time: Utc::now().timestamp_millis() as u64,
+                order_qty: quantity,
+                ord_type: b'1', // Market order
+                price: 0.0,     // Market orders have no price
+                stop_px: 0.0,
+                time_in_force: b'0', // Day order
+                expire_time: 0,
+                text: String::new(),
             }
         }
 
@@ -123,9 +139,8 @@ pub mod distributed_ats_new_order_single {
             price: f64,
             source_user: String,
         ) -> Self {
-            let mut header = Header::default();
-            header.msg_seq_num = Self::generate_sequence_number();
-            header.sending_time = Utc::now().format("%Y%m%d-%H:%M:%S%.3f").to_string();
+            // MsgSeqNum is allocated per session by the client when the order is sent
+            let header = Header::default();
 
             Self {
                 dats_source: "RUST_CLIENT".to_string(),
@@ -134,35 +149,33 @@ pub mod distributed_ats_new_order_single {
                 dats_destination_user: "DATA_SERVICE_A".to_string(),
                 fix_header: header,
                 cl_ord_id,
+                exec_inst: String::new(),
                 symbol,
                 security_exchange: "BTC_MARKET".to_string(),
-                side: side.to_string(),
-                order_qty: quantity,
-                ord_type: "2".to_string(), // Limit order
-                price,
-                time_in_force: "0".to_string(), // Day order
+                side: side.bytes().next().unwrap_or(0),
                 transact_time: Utc::now().timestamp_millis() as u64,
+                order_qty: quantity,
+                ord_type: b'2', // Limit order
+                price: price as f32,
+                stop_px: 0.0,
+                time_in_force: b'0', // Day order
+                expire_time: 0,
+                text: String::new(),
             }
         }
 
-        /// Generate sequence number for FIX protocol compliance
-        fn generate_sequence_number() -> i32 {
-            use std::sync::atomic::{AtomicI32, Ordering};
-            static SEQUENCE_COUNTER: AtomicI32 = AtomicI32::new(1);
-            SEQUENCE_COUNTER.fetch_add(1, Ordering::SeqCst)
-        }
-
         /// Get the full type name exactly matching C++ namespace
         pub const fn type_name() -> &'static str {
             "DistributedATS_NewOrderSingle::NewOrderSingle"
         }
     }
 
-    /// Generate unique ClOrdID following financial industry standards
+    /// Generate unique ClOrdID following financial industry standards.
+    /// The 30 character IDs exceed the venue's ClOrdID limit; take IDs from the
+    /// client's configured `OrderIdGenerator` instead.
+    #[deprecated(note = "IDs exceed the venue ClOrdID limit, use OrderDdsClient::next_cl_ord_id")]
     pub fn generate_unique_cl_ord_id() -> String {
-        let timestamp = Utc::now().timestamp_micros();
-        let uuid_suffix = uuid::Uuid::new_v4().to_string()[..6].to_string();
-        format!("CLO_{}_{}", timestamp, uuid_suffix)
+        format!("CLO_{}", crate::utils::order_id_generator::next_ulid())
     }
 }
 
@@ -0,0 +1,366 @@
+// src/order_builder.rs
+use crate::new_order_single::{Header, NewOrderSingle};
+use crate::order_message::{OrderRequest, OrderSide, OrderType};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+
+/// FIX TimeInForce (tag 59)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeInForce {
+    #[default]
+    Day,
+    GoodTillCancel,
+    AtTheOpening,
+    ImmediateOrCancel,
+    FillOrKill,
+    GoodTillCrossing,
+    /// Good till date, with the ExpireTime (tag 126)
+    GoodTillDate(DateTime<Utc>),
+    AtTheClose,
+}
+
+impl TimeInForce {
+    /// FIX character for the TimeInForce field
+    pub fn as_fix(&self) -> u8 {
+        match self {
+            Self::Day => b'0',
+            Self::GoodTillCancel => b'1',
+            Self::AtTheOpening => b'2',
+            Self::ImmediateOrCancel => b'3',
+            Self::FillOrKill => b'4',
+            Self::GoodTillCrossing => b'5',
+            Self::GoodTillDate(_) => b'6',
+            Self::AtTheClose => b'7',
+        }
+    }
+
+    /// ExpireTime of a GTD order in epoch milliseconds, 0 for any other TimeInForce
+    pub fn expire_time(&self) -> u64 {
+        match self {
+            Self::GoodTillDate(expire_time) => expire_time.timestamp_millis() as u64,
+            _ => 0,
+        }
+    }
+}
+
+/// Message an `OrderBuilder` can produce
+pub trait OrderTarget: Sized {
+    fn from_builder(builder: OrderBuilder) -> Result<Self>;
+}
+
+/// Fluent builder for every order type and TimeInForce, validated at `build()`
+///
+/// ```ignore
+/// let order: NewOrderSingle =
+///     OrderBuilder::stop_limit("BTC-USD", OrderSide::SELL, 1000.0, 49_000.0, 49_500.0)
+///     .cl_ord_id(&client.next_cl_ord_id()?)
+///     .account("rust_trader_001")
+///     .time_in_force(TimeInForce::GoodTillCancel)
+///     .build()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct OrderBuilder {
+    cl_ord_id: Option<String>,
+    client_id: String,
+    account: Option<String>,
+    symbol: String,
+    side: OrderSide,
+    order_type: OrderType,
+    quantity: f64,
+    price: Option<f64>,
+    stop_px: Option<f64>,
+    time_in_force: TimeInForce,
+    exec_inst: Option<String>,
+    text: Option<String>,
+    security_exchange: String,
+}
+
+impl OrderBuilder {
+    fn new(symbol: &str, side: OrderSide, order_type: OrderType, quantity: f64) -> Self {
+        Self {
+            cl_ord_id: None,
+            client_id: "rust_client".to_string(),
+            account: None,
+            symbol: symbol.to_string(),
+            side,
+            order_type,
+            quantity,
+            price: None,
+            stop_px: None,
+            time_in_force: TimeInForce::Day,
+            exec_inst: None,
+            text: None,
+            security_exchange: "BTC_MARKET".to_string(),
+        }
+    }
+
+    pub fn market(symbol: &str, side: OrderSide, quantity: f64) -> Self {
+        Self::new(symbol, side, OrderType::MARKET, quantity)
+    }
+
+    pub fn limit(symbol: &str, side: OrderSide, quantity: f64, price: f64) -> Self {
+        Self::new(symbol, side, OrderType::LIMIT, quantity).price(price)
+    }
+
+    pub fn stop(symbol: &str, side: OrderSide, quantity: f64, stop_px: f64) -> Self {
+        Self::new(symbol, side, OrderType::STOP, quantity).stop_px(stop_px)
+    }
+
+    pub fn stop_limit(
+        symbol: &str,
+        side: OrderSide,
+        quantity: f64,
+        price: f64,
+        stop_px: f64,
+    ) -> Self {
+        Self::new(symbol, side, OrderType::STOP_LIMIT, quantity)
+            .price(price)
+            .stop_px(stop_px)
+    }
+
+    /// ClOrdID of a NewOrderSingle, order ID of an OrderRequest
+    pub fn cl_ord_id(mut self, cl_ord_id: &str) -> Self {
+        self.cl_ord_id = Some(cl_ord_id.to_string());
+        self
+    }
+
+    pub fn has_cl_ord_id(&self) -> bool {
+        self.cl_ord_id.is_some()
+    }
+
+    pub fn client_id(mut self, client_id: &str) -> Self {
+        self.client_id = client_id.to_string();
+        self
+    }
+
+    /// Trading account: `user_id` of an OrderRequest, `DATS_SourceUser` of a NewOrderSingle
+    pub fn account(mut self, account: &str) -> Self {
+        self.account = Some(account.to_string());
+        self
+    }
+
+    pub fn price(mut self, price: f64) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn stop_px(mut self, stop_px: f64) -> Self {
+        self.stop_px = Some(stop_px);
+        self
+    }
+
+    pub fn time_in_force(mut self, time_in_force: TimeInForce) -> Self {
+        self.time_in_force = time_in_force;
+        self
+    }
+
+    /// Shorthand for `TimeInForce::GoodTillDate`
+    pub fn good_till_date(self, expire_time: DateTime<Utc>) -> Self {
+        self.time_in_force(TimeInForce::GoodTillDate(expire_time))
+    }
+
+    /// Space separated FIX ExecInst values, e.g. "6" for participate don't initiate
+    pub fn exec_inst(mut self, exec_inst: &str) -> Self {
+        self.exec_inst = Some(exec_inst.to_string());
+        self
+    }
+
+    pub fn text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    pub fn security_exchange(mut self, security_exchange: &str) -> Self {
+        self.security_exchange = security_exchange.to_string();
+        self
+    }
+
+    /// Validate the combination of fields and produce the target message
+    pub fn build<T: OrderTarget>(self) -> Result<T> {
+        self.validate()?;
+        T::from_builder(self)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let Some(cl_ord_id) = self.cl_ord_id.as_deref().filter(|id| !id.is_empty()) else {
+            bail!("ClOrdID is required");
+        };
+        if self.symbol.is_empty() {
+            bail!("Symbol cannot be empty for order {}", cl_ord_id);
+        }
+        if self.account.as_deref().is_none_or(str::is_empty) {
+            bail!("Account is required for order {}", cl_ord_id);
+        }
+        if self.quantity <= 0.0 {
+            bail!("Quantity must be positive: {}", self.quantity);
+        }
+
+        let needs_price = matches!(self.order_type, OrderType::LIMIT | OrderType::STOP_LIMIT);
+        match self.price {
+            Some(price) if needs_price && price <= 0.0 => {
+                bail!("Limit price must be positive: {}", price)
+            }
+            Some(_) if !needs_price => bail!("{:?} orders cannot have a price", self.order_type),
+            None if needs_price => bail!("{:?} orders require a price", self.order_type),
+            _ => {}
+        }
+
+        let needs_stop = matches!(self.order_type, OrderType::STOP | OrderType::STOP_LIMIT);
+        match self.stop_px {
+            Some(stop_px) if needs_stop && stop_px <= 0.0 => {
+                bail!("Stop price must be positive: {}", stop_px)
+            }
+            Some(_) if !needs_stop => {
+                bail!("{:?} orders cannot have a stop price", self.order_type)
+            }
+            None if needs_stop => bail!("{:?} orders require a stop price", self.order_type),
+            _ => {}
+        }
+
+        match self.time_in_force {
+            TimeInForce::GoodTillDate(expire_time) if expire_time <= Utc::now() => {
+                bail!("GTD expire time {} is in the past", expire_time)
+            }
+            TimeInForce::AtTheOpening | TimeInForce::AtTheClose
+                if matches!(self.order_type, OrderType::STOP | OrderType::STOP_LIMIT) =>
+            {
+                bail!("Stop orders cannot be sent for the opening or closing auction")
+            }
+            _ => {}
+        }
+
+        if let Some(exec_inst) = &self.exec_inst {
+            if exec_inst
+                .split_whitespace()
+                .any(|value| value.len() != 1 || !value.chars().all(|c| c.is_ascii_alphanumeric()))
+            {
+                bail!("Invalid ExecInst '{}'", exec_inst);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl OrderTarget for OrderRequest {
+    /// OrderRequest has no TimeInForce, ExecInst or Text: only DAY orders without them fit
+    fn from_builder(builder: OrderBuilder) -> Result<Self> {
+        if builder.time_in_force != TimeInForce::Day {
+            bail!(
+                "OrderRequest cannot carry TimeInForce {:?}, send a NewOrderSingle instead",
+                builder.time_in_force
+            );
+        }
+        if builder.exec_inst.is_some() || builder.text.is_some() {
+            bail!("OrderRequest cannot carry ExecInst or Text, send a NewOrderSingle instead");
+        }
+
+        let mut order = OrderRequest::new(
+            builder.client_id,
+            builder.cl_ord_id.unwrap_or_default(),
+            builder.symbol,
+            builder.side,
+            builder.order_type,
+            builder.quantity,
+            builder.price.unwrap_or(0.0),
+            builder.account.unwrap_or_default(),
+        );
+        order.stop_price = builder.stop_px.unwrap_or(0.0);
+        Ok(order)
+    }
+}
+
+impl OrderTarget for NewOrderSingle {
+    fn from_builder(builder: OrderBuilder) -> Result<Self> {
+        if builder.quantity.fract() != 0.0 || builder.quantity > i32::MAX as f64 {
+            bail!(
+                "NewOrderSingle quantity must be a whole number: {}",
+                builder.quantity
+            );
+        }
+
+        let side = match builder.side {
+            OrderSide::BUY => b'1',
+            OrderSide::SELL => b'2',
+        };
+        let ord_type = match builder.order_type {
+            OrderType::MARKET => b'1',
+            OrderType::LIMIT => b'2',
+            OrderType::STOP => b'3',
+            OrderType::STOP_LIMIT => b'4',
+        };
+
+        Ok(NewOrderSingle {
+            dats_source: "RUST_CLIENT".to_string(),
+            dats_destination: "DATA_SERVICE_A".to_string(),
+            dats_source_user: builder.account.unwrap_or_default(),
+            dats_destination_user: "DATA_SERVICE_A".to_string(),
+            // MsgSeqNum is allocated per session by the client when the order is sent
+            fix_header: Header::default(),
+            cl_ord_id: builder.cl_ord_id.unwrap_or_default(),
+            exec_inst: builder.exec_inst.unwrap_or_default(),
+            symbol: builder.symbol,
+            security_exchange: builder.security_exchange,
+            side,
+            transact_time: Utc::now().timestamp_millis() as u64,
+            order_qty: builder.quantity as i32,
+            ord_type,
+            price: builder.price.unwrap_or(0.0) as f32,
+            stop_px: builder.stop_px.unwrap_or(0.0) as f32,
+            time_in_force: builder.time_in_force.as_fix(),
+            expire_time: builder.time_in_force.expire_time(),
+            text: builder.text.unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn good_till_date_new_order_single_carries_its_expire_time() {
+        let expire_time = Utc::now() + Duration::hours(4);
+        let order: NewOrderSingle =
+            OrderBuilder::stop_limit("BTC-USD", OrderSide::SELL, 2.0, 49_000.0, 49_500.0)
+                .cl_ord_id("CL-1")
+                .account("rust_trader_001")
+                .good_till_date(expire_time)
+                .build()
+                .unwrap();
+
+        assert_eq!(order.side, b'2');
+        assert_eq!(order.ord_type, b'4');
+        assert_eq!(order.time_in_force, b'6');
+        assert_eq!(order.expire_time, expire_time.timestamp_millis() as u64);
+        assert_eq!(order.price, 49_000.0);
+        assert_eq!(order.stop_px, 49_500.0);
+    }
+
+    #[test]
+    fn only_good_till_date_orders_carry_an_expire_time() {
+        let order: NewOrderSingle = OrderBuilder::limit("BTC-USD", OrderSide::BUY, 1.0, 100.0)
+            .cl_ord_id("CL-1")
+            .account("rust_trader_001")
+            .time_in_force(TimeInForce::GoodTillCancel)
+            .build()
+            .unwrap();
+        assert_eq!(order.time_in_force, b'1');
+        assert_eq!(order.expire_time, 0);
+
+        let expired = OrderBuilder::limit("BTC-USD", OrderSide::BUY, 1.0, 100.0)
+            .cl_ord_id("CL-2")
+            .account("rust_trader_001")
+            .good_till_date(Utc::now() - Duration::minutes(1))
+            .build::<NewOrderSingle>();
+        assert!(expired.is_err());
+
+        let gtd_request = OrderBuilder::limit("BTC-USD", OrderSide::BUY, 1.0, 100.0)
+            .cl_ord_id("CL-3")
+            .account("rust_trader_001")
+            .good_till_date(Utc::now() + Duration::hours(1))
+            .build::<OrderRequest>();
+        assert!(gtd_request.is_err());
+    }
+}
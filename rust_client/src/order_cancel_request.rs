@@ -0,0 +1,235 @@
+// src/order_cancel_request.rs - Cancel and cancel/replace requests to the matching engine
+use crate::new_order_single::{Header, NewOrderSingle};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// Module matching C++ DistributedATS_OrderCancelRequest namespace
+pub mod distributed_ats_order_cancel_request {
+    use super::*;
+
+    /// OrderCancelRequest (MsgType F) matching C++ DistributedATS_OrderCancelRequest::OrderCancelRequest
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct OrderCancelRequest {
+        #[serde(rename = "DATS_Source")]
+        pub dats_source: String,
+
+        #[serde(rename = "DATS_Destination")]
+        pub dats_destination: String,
+
+        #[serde(rename = "DATS_SourceUser")]
+        pub dats_source_user: String,
+
+        #[serde(rename = "DATS_DestinationUser")]
+        pub dats_destination_user: String,
+
+        #[serde(rename = "fix_header")]
+        pub fix_header: Header,
+
+        /// ClOrdID of the order to cancel
+        #[serde(rename = "OrigClOrdID")]
+        pub orig_cl_ord_id: String,
+
+        /// ClOrdID of the cancel request itself
+        #[serde(rename = "ClOrdID")]
+        pub cl_ord_id: String,
+
+        #[serde(rename = "Symbol")]
+        pub symbol: String,
+
+        #[serde(rename = "SecurityExchange")]
+        pub security_exchange: String,
+
+        #[serde(rename = "Side")]
+        pub side: u8, // C++ char maps to Rust u8
+
+        #[serde(rename = "TransactTime")]
+        pub transact_time: u64,
+
+        #[serde(rename = "OrderQty")]
+        pub order_qty: i32,
+
+        #[serde(rename = "Text")]
+        pub text: String,
+    }
+
+    impl OrderCancelRequest {
+        /// Cancel request for an order sent earlier, routed like the order itself
+        pub fn for_order(cl_ord_id: String, order: &NewOrderSingle) -> Self {
+            Self {
+                dats_source: order.dats_source.clone(),
+                dats_destination: order.dats_destination.clone(),
+                dats_source_user: order.dats_source_user.clone(),
+                dats_destination_user: order.dats_destination_user.clone(),
+                fix_header: Header {
+                    msg_type: "F".to_string(),
+                    ..Header::default()
+                },
+                orig_cl_ord_id: order.cl_ord_id.clone(),
+                cl_ord_id,
+                symbol: order.symbol.clone(),
+                security_exchange: order.security_exchange.clone(),
+                side: order.side,
+                transact_time: Utc::now().timestamp_millis() as u64,
+                order_qty: order.order_qty,
+                text: String::new(),
+            }
+        }
+
+        /// Get the full type name exactly matching C++ namespace
+        pub const fn type_name() -> &'static str {
+            "DistributedATS_OrderCancelRequest::OrderCancelRequest"
+        }
+    }
+}
+
+/// Module matching C++ DistributedATS_OrderCancelReplaceRequest namespace
+pub mod distributed_ats_order_cancel_replace_request {
+    use super::*;
+
+    /// OrderCancelReplaceRequest (MsgType G) matching
+    /// C++ DistributedATS_OrderCancelReplaceRequest::OrderCancelReplaceRequest
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct OrderCancelReplaceRequest {
+        #[serde(rename = "DATS_Source")]
+        pub dats_source: String,
+
+        #[serde(rename = "DATS_Destination")]
+        pub dats_destination: String,
+
+        #[serde(rename = "DATS_SourceUser")]
+        pub dats_source_user: String,
+
+        #[serde(rename = "DATS_DestinationUser")]
+        pub dats_destination_user: String,
+
+        #[serde(rename = "fix_header")]
+        pub fix_header: Header,
+
+        /// ClOrdID of the order to replace
+        #[serde(rename = "OrigClOrdID")]
+        pub orig_cl_ord_id: String,
+
+        /// ClOrdID of the replacement order
+        #[serde(rename = "ClOrdID")]
+        pub cl_ord_id: String,
+
+        #[serde(rename = "ExecInst")]
+        pub exec_inst: String,
+
+        #[serde(rename = "Symbol")]
+        pub symbol: String,
+
+        #[serde(rename = "SecurityExchange")]
+        pub security_exchange: String,
+
+        #[serde(rename = "Side")]
+        pub side: u8,
+
+        #[serde(rename = "TransactTime")]
+        pub transact_time: u64,
+
+        #[serde(rename = "OrderQty")]
+        pub order_qty: i32,
+
+        #[serde(rename = "OrdType")]
+        pub ord_type: u8,
+
+        #[serde(rename = "Price")]
+        pub price: f32,
+
+        #[serde(rename = "StopPx")]
+        pub stop_px: f32,
+
+        #[serde(rename = "TimeInForce")]
+        pub time_in_force: u8,
+
+        #[serde(rename = "Text")]
+        pub text: String,
+    }
+
+    impl OrderCancelReplaceRequest {
+        /// Replace request carrying the order's current terms, to be amended by the caller
+        pub fn for_order(cl_ord_id: String, order: &NewOrderSingle) -> Self {
+            Self {
+                dats_source: order.dats_source.clone(),
+                dats_destination: order.dats_destination.clone(),
+                dats_source_user: order.dats_source_user.clone(),
+                dats_destination_user: order.dats_destination_user.clone(),
+                fix_header: Header {
+                    msg_type: "G".to_string(),
+                    ..Header::default()
+                },
+                orig_cl_ord_id: order.cl_ord_id.clone(),
+                cl_ord_id,
+                exec_inst: order.exec_inst.clone(),
+                symbol: order.symbol.clone(),
+                security_exchange: order.security_exchange.clone(),
+                side: order.side,
+                transact_time: Utc::now().timestamp_millis() as u64,
+                order_qty: order.order_qty,
+                ord_type: order.ord_type,
+                price: order.price,
+                stop_px: order.stop_px,
+                time_in_force: order.time_in_force,
+                text: order.text.clone(),
+            }
+        }
+
+        /// Get the full type name exactly matching C++ namespace
+        pub const fn type_name() -> &'static str {
+            "DistributedATS_OrderCancelReplaceRequest::OrderCancelReplaceRequest"
+        }
+    }
+}
+
+// Re-export for easier usage
+pub use distributed_ats_order_cancel_replace_request::*;
+pub use distributed_ats_order_cancel_request::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order() -> NewOrderSingle {
+        let mut order = NewOrderSingle::limit_order(
+            "CL-1".to_string(),
+            "BTC-USD".to_string(),
+            "2",
+            10,
+            100.5,
+            "rust_trader_001".to_string(),
+        );
+        order.stop_px = 99.0;
+        order.text = "original".to_string();
+        order
+    }
+
+    #[test]
+    fn cancel_request_refers_to_the_order_and_keeps_its_routing() {
+        let request = OrderCancelRequest::for_order("CL-2".to_string(), &order());
+        assert_eq!(request.fix_header.msg_type, "F");
+        assert_eq!(request.orig_cl_ord_id, "CL-1");
+        assert_eq!(request.cl_ord_id, "CL-2");
+        assert_eq!(request.dats_source_user, "rust_trader_001");
+        assert_eq!(request.dats_destination, "DATA_SERVICE_A");
+        assert_eq!((request.side, request.order_qty), (b'2', 10));
+        assert!(request.text.is_empty());
+    }
+
+    #[test]
+    fn replace_request_carries_the_current_terms() {
+        let request = OrderCancelReplaceRequest::for_order("CL-2".to_string(), &order());
+        assert_eq!(request.fix_header.msg_type, "G");
+        assert_eq!(request.orig_cl_ord_id, "CL-1");
+        assert_eq!(request.cl_ord_id, "CL-2");
+        assert_eq!(
+            (request.side, request.ord_type, request.time_in_force),
+            (b'2', b'2', b'0')
+        );
+        assert_eq!(
+            (request.order_qty, request.price, request.stop_px),
+            (10, 100.5, 99.0)
+        );
+        assert_eq!(request.text, "original");
+    }
+}
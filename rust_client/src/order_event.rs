@@ -0,0 +1,130 @@
+// src/order_event.rs
+use crate::order_handle::OrderUpdate;
+use chrono::{DateTime, Utc};
+
+/// Where a report came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReportSource {
+    /// OrderResponseReport on `order_response`
+    Oms,
+    /// ExecutionReport on `EXECUTION_REPORT_TOPIC`
+    MatchingEngine,
+    /// Produced in-process, e.g. by the `LoopbackGateway`
+    Simulated,
+}
+
+impl std::fmt::Display for ReportSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Oms => "OMS",
+            Self::MatchingEngine => "MatchingEngine",
+            Self::Simulated => "Simulated",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Report behind an `OrderEvent`, with its source and position in the merged stream
+#[derive(Debug, Clone)]
+pub struct OrderEventData {
+    /// Strictly increasing across both sources, in order of receipt
+    pub seq: u64,
+    pub source: ReportSource,
+    pub received_at: DateTime<Utc>,
+    pub update: OrderUpdate,
+}
+
+/// Report from either the OMS or the matching engine, normalized by ExecType
+#[derive(Debug, Clone)]
+pub enum OrderEvent {
+    /// Order accepted (ExecType New)
+    Ack(OrderEventData),
+    /// Order rejected (ExecType Rejected)
+    Reject(OrderEventData),
+    /// Execution with LastQty/LastPx
+    Fill(OrderEventData),
+    /// Order cancelled or expired
+    Cancel(OrderEventData),
+    /// Cancel/replace applied
+    Replace(OrderEventData),
+    /// Anything else: pending states, status responses, restatements
+    Status(OrderEventData),
+}
+
+impl OrderEvent {
+    pub fn new(seq: u64, source: ReportSource, update: OrderUpdate) -> Self {
+        let exec_type = update.exec_type;
+        let is_fill = update.is_fill();
+        let data = OrderEventData {
+            seq,
+            source,
+            received_at: Utc::now(),
+            update,
+        };
+
+        match exec_type {
+            _ if is_fill => Self::Fill(data),
+            b'0' => Self::Ack(data),
+            b'8' => Self::Reject(data),
+            b'4' | b'C' => Self::Cancel(data),
+            b'5' => Self::Replace(data),
+            _ => Self::Status(data),
+        }
+    }
+
+    pub fn data(&self) -> &OrderEventData {
+        match self {
+            Self::Ack(data)
+            | Self::Reject(data)
+            | Self::Fill(data)
+            | Self::Cancel(data)
+            | Self::Replace(data)
+            | Self::Status(data) => data,
+        }
+    }
+
+    pub fn update(&self) -> &OrderUpdate {
+        &self.data().update
+    }
+
+    pub fn source(&self) -> ReportSource {
+        self.data().source
+    }
+
+    pub fn seq(&self) -> u64 {
+        self.data().seq
+    }
+
+    /// Client-assigned identifier of the order the event is about
+    pub fn order_key(&self) -> &str {
+        self.update().order_key()
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Ack(_) => "Ack",
+            Self::Reject(_) => "Reject",
+            Self::Fill(_) => "Fill",
+            Self::Cancel(_) => "Cancel",
+            Self::Replace(_) => "Replace",
+            Self::Status(_) => "Status",
+        }
+    }
+}
+
+impl std::fmt::Display for OrderEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let update = self.update();
+        write!(
+            f,
+            "#{} {} {} from {}: {} {}/{}",
+            self.seq(),
+            self.kind(),
+            self.order_key(),
+            self.source(),
+            update.get_execution_status(),
+            update.cum_qty,
+            update.order_qty
+        )
+    }
+}
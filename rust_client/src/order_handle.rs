@@ -0,0 +1,448 @@
+// src/order_handle.rs
+use crate::order_event::{OrderEvent, ReportSource};
+use crate::report::{ExecutionReport, OrderResponseReport};
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::{broadcast, watch};
+use tokio::time::timeout;
+
+/// Normalized view of a report received for a tracked order
+#[derive(Debug, Clone)]
+pub struct OrderUpdate {
+    pub order_id: String,
+    pub orig_cl_ord_id: String,
+    pub exec_id: String,
+    pub exec_type: u8,
+    pub ord_status: u8,
+    pub ord_rej_reason: i32,
+    /// Trading user the report is routed back to (DATS_DestinationUser)
+    pub user_id: String,
+    pub symbol: String,
+    pub side: u8,
+    pub order_qty: i32,
+    pub last_qty: i32,
+    pub last_px: f32,
+    pub leaves_qty: i32,
+    pub cum_qty: i32,
+    pub avg_px: f32,
+    pub transact_time: u64,
+    pub text: String,
+}
+
+impl OrderUpdate {
+    /// Client-assigned identifier when present, otherwise the venue OrderID
+    pub fn order_key(&self) -> &str {
+        if self.orig_cl_ord_id.is_empty() {
+            &self.order_id
+        } else {
+            &self.orig_cl_ord_id
+        }
+    }
+
+    /// Order was acknowledged by the OMS or matching engine; PendingNew is not
+    /// an acknowledgement yet
+    pub fn is_accepted(&self) -> bool {
+        matches!(self.ord_status, b'0' | b'1' | b'2' | b'5' | b'6' | b'E')
+    }
+
+    pub fn is_rejected(&self) -> bool {
+        self.ord_status == b'8'
+    }
+
+    /// Report carries an execution (LastQty/LastPx)
+    pub fn is_fill(&self) -> bool {
+        self.last_qty > 0 && matches!(self.exec_type, b'F' | b'1' | b'2')
+    }
+
+    /// LastQty signed by side: positive for buys, negative for sells
+    pub fn signed_last_qty(&self) -> Option<f64> {
+        match self.side {
+            b'1' => Some(self.last_qty as f64),
+            b'2' | b'5' | b'6' => Some(-(self.last_qty as f64)),
+            _ => None,
+        }
+    }
+
+    pub fn is_filled(&self) -> bool {
+        self.ord_status == b'2'
+    }
+
+    /// No further reports are expected once an order reaches a terminal status
+    pub fn is_terminal(&self) -> bool {
+        matches!(self.ord_status, b'2' | b'4' | b'8' | b'C')
+    }
+
+    /// Get execution status as human readable string following FIX protocol
+    pub fn get_execution_status(&self) -> &'static str {
+        match self.ord_status {
+            b'0' => "New",
+            b'1' => "Partially Filled",
+            b'2' => "Filled",
+            b'4' => "Cancelled",
+            b'5' => "Replaced",
+            b'6' => "Pending Cancel",
+            b'8' => "Rejected",
+            b'A' => "Pending New",
+            b'C' => "Expired",
+            b'E' => "Pending Replace",
+            _ => "Unknown",
+        }
+    }
+}
+
+impl From<&OrderResponseReport> for OrderUpdate {
+    fn from(report: &OrderResponseReport) -> Self {
+        Self {
+            order_id: report.order_id.clone(),
+            orig_cl_ord_id: report.orig_cl_ord_id.clone(),
+            exec_id: report.exec_id.clone(),
+            exec_type: report.exec_type,
+            ord_status: report.ord_status,
+            ord_rej_reason: report.ord_rej_reason,
+            user_id: report.dats_destination_user.clone(),
+            symbol: report.symbol.clone(),
+            side: report.side,
+            order_qty: report.order_qty,
+            last_qty: report.last_qty,
+            last_px: report.last_px,
+            leaves_qty: report.leaves_qty,
+            cum_qty: report.cum_qty,
+            avg_px: report.avg_px,
+            transact_time: report.transact_time,
+            text: report.text.clone(),
+        }
+    }
+}
+
+impl From<&ExecutionReport> for OrderUpdate {
+    fn from(report: &ExecutionReport) -> Self {
+        Self {
+            order_id: report.order_id.clone(),
+            orig_cl_ord_id: report.orig_cl_ord_id.clone(),
+            exec_id: report.exec_id.clone(),
+            exec_type: report.exec_type,
+            ord_status: report.ord_status,
+            ord_rej_reason: report.ord_rej_reason,
+            user_id: report.dats_destination_user.clone(),
+            symbol: report.symbol.clone(),
+            side: report.side,
+            order_qty: report.order_qty,
+            last_qty: report.last_qty,
+            last_px: report.last_px,
+            leaves_qty: report.leaves_qty,
+            cum_qty: report.cum_qty,
+            avg_px: report.avg_px,
+            transact_time: report.transact_time,
+            text: report.text.clone(),
+        }
+    }
+}
+
+/// Reports buffered for slow `OrderTracker::subscribe` receivers
+const EVENT_CAPACITY: usize = 1024;
+
+/// Correlates incoming reports with in-flight orders sent by this client.
+///
+/// Orders are keyed by the client-assigned ID (`order_id` for OrderRequest,
+/// `ClOrdID` for NewOrderSingle), which comes back as `OrigClOrdID` or `OrderID`.
+pub struct OrderTracker {
+    orders: Mutex<HashMap<String, watch::Sender<Vec<OrderUpdate>>>>,
+    events: broadcast::Sender<OrderEvent>,
+    next_event_seq: AtomicU64,
+}
+
+impl Default for OrderTracker {
+    fn default() -> Self {
+        Self {
+            orders: Mutex::new(HashMap::new()),
+            events: broadcast::channel(EVENT_CAPACITY).0,
+            next_event_seq: AtomicU64::new(1),
+        }
+    }
+}
+
+impl OrderTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every report fed into the tracker from now on, from both the OMS and the
+    /// matching engine and tracked order or not, in order of receipt
+    pub fn subscribe(&self) -> broadcast::Receiver<OrderEvent> {
+        self.events.subscribe()
+    }
+
+    /// Start tracking an order before it is written to DDS
+    pub fn track(&self, cl_ord_id: &str) -> OrderHandle {
+        let (sender, receiver) = watch::channel(Vec::new());
+        let mut orders = self.orders.lock().unwrap();
+
+        // Drop orders whose handles were discarded without waiting for completion
+        orders.retain(|_, sender| sender.receiver_count() > 0);
+        orders.insert(cl_ord_id.to_string(), sender);
+
+        OrderHandle {
+            cl_ord_id: cl_ord_id.to_string(),
+            updates: receiver,
+        }
+    }
+
+    /// Handle sharing the history of an order already tracked, e.g. for a cancel
+    /// request; starts tracking the order if its handles were all dropped
+    pub fn watch(&self, cl_ord_id: &str) -> OrderHandle {
+        if let Some(sender) = self.orders.lock().unwrap().get(cl_ord_id) {
+            return OrderHandle {
+                cl_ord_id: cl_ord_id.to_string(),
+                updates: sender.subscribe(),
+            };
+        }
+        self.track(cl_ord_id)
+    }
+
+    /// Stop tracking an order, e.g. after a failed DDS write
+    pub fn untrack(&self, cl_ord_id: &str) {
+        self.orders.lock().unwrap().remove(cl_ord_id);
+    }
+
+    /// Number of orders still awaiting a terminal report
+    pub fn in_flight(&self) -> usize {
+        self.orders.lock().unwrap().len()
+    }
+
+    /// Feed an OrderResponseReport from the OMS into the tracker
+    pub fn on_order_response(&self, report: &OrderResponseReport) {
+        self.on_update(ReportSource::Oms, OrderUpdate::from(report));
+    }
+
+    /// Feed an ExecutionReport from the matching engine into the tracker
+    pub fn on_execution_report(&self, report: &ExecutionReport) {
+        self.on_update(ReportSource::MatchingEngine, OrderUpdate::from(report));
+    }
+
+    /// Feed an already normalized report, e.g. from a gateway without DDS
+    pub fn on_update(&self, source: ReportSource, update: OrderUpdate) {
+        let mut orders = self.orders.lock().unwrap();
+
+        // Sequenced and published under the lock so the merged stream keeps receipt order;
+        // no subscribers is not an error
+        let seq = self.next_event_seq.fetch_add(1, Ordering::Relaxed);
+        let _ = self
+            .events
+            .send(OrderEvent::new(seq, source, update.clone()));
+
+        let key = if orders.contains_key(&update.orig_cl_ord_id) {
+            update.orig_cl_ord_id.clone()
+        } else if orders.contains_key(&update.order_id) {
+            update.order_id.clone()
+        } else {
+            return;
+        };
+
+        let terminal = update.is_terminal();
+        if let Some(sender) = orders.get(&key) {
+            sender.send_modify(|updates| updates.push(update));
+        }
+
+        // Terminal reports close the channel; handles keep the final history
+        if terminal {
+            orders.remove(&key);
+        }
+    }
+}
+
+/// Awaitable handle for an order sent through `OrderDdsClient`
+#[derive(Debug, Clone)]
+pub struct OrderHandle {
+    cl_ord_id: String,
+    updates: watch::Receiver<Vec<OrderUpdate>>,
+}
+
+impl OrderHandle {
+    /// Client-assigned order identifier
+    pub fn cl_ord_id(&self) -> &str {
+        &self.cl_ord_id
+    }
+
+    /// Most recent report received for this order, if any
+    pub fn latest(&self) -> Option<OrderUpdate> {
+        self.updates.borrow().last().cloned()
+    }
+
+    /// Resolve once the order is acknowledged; fails if it is rejected first
+    pub async fn accepted(&self, wait: Duration) -> Result<OrderUpdate> {
+        let update = self
+            .wait_until("acknowledgement", wait, |u| {
+                u.is_accepted() || u.is_terminal()
+            })
+            .await?;
+
+        if update.is_rejected() {
+            return Err(anyhow!(
+                "Order {} rejected: {} (RejReason: {})",
+                self.cl_ord_id,
+                update.text,
+                update.ord_rej_reason
+            ));
+        }
+
+        Ok(update)
+    }
+
+    /// Resolve once the order is completely filled; fails on any other terminal status
+    pub async fn filled(&self, wait: Duration) -> Result<OrderUpdate> {
+        let update = self.wait_until("fill", wait, |u| u.is_terminal()).await?;
+
+        if !update.is_filled() {
+            return Err(anyhow!(
+                "Order {} finished without fill: {} {}",
+                self.cl_ord_id,
+                update.get_execution_status(),
+                update.text
+            ));
+        }
+
+        Ok(update)
+    }
+
+    /// Resolve with the terminal report (Filled, Cancelled, Rejected or Expired)
+    pub async fn done(&self, wait: Duration) -> Result<OrderUpdate> {
+        self.wait_until("completion", wait, |u| u.is_terminal())
+            .await
+    }
+
+    /// Iterate over every report for this order, including ones already received
+    pub fn events(&self, idle_timeout: Duration) -> OrderEvents {
+        OrderEvents {
+            updates: self.updates.clone(),
+            next_index: 0,
+            idle_timeout,
+        }
+    }
+
+    async fn wait_until<F>(&self, what: &str, wait: Duration, predicate: F) -> Result<OrderUpdate>
+    where
+        F: Fn(&OrderUpdate) -> bool,
+    {
+        let mut updates = self.updates.clone();
+
+        let result = timeout(
+            wait,
+            updates.wait_for(|history| history.iter().any(&predicate)),
+        )
+        .await;
+
+        match result {
+            Ok(Ok(history)) => Ok(history.iter().find(|u| predicate(u)).cloned().unwrap()),
+            Ok(Err(_)) => Err(anyhow!(
+                "Order {} is no longer tracked while waiting for {}",
+                self.cl_ord_id,
+                what
+            )),
+            Err(_) => {
+                warn!(
+                    "⏰ Timed out after {:?} waiting for {} of order {}",
+                    wait, what, self.cl_ord_id
+                );
+                Err(anyhow!(
+                    "Timed out after {:?} waiting for {} of order {}",
+                    wait,
+                    what,
+                    self.cl_ord_id
+                ))
+            }
+        }
+    }
+}
+
+/// Sequential reader over the reports of a single order
+pub struct OrderEvents {
+    updates: watch::Receiver<Vec<OrderUpdate>>,
+    next_index: usize,
+    idle_timeout: Duration,
+}
+
+impl OrderEvents {
+    /// Next report for the order; `None` once the order is done or no report
+    /// arrives within the idle timeout
+    pub async fn next(&mut self) -> Option<OrderUpdate> {
+        let index = self.next_index;
+
+        let result = timeout(
+            self.idle_timeout,
+            self.updates.wait_for(|history| history.len() > index),
+        )
+        .await;
+
+        match result {
+            Ok(Ok(history)) => {
+                let update = history[index].clone();
+                self.next_index += 1;
+                Some(update)
+            }
+            Ok(Err(_)) => {
+                info!("✅ Order event stream finished after {} reports", index);
+                None
+            }
+            Err(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WAIT: Duration = Duration::from_millis(50);
+
+    fn update(cl_ord_id: &str, exec_type: u8, ord_status: u8) -> OrderUpdate {
+        OrderUpdate {
+            order_id: format!("ORD-{}", cl_ord_id),
+            orig_cl_ord_id: cl_ord_id.to_string(),
+            exec_id: format!("EXEC-{}", ord_status as char),
+            exec_type,
+            ord_status,
+            ord_rej_reason: 0,
+            user_id: "rust_trader_001".to_string(),
+            symbol: "BTC-USD".to_string(),
+            side: b'1',
+            order_qty: 10,
+            last_qty: 0,
+            last_px: 0.0,
+            leaves_qty: 10,
+            cum_qty: 0,
+            avg_px: 0.0,
+            transact_time: 0,
+            text: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn pending_new_is_not_an_acknowledgement() {
+        let tracker = OrderTracker::new();
+        let handle = tracker.track("CL-1");
+
+        tracker.on_update(ReportSource::Oms, update("CL-1", b'A', b'A'));
+        assert!(!handle.latest().unwrap().is_accepted());
+        assert!(handle.accepted(WAIT).await.is_err());
+
+        tracker.on_update(ReportSource::MatchingEngine, update("CL-1", b'0', b'0'));
+        assert_eq!(handle.accepted(WAIT).await.unwrap().ord_status, b'0');
+    }
+
+    #[tokio::test]
+    async fn rejection_fails_accepted_and_stops_tracking() {
+        let tracker = OrderTracker::new();
+        let handle = tracker.track("CL-1");
+        let mut events = tracker.subscribe();
+
+        tracker.on_update(ReportSource::Oms, update("CL-1", b'8', b'8'));
+        assert!(handle.accepted(WAIT).await.is_err());
+        assert_eq!(handle.done(WAIT).await.unwrap().ord_status, b'8');
+        assert_eq!(tracker.in_flight(), 0);
+        assert_eq!(events.recv().await.unwrap().order_key(), "CL-1");
+    }
+}
@@ -0,0 +1,79 @@
+// src/order_mass_cancel_request.rs - Mass cancel requests to the matching engine
+use crate::new_order_single::Header;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// Module matching C++ DistributedATS_OrderMassCancelRequest namespace
+pub mod distributed_ats_order_mass_cancel_request {
+    use super::*;
+
+    /// MassCancelRequestType (530) cancelling every order of the session
+    pub const MASS_CANCEL_ALL_ORDERS: u8 = b'7';
+
+    /// OrderMassCancelRequest (MsgType q) matching C++ DistributedATS_OrderMassCancelRequest::OrderMassCancelRequest
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct OrderMassCancelRequest {
+        #[serde(rename = "DATS_Source")]
+        pub dats_source: String,
+
+        #[serde(rename = "DATS_Destination")]
+        pub dats_destination: String,
+
+        #[serde(rename = "DATS_SourceUser")]
+        pub dats_source_user: String,
+
+        #[serde(rename = "DATS_DestinationUser")]
+        pub dats_destination_user: String,
+
+        #[serde(rename = "fix_header")]
+        pub fix_header: Header,
+
+        #[serde(rename = "ClOrdID")]
+        pub cl_ord_id: String,
+
+        #[serde(rename = "MassCancelRequestType")]
+        pub mass_cancel_request_type: u8, // C++ char maps to Rust u8
+
+        #[serde(rename = "Symbol")]
+        pub symbol: String,
+
+        #[serde(rename = "SecurityExchange")]
+        pub security_exchange: String,
+
+        #[serde(rename = "TransactTime")]
+        pub transact_time: u64,
+
+        #[serde(rename = "Text")]
+        pub text: String,
+    }
+
+    impl OrderMassCancelRequest {
+        /// Request cancelling every order, with routing and header left to the caller
+        pub fn all_orders(cl_ord_id: String) -> Self {
+            Self {
+                dats_source: String::new(),
+                dats_destination: String::new(),
+                dats_source_user: String::new(),
+                dats_destination_user: String::new(),
+                fix_header: Header {
+                    msg_type: "q".to_string(),
+                    ..Header::default()
+                },
+                cl_ord_id,
+                mass_cancel_request_type: MASS_CANCEL_ALL_ORDERS,
+                symbol: String::new(),
+                security_exchange: String::new(),
+                transact_time: Utc::now().timestamp_millis() as u64,
+                text: String::new(),
+            }
+        }
+
+        /// Get the full type name exactly matching C++ namespace
+        pub const fn type_name() -> &'static str {
+            "DistributedATS_OrderMassCancelRequest::OrderMassCancelRequest"
+        }
+    }
+}
+
+// Re-export for easier usage
+pub use distributed_ats_order_mass_cancel_request::*;
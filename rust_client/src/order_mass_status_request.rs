@@ -0,0 +1,70 @@
+// src/order_mass_status_request.rs - Status requests to the matching engine
+use crate::new_order_single::Header;
+use serde::{Deserialize, Serialize};
+
+/// Module matching C++ DistributedATS_OrderMassStatusRequest namespace
+pub mod distributed_ats_order_mass_status_request {
+    use super::*;
+
+    /// MassStatusReqType (585) asking for the status of every order
+    pub const MASS_STATUS_ALL_ORDERS: i32 = 7;
+
+    /// OrderMassStatusRequest (MsgType AF) matching C++ DistributedATS_OrderMassStatusRequest::OrderMassStatusRequest
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct OrderMassStatusRequest {
+        #[serde(rename = "DATS_Source")]
+        pub dats_source: String,
+
+        #[serde(rename = "DATS_Destination")]
+        pub dats_destination: String,
+
+        #[serde(rename = "DATS_SourceUser")]
+        pub dats_source_user: String,
+
+        #[serde(rename = "DATS_DestinationUser")]
+        pub dats_destination_user: String,
+
+        #[serde(rename = "fix_header")]
+        pub fix_header: Header,
+
+        #[serde(rename = "MassStatusReqID")]
+        pub mass_status_req_id: String,
+
+        #[serde(rename = "MassStatusReqType")]
+        pub mass_status_req_type: i32,
+
+        #[serde(rename = "Symbol")]
+        pub symbol: String,
+
+        #[serde(rename = "SecurityExchange")]
+        pub security_exchange: String,
+    }
+
+    impl OrderMassStatusRequest {
+        /// Request for the status of every order, with routing and header left to the caller
+        pub fn all_orders(mass_status_req_id: String) -> Self {
+            Self {
+                dats_source: String::new(),
+                dats_destination: String::new(),
+                dats_source_user: String::new(),
+                dats_destination_user: String::new(),
+                fix_header: Header {
+                    msg_type: "AF".to_string(),
+                    ..Header::default()
+                },
+                mass_status_req_id,
+                mass_status_req_type: MASS_STATUS_ALL_ORDERS,
+                symbol: String::new(),
+                security_exchange: String::new(),
+            }
+        }
+
+        /// Get the full type name exactly matching C++ namespace
+        pub const fn type_name() -> &'static str {
+            "DistributedATS_OrderMassStatusRequest::OrderMassStatusRequest"
+        }
+    }
+}
+
+// Re-export for easier usage
+pub use distributed_ats_order_mass_status_request::*;
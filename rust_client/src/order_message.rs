@@ -1,5 +1,5 @@
 // src/order_message.rs
-use chrono::{DateTime, Utc};
+use chrono::Utc;
 use rustdds::*;
 use serde::{Deserialize, Serialize};
 
@@ -14,6 +14,7 @@ pub enum OrderSide {
 /// Order type enumeration matching OMS OrderType  
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 #[repr(u32)]
+#[allow(non_camel_case_types)]
 pub enum OrderType {
     MARKET = 1,
     LIMIT = 2,
@@ -52,6 +53,7 @@ impl OrderRequest {
 
 impl OrderRequest {
     /// Create a new order request with current timestamp
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         client_id: String,
         order_id: String,
@@ -120,9 +122,10 @@ impl OrderRequest {
     }
 }
 
-/// Generate unique order ID following financial industry standards
+/// Generate unique order ID following financial industry standards.
+/// The 30 character IDs exceed the venue's ClOrdID limit; take IDs from the
+/// client's configured `OrderIdGenerator` instead.
+#[deprecated(note = "IDs exceed the venue ClOrdID limit, use OrderDdsClient::next_cl_ord_id")]
 pub fn generate_unique_order_id() -> String {
-    let timestamp = Utc::now().timestamp_micros();
-    let uuid_suffix = uuid::Uuid::new_v4().to_string()[..8].to_string();
-    format!("ORD_{}_{}", timestamp, uuid_suffix)
+    format!("ORD_{}", crate::utils::order_id_generator::next_ulid())
 }
@@ -0,0 +1,418 @@
+// src/order_state.rs
+use crate::order_handle::OrderUpdate;
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+/// FIX order lifecycle states (OrdStatus tag 39)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OrderState {
+    PendingNew,
+    New,
+    PartiallyFilled,
+    Filled,
+    PendingCancel,
+    PendingReplace,
+    Cancelled,
+    Replaced,
+    Rejected,
+    Expired,
+}
+
+impl OrderState {
+    /// Map a FIX OrdStatus character to a lifecycle state
+    pub fn from_ord_status(ord_status: u8) -> Option<Self> {
+        match ord_status {
+            b'A' => Some(Self::PendingNew),
+            b'0' => Some(Self::New),
+            b'1' => Some(Self::PartiallyFilled),
+            b'2' => Some(Self::Filled),
+            b'6' => Some(Self::PendingCancel),
+            b'E' => Some(Self::PendingReplace),
+            b'4' => Some(Self::Cancelled),
+            b'5' => Some(Self::Replaced),
+            b'8' => Some(Self::Rejected),
+            b'C' => Some(Self::Expired),
+            _ => None,
+        }
+    }
+
+    /// Terminal states are sticky: no later report can move the order out of them
+    pub fn is_terminal(self) -> bool {
+        matches!(
+            self,
+            Self::Filled | Self::Cancelled | Self::Rejected | Self::Expired
+        )
+    }
+
+    /// Order is still working at the venue
+    pub fn is_open(self) -> bool {
+        !self.is_terminal()
+    }
+
+    /// Check whether moving from `self` to `next` is a legal FIX transition
+    pub fn can_transition_to(self, next: OrderState) -> bool {
+        use OrderState::*;
+
+        match self {
+            PendingNew => matches!(
+                next,
+                PendingNew
+                    | New
+                    | PartiallyFilled
+                    | Filled
+                    | PendingCancel
+                    | Cancelled
+                    | Rejected
+                    | Expired
+            ),
+            New => matches!(
+                next,
+                New | PartiallyFilled
+                    | Filled
+                    | PendingCancel
+                    | PendingReplace
+                    | Cancelled
+                    | Replaced
+                    | Rejected
+                    | Expired
+            ),
+            PartiallyFilled => matches!(
+                next,
+                PartiallyFilled
+                    | Filled
+                    | PendingCancel
+                    | PendingReplace
+                    | Cancelled
+                    | Replaced
+                    | Expired
+            ),
+            // A cancel reject returns the order to its working state
+            PendingCancel => matches!(
+                next,
+                PendingCancel | New | PartiallyFilled | Filled | Cancelled | Replaced | Expired
+            ),
+            // A replace reject likewise returns the order to its working state
+            PendingReplace => matches!(
+                next,
+                PendingReplace
+                    | New
+                    | PartiallyFilled
+                    | Filled
+                    | PendingCancel
+                    | Cancelled
+                    | Replaced
+                    | Expired
+            ),
+            Replaced => matches!(
+                next,
+                Replaced
+                    | New
+                    | PartiallyFilled
+                    | Filled
+                    | PendingCancel
+                    | PendingReplace
+                    | Cancelled
+                    | Expired
+            ),
+            Filled | Cancelled | Rejected | Expired => false,
+        }
+    }
+}
+
+impl std::fmt::Display for OrderState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::PendingNew => "Pending New",
+            Self::New => "New",
+            Self::PartiallyFilled => "Partially Filled",
+            Self::Filled => "Filled",
+            Self::PendingCancel => "Pending Cancel",
+            Self::PendingReplace => "Pending Replace",
+            Self::Cancelled => "Cancelled",
+            Self::Replaced => "Replaced",
+            Self::Rejected => "Rejected",
+            Self::Expired => "Expired",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// How a lifecycle handles reports that imply an illegal transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransitionPolicy {
+    /// Discard the report and keep the current state
+    #[default]
+    Reject,
+    /// Apply the report but mark it as illegal in the history.
+    /// Terminal states stay sticky regardless of this policy.
+    Flag,
+}
+
+/// Error returned when a report cannot be applied to an order lifecycle
+#[derive(Debug, Clone, PartialEq)]
+pub struct IllegalTransition {
+    pub from: Option<OrderState>,
+    pub ord_status: u8,
+    pub exec_id: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for IllegalTransition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.from {
+            Some(from) => write!(
+                f,
+                "Illegal transition from {} on OrdStatus '{}' (ExecID={}): {}",
+                from, self.ord_status as char, self.exec_id, self.reason
+            ),
+            None => write!(
+                f,
+                "Illegal initial OrdStatus '{}' (ExecID={}): {}",
+                self.ord_status as char, self.exec_id, self.reason
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IllegalTransition {}
+
+/// Report applied to an order lifecycle, kept for the audit trail
+#[derive(Debug, Clone)]
+pub struct LifecycleEntry {
+    pub update: OrderUpdate,
+    pub state: OrderState,
+    /// False when the report was applied despite an illegal transition
+    pub legal: bool,
+}
+
+/// Lifecycle of a single order driven by execution reports
+#[derive(Debug, Clone)]
+pub struct OrderLifecycle {
+    state: Option<OrderState>,
+    cum_qty: i32,
+    leaves_qty: i32,
+    policy: TransitionPolicy,
+    history: Vec<LifecycleEntry>,
+}
+
+impl OrderLifecycle {
+    pub fn new(policy: TransitionPolicy) -> Self {
+        Self {
+            state: None,
+            cum_qty: 0,
+            leaves_qty: 0,
+            policy,
+            history: Vec::new(),
+        }
+    }
+
+    /// Current state, `None` until the first report is applied
+    pub fn state(&self) -> Option<OrderState> {
+        self.state
+    }
+
+    pub fn cum_qty(&self) -> i32 {
+        self.cum_qty
+    }
+
+    pub fn leaves_qty(&self) -> i32 {
+        self.leaves_qty
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        self.state.is_some_and(OrderState::is_terminal)
+    }
+
+    /// Reports applied so far, oldest first
+    pub fn history(&self) -> &[LifecycleEntry] {
+        &self.history
+    }
+
+    /// Apply a report, returning the resulting state or why it was not applied
+    pub fn apply(&mut self, update: &OrderUpdate) -> Result<OrderState, IllegalTransition> {
+        let illegal = |reason: String| IllegalTransition {
+            from: self.state,
+            ord_status: update.ord_status,
+            exec_id: update.exec_id.clone(),
+            reason,
+        };
+
+        let next = OrderState::from_ord_status(update.ord_status)
+            .ok_or_else(|| illegal("unknown OrdStatus".to_string()))?;
+
+        if let Some(current) = self.state {
+            if current.is_terminal() {
+                return Err(illegal(format!("order already {}", current)));
+            }
+        }
+
+        let mut violation = None;
+        if let Some(current) = self.state {
+            if !current.can_transition_to(next) {
+                violation = Some(format!("{} -> {} is not a valid transition", current, next));
+            }
+        }
+        if update.cum_qty < self.cum_qty {
+            violation = Some(format!(
+                "CumQty went backwards ({} -> {})",
+                self.cum_qty, update.cum_qty
+            ));
+        }
+
+        let legal = violation.is_none();
+        if let Some(reason) = violation {
+            let error = illegal(reason);
+            if self.policy == TransitionPolicy::Reject {
+                return Err(error);
+            }
+            warn!("⚠️ Applying flagged report: {}", error);
+        }
+
+        self.history.push(LifecycleEntry {
+            update: update.clone(),
+            state: next,
+            legal,
+        });
+        self.state = Some(next);
+        self.cum_qty = self.cum_qty.max(update.cum_qty);
+        self.leaves_qty = if next.is_terminal() {
+            0
+        } else {
+            update.leaves_qty
+        };
+
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_STATES: [OrderState; 10] = [
+        OrderState::PendingNew,
+        OrderState::New,
+        OrderState::PartiallyFilled,
+        OrderState::Filled,
+        OrderState::PendingCancel,
+        OrderState::PendingReplace,
+        OrderState::Cancelled,
+        OrderState::Replaced,
+        OrderState::Rejected,
+        OrderState::Expired,
+    ];
+
+    fn update(ord_status: u8, cum_qty: i32, leaves_qty: i32) -> OrderUpdate {
+        OrderUpdate {
+            order_id: "ORD-1".to_string(),
+            orig_cl_ord_id: "CL-1".to_string(),
+            exec_id: format!("EXEC-{}-{}", ord_status as char, cum_qty),
+            exec_type: ord_status,
+            ord_status,
+            ord_rej_reason: 0,
+            user_id: "user001".to_string(),
+            symbol: "BTC-USD".to_string(),
+            side: b'1',
+            order_qty: 10,
+            last_qty: 0,
+            last_px: 0.0,
+            leaves_qty,
+            cum_qty,
+            avg_px: 0.0,
+            transact_time: 0,
+            text: String::new(),
+        }
+    }
+
+    #[test]
+    fn maps_every_ord_status() {
+        for (ord_status, state) in [
+            (b'A', OrderState::PendingNew),
+            (b'0', OrderState::New),
+            (b'1', OrderState::PartiallyFilled),
+            (b'2', OrderState::Filled),
+            (b'6', OrderState::PendingCancel),
+            (b'E', OrderState::PendingReplace),
+            (b'4', OrderState::Cancelled),
+            (b'5', OrderState::Replaced),
+            (b'8', OrderState::Rejected),
+            (b'C', OrderState::Expired),
+        ] {
+            assert_eq!(OrderState::from_ord_status(ord_status), Some(state));
+        }
+        assert_eq!(OrderState::from_ord_status(b'Z'), None);
+    }
+
+    #[test]
+    fn transition_table() {
+        use OrderState::*;
+
+        assert!(PendingNew.can_transition_to(New));
+        assert!(PendingNew.can_transition_to(Rejected));
+        assert!(!PendingNew.can_transition_to(Replaced));
+        assert!(New.can_transition_to(PendingReplace));
+        assert!(!New.can_transition_to(PendingNew));
+        assert!(PartiallyFilled.can_transition_to(PendingReplace));
+        assert!(!PartiallyFilled.can_transition_to(New));
+        assert!(!PartiallyFilled.can_transition_to(Rejected));
+        assert!(PendingCancel.can_transition_to(PartiallyFilled));
+        assert!(PendingReplace.can_transition_to(Replaced));
+        assert!(PendingReplace.can_transition_to(New));
+        assert!(!PendingReplace.can_transition_to(Rejected));
+        assert!(Replaced.can_transition_to(PendingReplace));
+    }
+
+    #[test]
+    fn terminal_states_are_sticky() {
+        for terminal in ALL_STATES.into_iter().filter(|s| s.is_terminal()) {
+            for next in ALL_STATES {
+                assert!(
+                    !terminal.can_transition_to(next),
+                    "{} -> {}",
+                    terminal,
+                    next
+                );
+            }
+        }
+
+        for policy in [TransitionPolicy::Reject, TransitionPolicy::Flag] {
+            let mut lifecycle = OrderLifecycle::new(policy);
+            lifecycle.apply(&update(b'0', 0, 10)).unwrap();
+            lifecycle.apply(&update(b'2', 10, 0)).unwrap();
+            assert!(lifecycle.apply(&update(b'1', 10, 0)).is_err());
+            assert_eq!(lifecycle.state(), Some(OrderState::Filled));
+        }
+    }
+
+    #[test]
+    fn illegal_transitions_follow_policy() {
+        let mut rejecting = OrderLifecycle::new(TransitionPolicy::Reject);
+        rejecting.apply(&update(b'1', 5, 5)).unwrap();
+        assert!(rejecting.apply(&update(b'0', 5, 5)).is_err());
+        assert!(rejecting.apply(&update(b'1', 3, 7)).is_err());
+        assert_eq!(rejecting.state(), Some(OrderState::PartiallyFilled));
+        assert_eq!(rejecting.history().len(), 1);
+
+        let mut flagging = OrderLifecycle::new(TransitionPolicy::Flag);
+        flagging.apply(&update(b'1', 5, 5)).unwrap();
+        assert_eq!(flagging.apply(&update(b'0', 5, 5)), Ok(OrderState::New));
+        assert!(!flagging.history()[1].legal);
+        assert_eq!(flagging.cum_qty(), 5);
+    }
+
+    #[test]
+    fn replace_cycle() {
+        let mut lifecycle = OrderLifecycle::new(TransitionPolicy::Reject);
+        for (ord_status, state) in [
+            (b'A', OrderState::PendingNew),
+            (b'0', OrderState::New),
+            (b'E', OrderState::PendingReplace),
+            (b'5', OrderState::Replaced),
+            (b'E', OrderState::PendingReplace),
+            (b'0', OrderState::New),
+        ] {
+            assert_eq!(lifecycle.apply(&update(ord_status, 0, 10)), Ok(state));
+        }
+        assert_eq!(lifecycle.leaves_qty(), 10);
+    }
+}
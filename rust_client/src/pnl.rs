@@ -0,0 +1,496 @@
+// src/pnl.rs
+use crate::order_handle::OrderUpdate;
+use crate::positions::{split_currency_pair, Position};
+use crate::report::{ExecutionReport, OrderResponseReport};
+use log::{info, warn};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// How realized PnL matches closing fills against open quantity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CostMethod {
+    /// Close the oldest open lots first
+    #[default]
+    Fifo,
+    /// Close against the running average cost of the position
+    AverageCost,
+}
+
+/// Which price unrealized PnL is marked to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarkSource {
+    /// Price set manually with `PnlEngine::set_manual_mark`
+    #[default]
+    Manual,
+}
+
+/// PnL of one user in one symbol
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SymbolPnl {
+    pub user_id: String,
+    pub symbol: String,
+    pub net_qty: f64,
+    /// Currency the raw PnL is denominated in (the quote currency for FX pairs)
+    pub currency: String,
+    pub realized: f64,
+    /// `None` while no mark price is available
+    pub unrealized: Option<f64>,
+    pub mark: Option<f64>,
+    /// PnL converted to the account currency, `None` without a conversion rate
+    pub realized_account: Option<f64>,
+    pub unrealized_account: Option<f64>,
+}
+
+/// PnL of one user in the account currency
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserPnl {
+    pub user_id: String,
+    pub currency: String,
+    pub realized: f64,
+    pub unrealized: f64,
+    /// Symbols left out of the totals because a mark or conversion rate is missing
+    pub unpriced_symbols: Vec<String>,
+}
+
+impl UserPnl {
+    pub fn total(&self) -> f64 {
+        self.realized + self.unrealized
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Lot {
+    qty: f64,
+    price: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct SymbolBook {
+    // FIFO open lots, all with the same sign
+    lots: VecDeque<Lot>,
+    // Average-cost position
+    net_qty: f64,
+    avg_cost: f64,
+    realized: f64,
+}
+
+impl SymbolBook {
+    fn apply_fifo(&mut self, mut qty: f64, price: f64) {
+        while qty != 0.0 {
+            let Some(front) = self.lots.front_mut() else {
+                break;
+            };
+            if front.qty.signum() == qty.signum() {
+                break;
+            }
+
+            let matched = qty.abs().min(front.qty.abs());
+            self.realized += matched * (price - front.price) * front.qty.signum();
+            front.qty += matched * qty.signum();
+            qty -= matched * qty.signum();
+
+            if front.qty == 0.0 {
+                self.lots.pop_front();
+            }
+        }
+
+        if qty != 0.0 {
+            self.lots.push_back(Lot { qty, price });
+        }
+        self.net_qty = self.lots.iter().map(|lot| lot.qty).sum();
+    }
+
+    fn apply_average(&mut self, qty: f64, price: f64) {
+        let new_qty = self.net_qty + qty;
+
+        if self.net_qty == 0.0 || self.net_qty.signum() == qty.signum() {
+            self.avg_cost =
+                (self.net_qty.abs() * self.avg_cost + qty.abs() * price) / new_qty.abs();
+        } else {
+            let matched = qty.abs().min(self.net_qty.abs());
+            self.realized += matched * (price - self.avg_cost) * self.net_qty.signum();
+
+            if new_qty == 0.0 {
+                self.avg_cost = 0.0;
+            } else if new_qty.signum() != self.net_qty.signum() {
+                self.avg_cost = price;
+            }
+        }
+
+        self.net_qty = new_qty;
+    }
+
+    fn unrealized(&self, method: CostMethod, mark: f64) -> f64 {
+        match method {
+            CostMethod::Fifo => self
+                .lots
+                .iter()
+                .map(|lot| lot.qty * (mark - lot.price))
+                .sum(),
+            CostMethod::AverageCost => self.net_qty * (mark - self.avg_cost),
+        }
+    }
+}
+
+#[derive(Default)]
+struct PnlState {
+    books: HashMap<(String, String), SymbolBook>,
+    applied_exec_ids: HashSet<String>,
+    // Manual marks keyed by symbol
+    manual_marks: HashMap<String, f64>,
+    // Explicit currency -> account currency rates
+    conversion_rates: HashMap<String, f64>,
+}
+
+/// Realized and unrealized PnL per user and symbol from fills and mark prices
+pub struct PnlEngine {
+    method: CostMethod,
+    mark_source: MarkSource,
+    account_currency: String,
+    state: RwLock<PnlState>,
+}
+
+impl PnlEngine {
+    pub fn new(method: CostMethod, mark_source: MarkSource, account_currency: &str) -> Self {
+        Self {
+            method,
+            mark_source,
+            account_currency: account_currency.to_uppercase(),
+            state: RwLock::new(PnlState::default()),
+        }
+    }
+
+    pub fn account_currency(&self) -> &str {
+        &self.account_currency
+    }
+
+    /// Apply a fill from an OrderResponseReport
+    pub fn on_order_response(&self, report: &OrderResponseReport) {
+        self.on_fill(&OrderUpdate::from(report));
+    }
+
+    /// Apply a fill from an ExecutionReport
+    pub fn on_execution_report(&self, report: &ExecutionReport) {
+        self.on_fill(&OrderUpdate::from(report));
+    }
+
+    /// Open a position at its average cost, e.g. from the start-of-day snapshot
+    /// the position keeper was seeded with; replaces any book for the user and symbol
+    pub fn seed(&self, position: &Position) {
+        let mut book = SymbolBook {
+            net_qty: position.net_qty,
+            avg_cost: position.avg_cost,
+            ..SymbolBook::default()
+        };
+        if position.net_qty != 0.0 {
+            book.lots.push_back(Lot {
+                qty: position.net_qty,
+                price: position.avg_cost,
+            });
+        } else {
+            book.avg_cost = 0.0;
+        }
+
+        let mut state = self.state.write().unwrap();
+        state
+            .books
+            .insert((position.user_id.clone(), position.symbol.clone()), book);
+    }
+
+    /// Apply a fill; non-fill reports and already applied ExecIDs are ignored
+    pub fn on_fill(&self, update: &OrderUpdate) -> bool {
+        if !update.is_fill() {
+            return false;
+        }
+        let Some(qty) = update.signed_last_qty() else {
+            warn!("⚠️ PnL ignoring fill {} with unknown side", update.exec_id);
+            return false;
+        };
+
+        let mut state = self.state.write().unwrap();
+        if !update.exec_id.is_empty() && !state.applied_exec_ids.insert(update.exec_id.clone()) {
+            return false;
+        }
+
+        let book = state
+            .books
+            .entry((update.user_id.clone(), update.symbol.clone()))
+            .or_default();
+        match self.method {
+            CostMethod::Fifo => book.apply_fifo(qty, update.last_px as f64),
+            CostMethod::AverageCost => book.apply_average(qty, update.last_px as f64),
+        }
+        true
+    }
+
+    /// Set a manual mark price for a symbol
+    pub fn set_manual_mark(&self, symbol: &str, price: f64) {
+        let mut state = self.state.write().unwrap();
+        state.manual_marks.insert(symbol.to_string(), price);
+    }
+
+    /// Set the rate converting one unit of `currency` into the account currency
+    pub fn set_conversion_rate(&self, currency: &str, rate: f64) {
+        let mut state = self.state.write().unwrap();
+        state.conversion_rates.insert(currency.to_uppercase(), rate);
+    }
+
+    fn mark(&self, state: &PnlState, symbol: &str) -> Option<f64> {
+        match self.mark_source {
+            MarkSource::Manual => state.manual_marks.get(symbol).copied(),
+        }
+    }
+
+    /// Rate converting `currency` into the account currency, from explicit rates
+    /// or from the mark of a pair between the two currencies
+    fn conversion_rate(&self, state: &PnlState, currency: &str) -> Option<f64> {
+        if currency == self.account_currency {
+            return Some(1.0);
+        }
+        if let Some(rate) = state.conversion_rates.get(currency) {
+            return Some(*rate);
+        }
+
+        for symbol in state.manual_marks.keys() {
+            let Some((base, quote)) = split_currency_pair(symbol) else {
+                continue;
+            };
+            let Some(price) = self.mark(state, symbol).filter(|p| *p > 0.0) else {
+                continue;
+            };
+            if base == currency && quote == self.account_currency {
+                return Some(price);
+            }
+            if base == self.account_currency && quote == currency {
+                return Some(1.0 / price);
+            }
+        }
+        None
+    }
+
+    fn symbol_pnl(
+        &self,
+        state: &PnlState,
+        user_id: &str,
+        symbol: &str,
+        book: &SymbolBook,
+    ) -> SymbolPnl {
+        // Non-FX symbols are assumed to be priced in the account currency
+        let currency = split_currency_pair(symbol)
+            .map(|(_, quote)| quote)
+            .unwrap_or_else(|| self.account_currency.clone());
+
+        let mark = self.mark(state, symbol);
+        let unrealized = mark.map(|mark| book.unrealized(self.method, mark));
+        let rate = self.conversion_rate(state, &currency);
+
+        SymbolPnl {
+            user_id: user_id.to_string(),
+            symbol: symbol.to_string(),
+            net_qty: book.net_qty,
+            currency,
+            realized: book.realized,
+            unrealized,
+            mark,
+            realized_account: rate.map(|rate| book.realized * rate),
+            unrealized_account: rate.zip(unrealized).map(|(rate, u)| u * rate),
+        }
+    }
+
+    /// PnL of a user in one symbol
+    pub fn pnl(&self, user_id: &str, symbol: &str) -> Option<SymbolPnl> {
+        let state = self.state.read().unwrap();
+        let book = state
+            .books
+            .get(&(user_id.to_string(), symbol.to_string()))?;
+        Some(self.symbol_pnl(&state, user_id, symbol, book))
+    }
+
+    /// PnL of every user and symbol
+    pub fn all_pnl(&self) -> Vec<SymbolPnl> {
+        let state = self.state.read().unwrap();
+        state
+            .books
+            .iter()
+            .map(|((user_id, symbol), book)| self.symbol_pnl(&state, user_id, symbol, book))
+            .collect()
+    }
+
+    /// PnL of a user summed over symbols in the account currency
+    pub fn user_pnl(&self, user_id: &str) -> UserPnl {
+        let mut total = UserPnl {
+            user_id: user_id.to_string(),
+            currency: self.account_currency.clone(),
+            ..UserPnl::default()
+        };
+
+        for pnl in self.all_pnl().into_iter().filter(|p| p.user_id == user_id) {
+            match (pnl.realized_account, pnl.unrealized_account) {
+                (Some(realized), Some(unrealized)) => {
+                    total.realized += realized;
+                    total.unrealized += unrealized;
+                }
+                // A flat position has nothing to mark
+                (Some(realized), None) if pnl.net_qty == 0.0 => total.realized += realized,
+                _ => total.unpriced_symbols.push(pnl.symbol),
+            }
+        }
+        total
+    }
+
+    /// Users with at least one fill
+    pub fn users(&self) -> Vec<String> {
+        let state = self.state.read().unwrap();
+        let users: HashSet<&String> = state.books.keys().map(|(user_id, _)| user_id).collect();
+        users.into_iter().cloned().collect()
+    }
+
+    /// Log the PnL of every symbol and user for the audit trail
+    pub fn log_snapshot(&self) {
+        for pnl in self.all_pnl() {
+            info!(
+                "💰 PNL_SNAPSHOT: User={}, Symbol={}, NetQty={}, Mark={:?}, Realized={} {}, Unrealized={:?} {}, Realized{}={:?}, Unrealized{}={:?}",
+                pnl.user_id,
+                pnl.symbol,
+                pnl.net_qty,
+                pnl.mark,
+                pnl.realized,
+                pnl.currency,
+                pnl.unrealized,
+                pnl.currency,
+                self.account_currency,
+                pnl.realized_account,
+                self.account_currency,
+                pnl.unrealized_account
+            );
+        }
+
+        for user_id in self.users() {
+            let total = self.user_pnl(&user_id);
+            info!(
+                "💰 PNL_SNAPSHOT: User={}, Realized={} {}, Unrealized={} {}, Total={} {}, Unpriced={:?}",
+                total.user_id,
+                total.realized,
+                total.currency,
+                total.unrealized,
+                total.currency,
+                total.total(),
+                total.currency,
+                total.unpriced_symbols
+            );
+        }
+    }
+
+    /// Log a PnL snapshot every `interval` until the returned task is aborted
+    pub fn spawn_snapshot_logger(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                self.log_snapshot();
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(exec_id: &str, side: u8, qty: i32, px: f32) -> OrderUpdate {
+        OrderUpdate {
+            order_id: "ORD-1".to_string(),
+            orig_cl_ord_id: "CL-1".to_string(),
+            exec_id: exec_id.to_string(),
+            exec_type: b'F',
+            ord_status: b'1',
+            ord_rej_reason: 0,
+            user_id: "user001".to_string(),
+            symbol: "AAPL".to_string(),
+            side,
+            order_qty: qty,
+            last_qty: qty,
+            last_px: px,
+            leaves_qty: 0,
+            cum_qty: qty,
+            avg_px: px,
+            transact_time: 0,
+            text: String::new(),
+        }
+    }
+
+    fn engine(method: CostMethod) -> PnlEngine {
+        let engine = PnlEngine::new(method, MarkSource::Manual, "usd");
+        engine.set_manual_mark("AAPL", 14.0);
+        engine
+    }
+
+    #[test]
+    fn fifo_closes_oldest_lots_first() {
+        let engine = engine(CostMethod::Fifo);
+        engine.on_fill(&fill("E1", b'1', 10, 10.0));
+        engine.on_fill(&fill("E2", b'1', 10, 12.0));
+        engine.on_fill(&fill("E3", b'2', 15, 13.0));
+
+        let pnl = engine.pnl("user001", "AAPL").unwrap();
+        assert_eq!(pnl.net_qty, 5.0);
+        assert_eq!(pnl.realized, 10.0 * 3.0 + 5.0 * 1.0);
+        assert_eq!(pnl.unrealized, Some(5.0 * 2.0));
+    }
+
+    #[test]
+    fn average_cost_closes_against_blended_price() {
+        let engine = engine(CostMethod::AverageCost);
+        engine.on_fill(&fill("E1", b'1', 10, 10.0));
+        engine.on_fill(&fill("E2", b'1', 10, 12.0));
+        engine.on_fill(&fill("E3", b'2', 15, 13.0));
+
+        let pnl = engine.pnl("user001", "AAPL").unwrap();
+        assert_eq!(pnl.net_qty, 5.0);
+        assert_eq!(pnl.realized, 15.0 * 2.0);
+        assert_eq!(pnl.unrealized, Some(5.0 * 3.0));
+    }
+
+    #[test]
+    fn crossing_flat_opens_at_fill_price() {
+        for method in [CostMethod::Fifo, CostMethod::AverageCost] {
+            let engine = engine(method);
+            engine.on_fill(&fill("E1", b'1', 10, 10.0));
+            engine.on_fill(&fill("E2", b'2', 15, 11.0));
+
+            let pnl = engine.pnl("user001", "AAPL").unwrap();
+            assert_eq!(pnl.net_qty, -5.0);
+            assert_eq!(pnl.realized, 10.0);
+            assert_eq!(pnl.unrealized, Some(-5.0 * 3.0));
+        }
+    }
+
+    #[test]
+    fn repeated_exec_ids_are_ignored() {
+        let engine = engine(CostMethod::Fifo);
+        assert!(engine.on_fill(&fill("E1", b'1', 10, 10.0)));
+        assert!(!engine.on_fill(&fill("E1", b'1', 10, 10.0)));
+        assert_eq!(engine.pnl("user001", "AAPL").unwrap().net_qty, 10.0);
+    }
+
+    #[test]
+    fn seeded_positions_carry_their_cost_basis() {
+        for method in [CostMethod::Fifo, CostMethod::AverageCost] {
+            let engine = engine(method);
+            engine.seed(&Position {
+                user_id: "user001".to_string(),
+                symbol: "AAPL".to_string(),
+                net_qty: 10.0,
+                avg_cost: 10.0,
+            });
+            engine.on_fill(&fill("E1", b'2', 4, 13.0));
+
+            let pnl = engine.pnl("user001", "AAPL").unwrap();
+            assert_eq!(pnl.net_qty, 6.0);
+            assert_eq!(pnl.realized, 12.0);
+            assert_eq!(pnl.unrealized, Some(24.0));
+        }
+    }
+}
@@ -0,0 +1,443 @@
+// src/positions.rs
+use crate::order_handle::OrderUpdate;
+use crate::report::{ExecutionReport, OrderResponseReport};
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::RwLock;
+use tokio::sync::broadcast;
+
+/// Capacity of the position change channel before slow subscribers lag
+const CHANGE_CHANNEL_CAPACITY: usize = 1024;
+
+/// Net position of one user in one symbol
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    pub user_id: String,
+    pub symbol: String,
+    /// Positive when long, negative when short
+    pub net_qty: f64,
+    /// Average price of the open quantity, zero when flat
+    pub avg_cost: f64,
+}
+
+impl Position {
+    pub fn is_flat(&self) -> bool {
+        self.net_qty == 0.0
+    }
+
+    /// Apply a signed fill quantity at a price, keeping the average cost of the open quantity
+    fn apply_fill(&mut self, signed_qty: f64, price: f64) {
+        let new_qty = self.net_qty + signed_qty;
+
+        if self.net_qty == 0.0 || self.net_qty.signum() == signed_qty.signum() {
+            // Opening or increasing: blend the average cost
+            let open = self.net_qty.abs();
+            self.avg_cost = (open * self.avg_cost + signed_qty.abs() * price) / new_qty.abs();
+        } else if new_qty == 0.0 {
+            self.avg_cost = 0.0;
+        } else if new_qty.signum() != self.net_qty.signum() {
+            // Crossed through flat: the remainder was opened at the fill price
+            self.avg_cost = price;
+        }
+
+        self.net_qty = new_qty;
+    }
+}
+
+/// Change notification published after every applied fill or seed
+#[derive(Debug, Clone)]
+pub struct PositionChange {
+    pub position: Position,
+    /// ExecID of the fill that caused the change, empty for seeded positions
+    pub exec_id: String,
+}
+
+/// Start-of-day snapshot file, e.g. `config/start_of_day_positions.json`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PositionSnapshot {
+    pub positions: Vec<Position>,
+}
+
+/// Split an FX or crypto pair into base and quote currency
+/// ("EURUSD", "EUR/USD", "BTC-USD" and "BTC_USD" are supported)
+pub fn split_currency_pair(symbol: &str) -> Option<(String, String)> {
+    let symbol = symbol.trim().to_uppercase();
+
+    if let Some((base, quote)) = symbol.split_once(['/', '-', '_']) {
+        if !base.is_empty() && !quote.is_empty() {
+            return Some((base.to_string(), quote.to_string()));
+        }
+        return None;
+    }
+
+    if symbol.len() == 6 && symbol.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Some((symbol[..3].to_string(), symbol[3..].to_string()));
+    }
+
+    None
+}
+
+#[derive(Default)]
+struct PositionBook {
+    positions: HashMap<(String, String), Position>,
+    // Currency exposure per user: base long / quote short on a buy
+    exposures: HashMap<(String, String), f64>,
+    // ExecIDs applied today and the day before, so the OMS and matching engine
+    // reports of a fill around midnight are still deduplicated
+    trading_day: NaiveDate,
+    applied_exec_ids: HashSet<String>,
+    previous_exec_ids: HashSet<String>,
+}
+
+impl PositionBook {
+    /// Record an ExecID, false when it was already applied
+    fn record_exec_id(&mut self, exec_id: &str) -> bool {
+        let today = Utc::now().date_naive();
+        if self.trading_day != today {
+            self.previous_exec_ids = if self.trading_day.succ_opt() == Some(today) {
+                std::mem::take(&mut self.applied_exec_ids)
+            } else {
+                HashSet::new()
+            };
+            self.applied_exec_ids.clear();
+            self.trading_day = today;
+        }
+
+        !self.previous_exec_ids.contains(exec_id)
+            && self.applied_exec_ids.insert(exec_id.to_string())
+    }
+
+    fn adjust_exposure(&mut self, user_id: &str, symbol: &str, signed_qty: f64, price: f64) {
+        if let Some((base, quote)) = split_currency_pair(symbol) {
+            *self
+                .exposures
+                .entry((user_id.to_string(), base))
+                .or_default() += signed_qty;
+            *self
+                .exposures
+                .entry((user_id.to_string(), quote))
+                .or_default() -= signed_qty * price;
+        }
+    }
+}
+
+/// Position keeping from fills, per user and symbol
+pub struct PositionKeeper {
+    book: RwLock<PositionBook>,
+    changes: broadcast::Sender<PositionChange>,
+}
+
+impl Default for PositionKeeper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PositionKeeper {
+    pub fn new() -> Self {
+        let (changes, _) = broadcast::channel(CHANGE_CHANNEL_CAPACITY);
+        Self {
+            book: RwLock::new(PositionBook::default()),
+            changes,
+        }
+    }
+
+    /// Seed positions from a start-of-day snapshot file
+    pub fn load_start_of_day<P: AsRef<Path>>(&self, path: P) -> Result<usize> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read start-of-day positions {:?}", path))?;
+        let snapshot: PositionSnapshot = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse start-of-day positions {:?}", path))?;
+
+        let count = snapshot.positions.len();
+        for position in snapshot.positions {
+            self.seed(position);
+        }
+
+        info!("✅ Seeded {} start-of-day positions from {:?}", count, path);
+        Ok(count)
+    }
+
+    /// Replace the position for a user and symbol, e.g. from a snapshot
+    pub fn seed(&self, position: Position) {
+        {
+            let mut book = self.book.write().unwrap();
+            let key = (position.user_id.clone(), position.symbol.clone());
+
+            // Swap the previous contribution to currency exposure for the seeded one
+            if let Some(previous) = book.positions.get(&key).cloned() {
+                book.adjust_exposure(
+                    &previous.user_id,
+                    &previous.symbol,
+                    -previous.net_qty,
+                    previous.avg_cost,
+                );
+            }
+            book.adjust_exposure(
+                &position.user_id,
+                &position.symbol,
+                position.net_qty,
+                position.avg_cost,
+            );
+            book.positions.insert(key, position.clone());
+        }
+
+        let _ = self.changes.send(PositionChange {
+            position,
+            exec_id: String::new(),
+        });
+    }
+
+    /// Receive a notification for every position change from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<PositionChange> {
+        self.changes.subscribe()
+    }
+
+    /// Apply a fill from an OrderResponseReport
+    pub fn on_order_response(&self, report: &OrderResponseReport) {
+        self.on_fill(&OrderUpdate::from(report));
+    }
+
+    /// Apply a fill from an ExecutionReport
+    pub fn on_execution_report(&self, report: &ExecutionReport) {
+        self.on_fill(&OrderUpdate::from(report));
+    }
+
+    /// Apply a fill; non-fill reports and already applied ExecIDs are ignored.
+    /// Returns true when the position changed.
+    pub fn on_fill(&self, update: &OrderUpdate) -> bool {
+        if !update.is_fill() {
+            return false;
+        }
+
+        let Some(signed_qty) = update.signed_last_qty() else {
+            warn!(
+                "⚠️ Ignoring fill {} with unknown side '{}'",
+                update.exec_id, update.side as char
+            );
+            return false;
+        };
+        let price = update.last_px as f64;
+
+        let position = {
+            let mut book = self.book.write().unwrap();
+
+            // Both the OMS and the matching engine report the same execution
+            if !update.exec_id.is_empty() && !book.record_exec_id(&update.exec_id) {
+                return false;
+            }
+
+            let key = (update.user_id.clone(), update.symbol.clone());
+            let position = book.positions.entry(key).or_insert_with(|| Position {
+                user_id: update.user_id.clone(),
+                symbol: update.symbol.clone(),
+                ..Position::default()
+            });
+            position.apply_fill(signed_qty, price);
+            let position = position.clone();
+
+            book.adjust_exposure(&update.user_id, &update.symbol, signed_qty, price);
+            position
+        };
+
+        info!(
+            "📊 POSITION: User={}, Symbol={}, NetQty={}, AvgCost={} (ExecID={})",
+            position.user_id, position.symbol, position.net_qty, position.avg_cost, update.exec_id
+        );
+
+        let _ = self.changes.send(PositionChange {
+            position,
+            exec_id: update.exec_id.clone(),
+        });
+        true
+    }
+
+    /// Position of a user in a symbol
+    pub fn position(&self, user_id: &str, symbol: &str) -> Option<Position> {
+        let book = self.book.read().unwrap();
+        book.positions
+            .get(&(user_id.to_string(), symbol.to_string()))
+            .cloned()
+    }
+
+    /// All positions of a user
+    pub fn positions_for_user(&self, user_id: &str) -> Vec<Position> {
+        let book = self.book.read().unwrap();
+        book.positions
+            .values()
+            .filter(|p| p.user_id == user_id)
+            .cloned()
+            .collect()
+    }
+
+    /// All positions across users
+    pub fn positions(&self) -> Vec<Position> {
+        self.book
+            .read()
+            .unwrap()
+            .positions
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// Net quantity in a symbol summed over all users
+    pub fn net_qty(&self, symbol: &str) -> f64 {
+        let book = self.book.read().unwrap();
+        book.positions
+            .values()
+            .filter(|p| p.symbol == symbol)
+            .map(|p| p.net_qty)
+            .sum()
+    }
+
+    /// Exposure of a user in a currency (positive long, negative short)
+    pub fn exposure(&self, user_id: &str, currency: &str) -> f64 {
+        let book = self.book.read().unwrap();
+        book.exposures
+            .get(&(user_id.to_string(), currency.to_uppercase()))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Currency exposures summed over all users
+    pub fn exposures(&self) -> HashMap<String, f64> {
+        let book = self.book.read().unwrap();
+        let mut totals: HashMap<String, f64> = HashMap::new();
+        for ((_, currency), amount) in book.exposures.iter() {
+            *totals.entry(currency.clone()).or_default() += amount;
+        }
+        totals
+    }
+
+    /// Current positions as a snapshot that can be written and re-seeded later
+    pub fn snapshot(&self) -> PositionSnapshot {
+        PositionSnapshot {
+            positions: self.positions(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(
+        exec_id: &str,
+        user_id: &str,
+        symbol: &str,
+        side: u8,
+        qty: i32,
+        px: f32,
+    ) -> OrderUpdate {
+        OrderUpdate {
+            order_id: "ORD-1".to_string(),
+            orig_cl_ord_id: "CL-1".to_string(),
+            exec_id: exec_id.to_string(),
+            exec_type: b'F',
+            ord_status: b'1',
+            ord_rej_reason: 0,
+            user_id: user_id.to_string(),
+            symbol: symbol.to_string(),
+            side,
+            order_qty: qty,
+            last_qty: qty,
+            last_px: px,
+            leaves_qty: 0,
+            cum_qty: qty,
+            avg_px: px,
+            transact_time: 0,
+            text: String::new(),
+        }
+    }
+
+    fn execution_report(update: &OrderUpdate) -> ExecutionReport {
+        ExecutionReport {
+            dats_destination_user: update.user_id.clone(),
+            order_id: update.order_id.clone(),
+            orig_cl_ord_id: update.orig_cl_ord_id.clone(),
+            exec_id: update.exec_id.clone(),
+            exec_type: update.exec_type,
+            ord_status: update.ord_status,
+            symbol: update.symbol.clone(),
+            side: update.side,
+            order_qty: update.order_qty,
+            last_qty: update.last_qty,
+            last_px: update.last_px,
+            cum_qty: update.cum_qty,
+            avg_px: update.avg_px,
+            ..ExecutionReport::default()
+        }
+    }
+
+    #[test]
+    fn fills_reported_by_the_oms_and_the_matching_engine_apply_once() {
+        let keeper = PositionKeeper::new();
+        let update = fill("EXEC-1", "user001", "BTC-USD", b'1', 10, 100.0);
+
+        keeper.on_execution_report(&execution_report(&update));
+        assert!(!keeper.on_fill(&update));
+        assert_eq!(keeper.position("user001", "BTC-USD").unwrap().net_qty, 10.0);
+
+        assert!(keeper.on_fill(&fill("EXEC-2", "user001", "BTC-USD", b'1', 5, 106.0)));
+        let position = keeper.position("user001", "BTC-USD").unwrap();
+        assert_eq!(position.net_qty, 15.0);
+        assert_eq!(position.avg_cost, 102.0);
+    }
+
+    #[test]
+    fn buys_go_long_and_sells_go_short_in_base_and_quote() {
+        let keeper = PositionKeeper::new();
+        keeper.on_fill(&fill("EXEC-1", "user001", "EURUSD", b'1', 1000, 1.25));
+        assert_eq!(keeper.exposure("user001", "EUR"), 1000.0);
+        assert_eq!(keeper.exposure("user001", "USD"), -1250.0);
+
+        keeper.on_fill(&fill("EXEC-2", "user001", "EURUSD", b'2', 1500, 1.5));
+        let position = keeper.position("user001", "EURUSD").unwrap();
+        assert_eq!(position.net_qty, -500.0);
+        assert_eq!(position.avg_cost, 1.5);
+        assert_eq!(keeper.exposure("user001", "eur"), -500.0);
+        assert_eq!(keeper.exposure("user001", "USD"), -1250.0 + 2250.0);
+
+        let unknown_side = fill("EXEC-3", "user001", "EURUSD", b'9', 10, 1.5);
+        assert!(!keeper.on_fill(&unknown_side));
+    }
+
+    #[test]
+    fn positions_are_kept_per_user_and_symbol() {
+        let keeper = PositionKeeper::new();
+        keeper.on_fill(&fill("EXEC-1", "user001", "BTC-USD", b'1', 10, 100.0));
+        keeper.on_fill(&fill("EXEC-2", "user002", "BTC-USD", b'2', 4, 100.0));
+        keeper.on_fill(&fill("EXEC-3", "user001", "ETH-USD", b'2', 3, 10.0));
+
+        assert_eq!(keeper.position("user001", "BTC-USD").unwrap().net_qty, 10.0);
+        assert_eq!(keeper.position("user002", "BTC-USD").unwrap().net_qty, -4.0);
+        assert_eq!(keeper.position("user001", "ETH-USD").unwrap().net_qty, -3.0);
+        assert!(keeper.position("user002", "ETH-USD").is_none());
+        assert_eq!(keeper.positions_for_user("user001").len(), 2);
+        assert_eq!(keeper.net_qty("BTC-USD"), 6.0);
+        assert_eq!(keeper.exposures()["BTC"], 6.0);
+    }
+
+    #[test]
+    fn exec_ids_are_kept_for_today_and_the_day_before_only() {
+        let today = Utc::now().date_naive();
+        let mut book = PositionBook {
+            trading_day: today.pred_opt().unwrap(),
+            applied_exec_ids: HashSet::from(["EXEC-1".to_string()]),
+            ..PositionBook::default()
+        };
+        assert!(!book.record_exec_id("EXEC-1"));
+        assert!(book.record_exec_id("EXEC-2"));
+        assert_eq!(book.applied_exec_ids.len(), 1);
+
+        book.trading_day = today - chrono::Duration::days(3);
+        assert!(book.record_exec_id("EXEC-1"));
+        assert!(book.previous_exec_ids.is_empty());
+    }
+}
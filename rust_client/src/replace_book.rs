@@ -0,0 +1,226 @@
+// src/replace_book.rs
+use crate::new_order_single::NewOrderSingle;
+use crate::order_handle::OrderUpdate;
+use crate::report::{ExecutionReport, OrderCancelReject};
+use anyhow::{bail, Result};
+use log::{info, warn};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Replacement sent for an order, waiting for the matching engine
+#[derive(Debug, Clone)]
+struct PendingReplace {
+    cl_ord_id: String,
+    order: NewOrderSingle,
+}
+
+#[derive(Debug, Default)]
+struct ReplaceState {
+    // Pending replacement by the ClOrdID it replaces
+    pending: HashMap<String, PendingReplace>,
+    // ClOrdID of the confirmed replacement of each replaced order
+    replaced_by: HashMap<String, String>,
+    // Terms of confirmed replacements by their ClOrdID
+    replacements: HashMap<String, NewOrderSingle>,
+}
+
+impl ReplaceState {
+    /// Take the pending replacement matching a report's ClOrdID, given either the
+    /// replaced or the replacement ClOrdID
+    fn take_pending(&mut self, cl_ord_id: &str) -> Option<(String, PendingReplace)> {
+        let replaced = if self.pending.contains_key(cl_ord_id) {
+            cl_ord_id.to_string()
+        } else {
+            self.pending
+                .iter()
+                .find(|(_, pending)| pending.cl_ord_id == cl_ord_id)
+                .map(|(replaced, _)| replaced.clone())?
+        };
+        self.pending
+            .remove(&replaced)
+            .map(|pending| (replaced, pending))
+    }
+}
+
+/// Amendments of NewOrderSingles. A replacement only carries the live terms of its
+/// order once the matching engine reports it Replaced (ExecType 5); a rejected
+/// replace leaves the order as it was.
+#[derive(Debug, Default)]
+pub struct ReplaceBook {
+    state: Mutex<ReplaceState>,
+}
+
+impl ReplaceBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a replace request for `cl_ord_id` before it is sent. Fails while an
+    /// earlier replacement of the order is still pending.
+    pub fn add_pending(&self, cl_ord_id: &str, replacement: NewOrderSingle) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(pending) = state.pending.get(cl_ord_id) {
+            bail!(
+                "Order {} already has replacement {} pending",
+                cl_ord_id,
+                pending.cl_ord_id
+            );
+        }
+
+        state.pending.insert(
+            cl_ord_id.to_string(),
+            PendingReplace {
+                cl_ord_id: replacement.cl_ord_id.clone(),
+                order: replacement,
+            },
+        );
+        Ok(())
+    }
+
+    /// Forget the pending replacement of an order whose replace request was not sent
+    pub fn remove_pending(&self, cl_ord_id: &str) {
+        self.state.lock().unwrap().pending.remove(cl_ord_id);
+    }
+
+    /// ClOrdID of the replacement pending for an order
+    pub fn pending(&self, cl_ord_id: &str) -> Option<String> {
+        let state = self.state.lock().unwrap();
+        state
+            .pending
+            .get(cl_ord_id)
+            .map(|pending| pending.cl_ord_id.clone())
+    }
+
+    /// Terms of the latest confirmed replacement of an order, given the original or a
+    /// replacement ClOrdID. None if the order was never replaced.
+    pub fn live_order(&self, cl_ord_id: &str) -> Option<NewOrderSingle> {
+        let state = self.state.lock().unwrap();
+        let mut latest = cl_ord_id;
+        while let Some(next) = state.replaced_by.get(latest) {
+            latest = next;
+        }
+        state.replacements.get(latest).cloned()
+    }
+
+    /// Promote a pending replacement on its Replaced report and drop it when the
+    /// replacement is rejected
+    pub fn on_update(&self, update: &OrderUpdate) {
+        if !matches!(update.exec_type, b'5' | b'8') {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let Some((replaced, pending)) = state.take_pending(update.order_key()) else {
+            return;
+        };
+
+        if update.exec_type == b'8' {
+            warn!(
+                "❌ Replacement {} of order {} rejected: {}",
+                pending.cl_ord_id, replaced, update.text
+            );
+            return;
+        }
+
+        info!("✏️ Order {} replaced by {}", replaced, pending.cl_ord_id);
+        state
+            .replaced_by
+            .insert(replaced, pending.cl_ord_id.clone());
+        state.replacements.insert(pending.cl_ord_id, pending.order);
+    }
+
+    /// Track replacements from an ExecutionReport
+    pub fn on_execution_report(&self, report: &ExecutionReport) {
+        self.on_update(&OrderUpdate::from(report));
+    }
+
+    /// Drop the pending replacement a Cancel/Replace reject refers to
+    pub fn on_cancel_reject(&self, reject: &OrderCancelReject) {
+        if reject.cxl_rej_response_to != b'2' {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let pending = state
+            .take_pending(&reject.orig_cl_ord_id)
+            .or_else(|| state.take_pending(&reject.cl_ord_id));
+        if let Some((replaced, pending)) = pending {
+            warn!(
+                "❌ Replacement {} of order {} rejected: {}",
+                pending.cl_ord_id, replaced, reject.text
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(cl_ord_id: &str, quantity: i32) -> NewOrderSingle {
+        NewOrderSingle::limit_order(
+            cl_ord_id.to_string(),
+            "BTC-USD".to_string(),
+            "1",
+            quantity,
+            100.0,
+            "rust_trader_001".to_string(),
+        )
+    }
+
+    fn update(orig_cl_ord_id: &str, exec_type: u8) -> OrderUpdate {
+        OrderUpdate {
+            order_id: "ORD-1".to_string(),
+            orig_cl_ord_id: orig_cl_ord_id.to_string(),
+            exec_id: format!("EXEC-{}", exec_type as char),
+            exec_type,
+            ord_status: exec_type,
+            ord_rej_reason: 0,
+            user_id: "rust_trader_001".to_string(),
+            symbol: "BTC-USD".to_string(),
+            side: b'1',
+            order_qty: 20,
+            last_qty: 0,
+            last_px: 0.0,
+            leaves_qty: 20,
+            cum_qty: 0,
+            avg_px: 0.0,
+            transact_time: 0,
+            text: String::new(),
+        }
+    }
+
+    #[test]
+    fn replacement_goes_live_on_replaced_report() {
+        let book = ReplaceBook::new();
+        book.add_pending("CL-1", order("CL-2", 20)).unwrap();
+        assert_eq!(book.pending("CL-1").as_deref(), Some("CL-2"));
+        assert!(book.live_order("CL-1").is_none());
+        assert!(book.add_pending("CL-1", order("CL-3", 30)).is_err());
+
+        book.on_update(&update("CL-1", b'0'));
+        assert!(book.live_order("CL-1").is_none());
+
+        book.on_update(&update("CL-2", b'5'));
+        assert!(book.pending("CL-1").is_none());
+        assert_eq!(book.live_order("CL-1").unwrap().order_qty, 20);
+        assert_eq!(book.live_order("CL-2").unwrap().cl_ord_id, "CL-2");
+
+        book.add_pending("CL-2", order("CL-3", 30)).unwrap();
+        book.on_update(&update("CL-2", b'5'));
+        assert_eq!(book.live_order("CL-1").unwrap().cl_ord_id, "CL-3");
+    }
+
+    #[test]
+    fn rejected_replacement_leaves_the_order_as_it_was() {
+        let book = ReplaceBook::new();
+        book.add_pending("CL-1", order("CL-2", 20)).unwrap();
+        book.on_update(&update("CL-2", b'8'));
+        assert!(book.pending("CL-1").is_none());
+        assert!(book.live_order("CL-1").is_none());
+
+        book.add_pending("CL-1", order("CL-3", 30)).unwrap();
+        book.remove_pending("CL-1");
+        assert!(book.pending("CL-1").is_none());
+    }
+}
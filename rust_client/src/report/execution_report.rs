@@ -1,8 +1,10 @@
-use rustdds::*;
+use crate::gap_fill::InboundMessage;
+use crate::order_handle::OrderUpdate;
+use crate::report::ReportMessage;
 use serde::{Deserialize, Serialize};
 
 /// FIX Header structure matching C++ DistributedATS::Header
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Header {
     #[serde(rename = "BeginString")]
     pub begin_string: String,
@@ -32,22 +34,6 @@ pub struct Header {
     pub sending_time: u64, // C++ unsigned long long maps to u64
 }
 
-impl Default for Header {
-    fn default() -> Self {
-        Self {
-            begin_string: String::new(),
-            body_length: 0,
-            msg_type: String::new(),
-            sender_comp_id: String::new(),
-            target_comp_id: String::new(),
-            msg_seq_num: 0,
-            sender_sub_id: String::new(),
-            target_sub_id: String::new(),
-            sending_time: 0,
-        }
-    }
-}
-
 /// ✅ FIXED: ExecutionReport struct exactly matching C++ FastDDS IDL structure
 /// Following OMS architecture patterns for reliable financial message handling
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -135,6 +121,10 @@ pub struct ExecutionReport {
 
     #[serde(rename = "Text")]
     pub text: String,
+
+    /// Set by a listener delivering a report older than one it already processed
+    #[serde(skip)]
+    pub stale: bool,
 }
 
 impl ExecutionReport {
@@ -291,13 +281,61 @@ impl ExecutionReport {
     }
 }
 
+impl ReportMessage for ExecutionReport {
+    const TOPIC_NAME: &'static str = "EXECUTION_REPORT_TOPIC";
+    const TYPE_NAME: &'static str = Self::type_name();
+    const NAME: &'static str = "ExecutionReport";
+
+    fn cache_keys(&self) -> Vec<String> {
+        vec![self.order_id.clone(), self.orig_cl_ord_id.clone()]
+    }
+
+    fn audit_fields(&self) -> String {
+        format!(
+            "ExecID={}, OrderID={}, OrigClOrdID={}, Symbol={}, Side={}, Qty={}, Price={}, ExecType={}, OrdStatus={}, CumQty={}, AvgPx={}, Text='{}'",
+            self.exec_id,
+            self.order_id,
+            self.orig_cl_ord_id,
+            self.symbol,
+            self.side,
+            self.order_qty,
+            self.price,
+            self.get_execution_type(),
+            self.get_execution_status(),
+            self.cum_qty,
+            self.avg_px,
+            self.text
+        )
+    }
+
+    fn order_update(&self) -> Option<OrderUpdate> {
+        Some(OrderUpdate::from(self))
+    }
+
+    fn inbound(&self) -> Option<InboundMessage> {
+        Some(InboundMessage::new(
+            &self.dats_source,
+            &self.dats_destination,
+            &self.dats_source_user,
+            &self.dats_destination_user,
+            &self.fix_header,
+        ))
+    }
+
+    fn mark_stale(&mut self) {
+        self.stale = true;
+    }
+
+    fn is_stale(&self) -> bool {
+        self.stale
+    }
+}
+
 /// Implement RustDDS serialization traits for FastDDS compatibility
 impl rustdds::Keyed for ExecutionReport {
     type K = ();
 
-    fn key(&self) -> Self::K {
-        ()
-    }
+    fn key(&self) -> Self::K {}
 }
 
 /// Implement Default for ExecutionReport following RAII principles
@@ -331,6 +369,7 @@ impl Default for ExecutionReport {
             avg_px: 0.0,
             transact_time: 0,
             text: String::new(),
+            stale: false,
         }
     }
 }
@@ -2,14 +2,31 @@
 //! Provides comprehensive execution report handling for financial trading systems
 
 pub mod execution_report;
-pub mod execution_report_listener;
+pub mod order_cancel_reject;
+pub mod order_mass_cancel_report;
 pub mod order_response_report;
-pub mod order_response_report_listener;
+pub mod report_callback;
+pub mod report_decoder;
+pub mod report_listener;
+pub mod report_ordering;
+pub mod report_stream;
+pub mod status_cache;
 
 // Re-export key types for easier usage following OMS patterns
 pub use execution_report::ExecutionReport;
-pub use execution_report_listener::{ExecutionReportConnectionStatus, ExecutionReportListener};
+pub use order_cancel_reject::OrderCancelReject;
+pub use order_mass_cancel_report::OrderMassCancelReport;
 pub use order_response_report::OrderResponseReport;
-pub use order_response_report_listener::{
-    OrderResponseListener, OrderResponseReportConnectionStatus,
+pub use report_callback::{
+    AsyncReportCallback, CallbackGuard, ReportCallback, DEFAULT_CALLBACK_PRIORITY,
 };
+pub use report_decoder::{PayloadDecodeError, ReportDeserializerAdapter, ReportReader};
+pub use report_listener::{ReportListener, ReportListenerStatus, ReportMessage};
+pub use report_ordering::{ReportOrdering, StaleReportPolicy};
+pub use report_stream::{ReportFilter, ReportLagged, ReportStream};
+pub use status_cache::{ArchiveReason, ArchivedOrder, CacheRetention, StatusPage};
+
+pub type ExecutionReportListener = ReportListener<ExecutionReport>;
+pub type OrderResponseListener = ReportListener<OrderResponseReport>;
+pub type OrderCancelRejectListener = ReportListener<OrderCancelReject>;
+pub type OrderMassCancelReportListener = ReportListener<OrderMassCancelReport>;
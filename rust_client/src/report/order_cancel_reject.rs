@@ -0,0 +1,94 @@
+use crate::gap_fill::InboundMessage;
+use crate::report::execution_report::Header;
+use crate::report::ReportMessage;
+use serde::{Deserialize, Serialize};
+
+/// OrderCancelReject (MsgType 9) matching C++ DistributedATS_OrderCancelReject::OrderCancelReject
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrderCancelReject {
+    #[serde(rename = "DATS_Source")]
+    pub dats_source: String,
+
+    #[serde(rename = "DATS_Destination")]
+    pub dats_destination: String,
+
+    #[serde(rename = "DATS_SourceUser")]
+    pub dats_source_user: String,
+
+    #[serde(rename = "DATS_DestinationUser")]
+    pub dats_destination_user: String,
+
+    #[serde(rename = "fix_header")]
+    pub fix_header: Header,
+
+    #[serde(rename = "OrderID")]
+    pub order_id: String,
+
+    /// ClOrdID of the rejected cancel or cancel/replace request
+    #[serde(rename = "ClOrdID")]
+    pub cl_ord_id: String,
+
+    /// ClOrdID of the order the request was for
+    #[serde(rename = "OrigClOrdID")]
+    pub orig_cl_ord_id: String,
+
+    #[serde(rename = "OrdStatus")]
+    pub ord_status: u8, // C++ char maps to Rust u8
+
+    #[serde(rename = "TransactTime")]
+    pub transact_time: u64,
+
+    /// '1' for an OrderCancelRequest, '2' for an OrderCancelReplaceRequest
+    #[serde(rename = "CxlRejResponseTo")]
+    pub cxl_rej_response_to: u8,
+
+    #[serde(rename = "Text")]
+    pub text: String,
+}
+
+impl OrderCancelReject {
+    pub const fn type_name() -> &'static str {
+        "DistributedATS_OrderCancelReject::OrderCancelReject"
+    }
+
+    /// Get the rejected request type as human readable string following FIX protocol
+    pub fn get_response_to(&self) -> &'static str {
+        match self.cxl_rej_response_to {
+            b'1' => "Cancel",
+            b'2' => "Cancel/Replace",
+            _ => "Unknown",
+        }
+    }
+}
+
+impl ReportMessage for OrderCancelReject {
+    const TOPIC_NAME: &'static str = "ORDER_CANCEL_REJECT_TOPIC";
+    const TYPE_NAME: &'static str = Self::type_name();
+    const NAME: &'static str = "OrderCancelReject";
+
+    fn cache_keys(&self) -> Vec<String> {
+        vec![self.orig_cl_ord_id.clone(), self.cl_ord_id.clone()]
+    }
+
+    fn audit_fields(&self) -> String {
+        format!(
+            "OrderID={}, ClOrdID={}, OrigClOrdID={}, ResponseTo={}, OrdStatus={}, Text='{}'",
+            self.order_id,
+            self.cl_ord_id,
+            self.orig_cl_ord_id,
+            self.get_response_to(),
+            self.ord_status as char,
+            self.text
+        )
+    }
+
+    fn inbound(&self) -> Option<InboundMessage> {
+        Some(InboundMessage::new(
+            &self.dats_source,
+            &self.dats_destination,
+            &self.dats_source_user,
+            &self.dats_destination_user,
+            &self.fix_header,
+        ))
+    }
+}
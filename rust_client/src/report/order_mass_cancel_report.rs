@@ -0,0 +1,88 @@
+use crate::gap_fill::InboundMessage;
+use crate::report::execution_report::Header;
+use crate::report::ReportMessage;
+use serde::{Deserialize, Serialize};
+
+/// OrderMassCancelReport (MsgType r) matching
+/// C++ DistributedATS_OrderMassCancelReport::OrderMassCancelReport
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrderMassCancelReport {
+    #[serde(rename = "DATS_Source")]
+    pub dats_source: String,
+
+    #[serde(rename = "DATS_Destination")]
+    pub dats_destination: String,
+
+    #[serde(rename = "DATS_SourceUser")]
+    pub dats_source_user: String,
+
+    #[serde(rename = "DATS_DestinationUser")]
+    pub dats_destination_user: String,
+
+    #[serde(rename = "fix_header")]
+    pub fix_header: Header,
+
+    #[serde(rename = "OrderID")]
+    pub order_id: String,
+
+    #[serde(rename = "MassCancelRequestType")]
+    pub mass_cancel_request_type: u8, // C++ char maps to Rust u8
+
+    /// '0' when the request was rejected, otherwise echoes the request type
+    #[serde(rename = "MassCancelResponse")]
+    pub mass_cancel_response: u8,
+
+    #[serde(rename = "Symbol")]
+    pub symbol: String,
+
+    #[serde(rename = "SecurityExchange")]
+    pub security_exchange: String,
+
+    #[serde(rename = "TransactTime")]
+    pub transact_time: u64,
+
+    #[serde(rename = "Text")]
+    pub text: String,
+}
+
+impl OrderMassCancelReport {
+    pub const fn type_name() -> &'static str {
+        "DistributedATS_OrderMassCancelReport::OrderMassCancelReport"
+    }
+
+    pub fn is_rejected(&self) -> bool {
+        self.mass_cancel_response == b'0'
+    }
+}
+
+impl ReportMessage for OrderMassCancelReport {
+    const TOPIC_NAME: &'static str = "ORDER_MASS_CANCEL_REPORT_TOPIC";
+    const TYPE_NAME: &'static str = Self::type_name();
+    const NAME: &'static str = "OrderMassCancelReport";
+
+    fn cache_keys(&self) -> Vec<String> {
+        vec![self.order_id.clone()]
+    }
+
+    fn audit_fields(&self) -> String {
+        format!(
+            "OrderID={}, RequestType={}, Response={}, Symbol={}, Exchange={}, Text='{}'",
+            self.order_id,
+            self.mass_cancel_request_type as char,
+            self.mass_cancel_response as char,
+            self.symbol,
+            self.security_exchange,
+            self.text
+        )
+    }
+
+    fn inbound(&self) -> Option<InboundMessage> {
+        Some(InboundMessage::new(
+            &self.dats_source,
+            &self.dats_destination,
+            &self.dats_source_user,
+            &self.dats_destination_user,
+            &self.fix_header,
+        ))
+    }
+}
@@ -1,4 +1,5 @@
-use rustdds::*;
+use crate::order_handle::OrderUpdate;
+use crate::report::ReportMessage;
 use serde::{Deserialize, Serialize};
 
 /// ✅ FIXED: OrderResponseReport struct exactly matching C++ FastDDS IDL
@@ -84,6 +85,10 @@ pub struct OrderResponseReport {
 
     #[serde(rename = "Text")]
     pub text: String,
+
+    /// Set by a listener delivering a report older than one it already processed
+    #[serde(skip)]
+    pub stale: bool,
 }
 
 impl OrderResponseReport {
@@ -189,3 +194,43 @@ impl OrderResponseReport {
         )
     }
 }
+
+impl ReportMessage for OrderResponseReport {
+    const TOPIC_NAME: &'static str = "order_response";
+    const TYPE_NAME: &'static str = "OMS::OrderResponseReport";
+    const NAME: &'static str = "OrderResponseReport";
+
+    fn cache_keys(&self) -> Vec<String> {
+        vec![self.order_id.clone(), self.orig_cl_ord_id.clone()]
+    }
+
+    fn audit_fields(&self) -> String {
+        format!(
+            "ExecID={}, OrderID={}, OrigClOrdID={}, Symbol={}, Side={}, Qty={}, Price={}, ExecType={}, OrdStatus={}, CumQty={}, AvgPx={}, Text='{}'",
+            self.exec_id,
+            self.order_id,
+            self.orig_cl_ord_id,
+            self.symbol,
+            self.side,
+            self.order_qty,
+            self.price,
+            self.get_execution_type(),
+            self.get_execution_status(),
+            self.cum_qty,
+            self.avg_px,
+            self.text
+        )
+    }
+
+    fn order_update(&self) -> Option<OrderUpdate> {
+        Some(OrderUpdate::from(self))
+    }
+
+    fn mark_stale(&mut self) {
+        self.stale = true;
+    }
+
+    fn is_stale(&self) -> bool {
+        self.stale
+    }
+}
@@ -0,0 +1,185 @@
+// src/report/report_callback.rs
+use futures::future::BoxFuture;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock, Weak};
+
+/// Callback invoked inline for every received report
+pub type ReportCallback<T> = Arc<dyn Fn(&T) + Send + Sync>;
+
+/// Callback whose future is spawned for every received report
+pub type AsyncReportCallback<T> = Arc<dyn Fn(T) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Priority of callbacks that don't ask for one; higher runs first
+pub const DEFAULT_CALLBACK_PRIORITY: i32 = 0;
+
+pub(crate) enum Callback<T> {
+    Sync(ReportCallback<T>),
+    Async(AsyncReportCallback<T>),
+}
+
+// Manual impl: cloning the Arcs doesn't need `T: Clone`
+impl<T> Clone for Callback<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Sync(callback) => Self::Sync(Arc::clone(callback)),
+            Self::Async(callback) => Self::Async(Arc::clone(callback)),
+        }
+    }
+}
+
+struct RegisteredCallback<T> {
+    id: u64,
+    priority: i32,
+    callback: Callback<T>,
+}
+
+/// Callbacks of a listener, kept in priority order
+pub(crate) struct CallbackRegistry<T> {
+    callbacks: Arc<RwLock<Vec<RegisteredCallback<T>>>>,
+    next_id: AtomicU64,
+}
+
+impl<T: Send + Sync + 'static> CallbackRegistry<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            callbacks: Arc::new(RwLock::new(Vec::new())),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Add a callback after every other of equal or higher priority
+    pub(crate) fn register(&self, priority: i32, callback: Callback<T>) -> CallbackGuard {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        {
+            let mut callbacks = self.callbacks.write().unwrap();
+            let index = callbacks.partition_point(|c| c.priority >= priority);
+            callbacks.insert(
+                index,
+                RegisteredCallback {
+                    id,
+                    priority,
+                    callback,
+                },
+            );
+        }
+
+        let callbacks: Weak<RwLock<Vec<RegisteredCallback<T>>>> = Arc::downgrade(&self.callbacks);
+        CallbackGuard {
+            unregister: Some(Box::new(move || {
+                if let Some(callbacks) = callbacks.upgrade() {
+                    callbacks.write().unwrap().retain(|c| c.id != id);
+                }
+            })),
+        }
+    }
+
+    /// Callbacks in priority order, cloned so none runs under the lock
+    pub(crate) fn snapshot(&self) -> Vec<Callback<T>> {
+        self.callbacks
+            .read()
+            .unwrap()
+            .iter()
+            .map(|c| c.callback.clone())
+            .collect()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.callbacks.read().unwrap().len()
+    }
+}
+
+/// Keeps a callback registered; dropping it unregisters the callback
+#[must_use = "dropping the guard unregisters the callback; call detach() to keep it"]
+pub struct CallbackGuard {
+    unregister: Option<Box<dyn FnOnce() + Send + Sync>>,
+}
+
+impl CallbackGuard {
+    /// Keep the callback registered for the lifetime of the listener
+    pub fn detach(mut self) {
+        self.unregister = None;
+    }
+}
+
+impl Drop for CallbackGuard {
+    fn drop(&mut self) {
+        if let Some(unregister) = self.unregister.take() {
+            unregister();
+        }
+    }
+}
+
+impl std::fmt::Debug for CallbackGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CallbackGuard")
+            .field("registered", &self.unregister.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Sync callback appending `name` to `calls`
+    fn recorder(calls: &Arc<Mutex<Vec<&'static str>>>, name: &'static str) -> Callback<i32> {
+        let calls = Arc::clone(calls);
+        Callback::Sync(Arc::new(move |_| calls.lock().unwrap().push(name)))
+    }
+
+    fn run(registry: &CallbackRegistry<i32>) {
+        for callback in registry.snapshot() {
+            match callback {
+                Callback::Sync(callback) => callback(&1),
+                Callback::Async(_) => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn callbacks_run_by_priority_then_registration_order() {
+        let registry = CallbackRegistry::new();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let guards = [
+            registry.register(DEFAULT_CALLBACK_PRIORITY, recorder(&calls, "first")),
+            registry.register(10, recorder(&calls, "urgent")),
+            registry.register(DEFAULT_CALLBACK_PRIORITY, recorder(&calls, "second")),
+            registry.register(-5, recorder(&calls, "last")),
+        ];
+
+        run(&registry);
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["urgent", "first", "second", "last"]
+        );
+        drop(guards);
+    }
+
+    #[test]
+    fn dropping_the_guard_unregisters_and_detach_keeps_the_callback() {
+        let registry = CallbackRegistry::new();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let dropped = registry.register(DEFAULT_CALLBACK_PRIORITY, recorder(&calls, "dropped"));
+        registry
+            .register(DEFAULT_CALLBACK_PRIORITY, recorder(&calls, "detached"))
+            .detach();
+        assert_eq!(registry.len(), 2);
+
+        drop(dropped);
+        assert_eq!(registry.len(), 1);
+        run(&registry);
+        assert_eq!(*calls.lock().unwrap(), vec!["detached"]);
+    }
+
+    #[test]
+    fn guard_outliving_the_registry_is_harmless() {
+        let registry = CallbackRegistry::new();
+        let guard = registry.register(
+            DEFAULT_CALLBACK_PRIORITY,
+            Callback::Async(Arc::new(|_: i32| Box::pin(async {}))),
+        );
+        drop(registry);
+        drop(guard);
+    }
+}
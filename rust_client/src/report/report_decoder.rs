@@ -0,0 +1,103 @@
+// src/report/report_decoder.rs
+use rustdds::no_key::{DataReader, Decode, DefaultDecoder, DeserializerAdapter};
+use rustdds::{CDRDeserializerAdapter, RepresentationIdentifier};
+use serde::de::DeserializeOwned;
+use std::fmt::Write;
+use std::marker::PhantomData;
+
+// Marks the hex payload inside a read error reason
+const PAYLOAD_MARKER: &str = " payload=";
+
+/// Report reader whose decode errors keep the raw sample bytes
+pub type ReportReader<T> = DataReader<T, ReportDeserializerAdapter<T>>;
+
+/// CDR deserializer adapter that puts the undecodable sample bytes in its
+/// error, since RustDDS only passes the error text on to the reader
+pub struct ReportDeserializerAdapter<D>(PhantomData<D>);
+
+impl<D> DeserializerAdapter<D> for ReportDeserializerAdapter<D> {
+    type Error = PayloadDecodeError;
+    type Decoded = D;
+
+    fn supported_encodings() -> &'static [RepresentationIdentifier] {
+        <CDRDeserializerAdapter<D> as DeserializerAdapter<D>>::supported_encodings()
+    }
+
+    fn transform_decoded(decoded: Self::Decoded) -> D {
+        decoded
+    }
+}
+
+impl<D: DeserializeOwned> DefaultDecoder<D> for ReportDeserializerAdapter<D> {
+    type Decoder = ReportDecoder<D>;
+    const DECODER: Self::Decoder = ReportDecoder(PhantomData);
+}
+
+/// CDR decoder behind `ReportDeserializerAdapter`
+pub struct ReportDecoder<D>(PhantomData<D>);
+
+impl<D> Clone for ReportDecoder<D> {
+    fn clone(&self) -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<D: DeserializeOwned> Decode<D> for ReportDecoder<D> {
+    type Error = PayloadDecodeError;
+
+    fn decode_bytes(
+        self,
+        input_bytes: &[u8],
+        encoding: RepresentationIdentifier,
+    ) -> Result<D, Self::Error> {
+        <CDRDeserializerAdapter<D> as DefaultDecoder<D>>::DECODER
+            .decode_bytes(input_bytes, encoding)
+            .map_err(|e| PayloadDecodeError {
+                error: format!("{} ({:?})", e, encoding),
+                payload: input_bytes.to_vec(),
+            })
+    }
+}
+
+/// Decode error carrying the bytes that failed to decode
+#[derive(Debug)]
+pub struct PayloadDecodeError {
+    pub error: String,
+    pub payload: Vec<u8>,
+}
+
+impl std::fmt::Display for PayloadDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            self.error,
+            PAYLOAD_MARKER,
+            to_hex(&self.payload)
+        )
+    }
+}
+
+impl std::error::Error for PayloadDecodeError {}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(hex, "{:02x}", byte);
+    }
+    hex
+}
+
+/// Split a read error reason into its text and the hex payload a
+/// `PayloadDecodeError` put in it, if any
+pub(crate) fn split_payload(reason: &str) -> (String, Option<String>) {
+    let Some(start) = reason.find(PAYLOAD_MARKER) else {
+        return (reason.to_string(), None);
+    };
+    let hex_start = start + PAYLOAD_MARKER.len();
+    let hex_end = reason[hex_start..]
+        .find(|c: char| !c.is_ascii_hexdigit())
+        .map_or(reason.len(), |len| hex_start + len);
+    let error = format!("{}{}", &reason[..start], &reason[hex_end..]);
+    (error, Some(reason[hex_start..hex_end].to_string()))
+}
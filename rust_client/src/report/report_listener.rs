@@ -0,0 +1,691 @@
+use crate::dead_letter::{DeadLetterCounts, DeadLetterQueue, DeadLetterSink};
+use crate::gap_fill::{GapFiller, InboundMessage};
+use crate::journal::OrderJournal;
+use crate::order_handle::OrderUpdate;
+use crate::order_state::{OrderLifecycle, TransitionPolicy};
+use crate::report::report_callback::{
+    Callback, CallbackGuard, CallbackRegistry, DEFAULT_CALLBACK_PRIORITY,
+};
+use crate::report::report_decoder::{ReportDeserializerAdapter, ReportReader};
+use crate::report::report_ordering::{ReportOrdering, StaleReportPolicy};
+use crate::report::report_stream::{ReportFilter, ReportStream};
+use crate::report::status_cache::{ArchivedOrder, CacheRetention, StatusCache, StatusPage};
+use crate::supervisor::CancellationToken;
+use crate::utils::panics::{catch_unwind, catch_unwind_async};
+use anyhow::{bail, Result};
+use futures::{FutureExt, StreamExt};
+use log::{debug, error, info, warn};
+use rustdds::dds::ReadError;
+use rustdds::no_key::{DataReaderEventStream, DataReaderStream};
+use rustdds::DataReaderStatus;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use tokio::runtime::Handle;
+use tokio::sync::{broadcast, Mutex, Semaphore};
+
+const DEFAULT_DOMAIN_ID: u16 = 0;
+// Reports buffered per subscriber before a slow one starts lagging
+const DEFAULT_STREAM_CAPACITY: usize = 1024;
+// Reports copied per lock when iterating the status cache
+const STATUS_PAGE_SIZE: usize = 256;
+// Async callbacks running at once before the reader waits for one to finish
+const DEFAULT_CALLBACK_CONCURRENCY: usize = 64;
+
+/// Inbound DDS message a `ReportListener` can read, cache and dispatch
+pub trait ReportMessage: Clone + Send + Sync + Serialize + DeserializeOwned + 'static {
+    /// DDS topic name matching the C++ configuration
+    const TOPIC_NAME: &'static str;
+    /// Type name exactly matching the C++ IDL namespace
+    const TYPE_NAME: &'static str;
+    /// Message name used in logs
+    const NAME: &'static str;
+
+    /// Keys the latest report is cached under, e.g. OrderID and OrigClOrdID
+    fn cache_keys(&self) -> Vec<String>;
+
+    /// Fields logged for the audit trail of every received report
+    fn audit_fields(&self) -> String;
+
+    /// Normalized update for messages that move an order through its lifecycle
+    fn order_update(&self) -> Option<OrderUpdate> {
+        None
+    }
+
+    /// Routing and MsgSeqNum for inbound gap detection, for messages with a FIX header
+    fn inbound(&self) -> Option<InboundMessage> {
+        None
+    }
+
+    /// Flag a report delivered under `StaleReportPolicy::Deliver`
+    fn mark_stale(&mut self) {}
+
+    /// Whether the report is older than one already processed for its order
+    fn is_stale(&self) -> bool {
+        false
+    }
+}
+
+/// Listener for one report topic: logs, caches the latest report per order,
+/// tracks order lifecycles and invokes registered callbacks
+pub struct ReportListener<T: ReportMessage> {
+    // Async sample stream woken by the DDS background thread as samples arrive
+    samples: Mutex<DataReaderStream<T, ReportDeserializerAdapter<T>>>,
+    // Reader status events and the publications currently matched to the reader
+    status_events: std::sync::Mutex<DataReaderEventStream<T, ReportDeserializerAdapter<T>>>,
+    matched_publications: AtomicUsize,
+    // Latest report, lifecycle and ExecIDs per order, bounded by its retention
+    status_cache: RwLock<StatusCache<T>>,
+    // Where orders leaving the cache are archived
+    journal: Option<Arc<OrderJournal>>,
+    transition_policy: TransitionPolicy,
+    stale_policy: StaleReportPolicy,
+    gap_filler: Option<Arc<GapFiller>>,
+    callbacks: CallbackRegistry<T>,
+    // Runtime async callbacks are spawned on, the reader's when None
+    callback_runtime: Option<Handle>,
+    callback_permits: Arc<Semaphore>,
+    subscribers: broadcast::Sender<T>,
+    // Undecodable samples and reports callbacks panicked on
+    dead_letters: Arc<DeadLetterSink>,
+}
+
+impl<T: ReportMessage> ReportListener<T> {
+    pub fn new(reader: ReportReader<T>) -> Self {
+        info!(
+            "🔧 Initializing {} listener on topic '{}'",
+            T::NAME,
+            T::TOPIC_NAME
+        );
+
+        let samples = reader.async_sample_stream();
+        Self {
+            status_events: std::sync::Mutex::new(samples.async_event_stream()),
+            matched_publications: AtomicUsize::new(0),
+            samples: Mutex::new(samples),
+            status_cache: RwLock::new(StatusCache::new(CacheRetention::default())),
+            journal: None,
+            transition_policy: TransitionPolicy::default(),
+            stale_policy: StaleReportPolicy::default(),
+            gap_filler: None,
+            callbacks: CallbackRegistry::new(),
+            callback_runtime: None,
+            callback_permits: Arc::new(Semaphore::new(DEFAULT_CALLBACK_CONCURRENCY)),
+            subscribers: broadcast::channel(DEFAULT_STREAM_CAPACITY).0,
+            dead_letters: Arc::new(DeadLetterSink::new(T::NAME, T::TOPIC_NAME, None)),
+        }
+    }
+
+    /// Reports each subscriber may fall behind by before it lags; call before `subscribe`
+    pub fn with_stream_capacity(mut self, capacity: usize) -> Self {
+        self.subscribers = broadcast::channel(capacity).0;
+        self
+    }
+
+    /// Spawn async callbacks on `runtime` instead of the runtime reading reports
+    pub fn with_callback_runtime(mut self, runtime: Handle) -> Self {
+        self.callback_runtime = Some(runtime);
+        self
+    }
+
+    /// Maximum async callbacks in flight; reading pauses while all are busy
+    pub fn with_callback_concurrency(mut self, limit: usize) -> Self {
+        self.callback_permits = Arc::new(Semaphore::new(limit.max(1)));
+        self
+    }
+
+    /// Choose whether illegal transitions are discarded or applied and flagged
+    pub fn with_transition_policy(mut self, policy: TransitionPolicy) -> Self {
+        self.transition_policy = policy;
+        self
+    }
+
+    /// Choose what happens to reports older than one already processed
+    pub fn with_stale_policy(mut self, policy: StaleReportPolicy) -> Self {
+        self.stale_policy = policy;
+        self
+    }
+
+    /// Bound the status cache; call before reports arrive
+    pub fn with_retention(mut self, retention: CacheRetention) -> Self {
+        self.status_cache = RwLock::new(StatusCache::new(retention));
+        self
+    }
+
+    /// Archive orders evicted or expired from the status cache
+    pub fn with_journal(mut self, journal: Arc<OrderJournal>) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Write undecodable samples and reports callbacks panicked on to `queue`
+    pub fn with_dead_letters(mut self, queue: Arc<DeadLetterQueue>) -> Self {
+        self.dead_letters = Arc::new(DeadLetterSink::new(T::NAME, T::TOPIC_NAME, Some(queue)));
+        self
+    }
+
+    /// Check inbound MsgSeqNums for gaps; share one filler between listeners
+    /// whose messages come from the same sessions
+    pub fn with_gap_filler(mut self, gap_filler: Arc<GapFiller>) -> Self {
+        self.gap_filler = Some(gap_filler);
+        self
+    }
+
+    /// Process every report already available on the topic without waiting,
+    /// returning how many were read. Returns 0 while `run` owns the stream,
+    /// since it processes reports as soon as they arrive.
+    pub async fn poll_once(&self) -> Result<usize> {
+        let Ok(mut samples) = self.samples.try_lock() else {
+            return Ok(0);
+        };
+
+        Ok(self.drain(&mut samples).await)
+    }
+
+    /// Process every report already available on the stream, returning how many
+    async fn drain(
+        &self,
+        samples: &mut DataReaderStream<T, ReportDeserializerAdapter<T>>,
+    ) -> usize {
+        let mut processed = 0usize;
+        while let Some(Some(result)) = samples.next().now_or_never() {
+            match result {
+                Ok(sample) => {
+                    self.process(sample.into_value()).await;
+                    processed += 1;
+                }
+                Err(e) => self.read_error(e),
+            }
+        }
+        processed
+    }
+
+    /// Process reports the moment they arrive until the task is dropped
+    pub async fn run(&self) {
+        if let Err(e) = self.run_until(CancellationToken::new()).await {
+            warn!("⚠️ {:#}", e);
+        }
+    }
+
+    /// Process reports the moment they arrive until `cancel` fires, then
+    /// process those already received and return. Fails if the sample
+    /// stream ends first.
+    pub async fn run_until(&self, cancel: CancellationToken) -> Result<()> {
+        info!("🎧 Starting continuous {} listening...", T::NAME);
+        let mut samples = self.samples.lock().await;
+        loop {
+            tokio::select! {
+                biased;
+                _ = cancel.cancelled() => break,
+                result = samples.next() => match result {
+                    Some(Ok(sample)) => self.process(sample.into_value()).await,
+                    Some(Err(e)) => self.read_error(e),
+                    None => bail!("{} sample stream ended", T::NAME),
+                },
+            }
+        }
+
+        let drained = self.drain(&mut samples).await;
+        info!(
+            "🛑 {} listener stopped, {} pending reports drained",
+            T::NAME,
+            drained
+        );
+        Ok(())
+    }
+
+    /// Dead-letter samples that failed to decode and keep reading
+    fn read_error(&self, e: ReadError) {
+        match e {
+            ReadError::Deserialization { reason } => self.dead_letters.undecodable(&reason),
+            e => error!("❌ Error reading {}: {}", T::NAME, e),
+        }
+    }
+
+    async fn process(&self, mut report: T) {
+        info!(
+            "📋 {} AUDIT: Topic='{}', {}",
+            T::NAME,
+            T::TOPIC_NAME,
+            report.audit_fields()
+        );
+
+        // Sequence numbers count every message, including ones dropped below
+        if let (Some(gap_filler), Some(inbound)) = (&self.gap_filler, report.inbound()) {
+            gap_filler.on_inbound(&inbound);
+        }
+
+        match self.check_ordering(&report) {
+            ReportOrdering::Fresh => {}
+            ReportOrdering::Duplicate => {
+                debug!(
+                    "🔁 Duplicate {} ignored: {}",
+                    T::NAME,
+                    report.audit_fields()
+                );
+                return;
+            }
+            ReportOrdering::Stale(reason) => match self.stale_policy {
+                StaleReportPolicy::Drop => {
+                    debug!("⏪ Stale {} dropped: {}", T::NAME, reason);
+                    return;
+                }
+                StaleReportPolicy::Log => {
+                    warn!("⚠️ Stale {} dropped: {}", T::NAME, reason);
+                    return;
+                }
+                StaleReportPolicy::Deliver => {
+                    warn!("⚠️ Stale {} delivered flagged: {}", T::NAME, reason);
+                    report.mark_stale();
+                    self.dispatch(&report).await;
+                    self.publish(report);
+                    return;
+                }
+            },
+        }
+
+        self.update_cache(&report);
+        self.dispatch(&report).await;
+        self.publish(report);
+    }
+
+    /// Hand a processed report to the stream subscribers, if any
+    fn publish(&self, report: T) {
+        if self.subscribers.receiver_count() > 0 {
+            let _ = self.subscribers.send(report);
+        }
+    }
+
+    /// Run callbacks in priority order: sync ones inline, async ones spawned
+    /// once a concurrency permit is free. A panicking callback dead-letters
+    /// the report without stopping the others.
+    async fn dispatch(&self, report: &T) {
+        for callback in self.callbacks.snapshot() {
+            match callback {
+                Callback::Sync(callback) => {
+                    if let Err(panic) = catch_unwind(|| callback(report)) {
+                        self.dead_letters.callback_panic(report, panic);
+                    }
+                }
+                Callback::Async(callback) => {
+                    let Ok(permit) = Arc::clone(&self.callback_permits).acquire_owned().await
+                    else {
+                        continue;
+                    };
+                    let task = match catch_unwind(|| callback(report.clone())) {
+                        Ok(task) => task,
+                        Err(panic) => {
+                            self.dead_letters.callback_panic(report, panic);
+                            continue;
+                        }
+                    };
+                    let dead_letters = Arc::clone(&self.dead_letters);
+                    let report = report.clone();
+                    let task = async move {
+                        if let Err(panic) = catch_unwind_async(task).await {
+                            dead_letters.callback_panic(&report, panic);
+                        }
+                        drop(permit);
+                    };
+                    match &self.callback_runtime {
+                        Some(runtime) => runtime.spawn(task),
+                        None => tokio::spawn(task),
+                    };
+                }
+            }
+        }
+    }
+
+    fn check_ordering(&self, report: &T) -> ReportOrdering {
+        self.status_cache.write().unwrap().check_ordering(report)
+    }
+
+    fn update_cache(&self, report: &T) {
+        let archived = self
+            .status_cache
+            .write()
+            .unwrap()
+            .update(report, self.transition_policy);
+        self.archive(archived);
+    }
+
+    fn archive(&self, archived: Vec<ArchivedOrder<T>>) {
+        for order in archived {
+            debug!(
+                "🗄️ {} for order {} leaves the cache ({:?})",
+                T::NAME,
+                order.order_key,
+                order.reason
+            );
+            if let Some(journal) = &self.journal {
+                if let Err(e) = journal.append(&order) {
+                    error!(
+                        "❌ Failed to archive order {} to the journal: {:#}",
+                        order.order_key, e
+                    );
+                }
+            }
+        }
+    }
+
+    /// Archive orders terminal for longer than the TTL, returning how many
+    pub fn evict_expired(&self) -> usize {
+        let archived = self.status_cache.write().unwrap().evict_expired();
+        let count = archived.len();
+        self.archive(archived);
+        count
+    }
+
+    /// Latest report cached under an OrderID or ClOrdID
+    pub fn get_order_status(&self, order_id: &str) -> Option<T> {
+        self.status_cache.read().unwrap().get(order_id)
+    }
+
+    /// Get the lifecycle (state, quantities and applied reports) of an order
+    pub fn get_order_lifecycle(&self, order_id: &str) -> Option<OrderLifecycle> {
+        self.status_cache.read().unwrap().lifecycle(order_id)
+    }
+
+    /// Up to `limit` cached reports by order key, starting after the key `after`
+    pub fn order_statuses_page(&self, after: Option<&str>, limit: usize) -> StatusPage<T> {
+        self.status_cache.read().unwrap().page(after, limit)
+    }
+
+    /// Iterate over cached reports by order key, copying one page at a time
+    pub fn order_statuses(&self) -> impl Iterator<Item = (String, T)> + '_ {
+        let mut page = self.order_statuses_page(None, STATUS_PAGE_SIZE);
+        let mut items = std::mem::take(&mut page.items).into_iter();
+        std::iter::from_fn(move || loop {
+            if let Some(item) = items.next() {
+                return Some(item);
+            }
+            let after = page.next.take()?;
+            page = self.order_statuses_page(Some(&after), STATUS_PAGE_SIZE);
+            items = std::mem::take(&mut page.items).into_iter();
+        })
+    }
+
+    /// Orders currently cached
+    pub fn cached_orders(&self) -> usize {
+        self.status_cache.read().unwrap().len()
+    }
+
+    /// Register a callback invoked for every report read from now on
+    pub fn register_callback<F>(&self, callback: F) -> CallbackGuard
+    where
+        F: Fn(&T) + Send + Sync + 'static,
+    {
+        self.register_callback_with_priority(DEFAULT_CALLBACK_PRIORITY, callback)
+    }
+
+    /// Register a callback that runs before those of lower priority
+    pub fn register_callback_with_priority<F>(&self, priority: i32, callback: F) -> CallbackGuard
+    where
+        F: Fn(&T) + Send + Sync + 'static,
+    {
+        info!(
+            "✅ Registered new {} callback (priority {})",
+            T::NAME,
+            priority
+        );
+        self.callbacks
+            .register(priority, Callback::Sync(Arc::new(callback)))
+    }
+
+    /// Register a callback whose future is spawned for every report read from now on
+    pub fn register_async_callback<F, Fut>(&self, callback: F) -> CallbackGuard
+    where
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.register_async_callback_with_priority(DEFAULT_CALLBACK_PRIORITY, callback)
+    }
+
+    /// Register an async callback that is started before those of lower priority
+    pub fn register_async_callback_with_priority<F, Fut>(
+        &self,
+        priority: i32,
+        callback: F,
+    ) -> CallbackGuard
+    where
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        info!(
+            "✅ Registered new async {} callback (priority {})",
+            T::NAME,
+            priority
+        );
+        self.callbacks.register(
+            priority,
+            Callback::Async(Arc::new(move |report| Box::pin(callback(report)))),
+        )
+    }
+
+    /// Number of registered callbacks
+    pub fn callback_count(&self) -> usize {
+        self.callbacks.len()
+    }
+
+    /// Stream of every report read from now on
+    pub fn subscribe(&self) -> ReportStream<T> {
+        ReportStream::new(self.subscribers.subscribe(), T::NAME, None)
+    }
+
+    /// Stream of the reports read from now on that match `filter`
+    pub fn subscribe_filtered<F>(&self, filter: F) -> ReportStream<T>
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        let filter: ReportFilter<T> = Box::new(filter);
+        ReportStream::new(self.subscribers.subscribe(), T::NAME, Some(filter))
+    }
+
+    /// Number of live `ReportStream`s
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.receiver_count()
+    }
+
+    /// Samples dead-lettered so far
+    pub fn dead_letter_counts(&self) -> DeadLetterCounts {
+        self.dead_letters.counts()
+    }
+
+    /// Publications currently matched to the reader, from the SubscriptionMatched
+    /// status events received so far
+    pub fn matched_publications(&self) -> usize {
+        let mut events = self.status_events.lock().unwrap();
+        while let Some(Some(status)) = events.next().now_or_never() {
+            if let DataReaderStatus::SubscriptionMatched { current, .. } = status {
+                let count = current.count().max(0) as usize;
+                debug!("🔗 {} reader matched {} publications", T::NAME, count);
+                self.matched_publications.store(count, Ordering::Relaxed);
+            }
+        }
+        self.matched_publications.load(Ordering::Relaxed)
+    }
+
+    pub fn get_connection_status(&self) -> ReportListenerStatus {
+        let matched_publications = self.matched_publications();
+        ReportListenerStatus {
+            domain_id: DEFAULT_DOMAIN_ID,
+            message_name: T::NAME,
+            topic_name: T::TOPIC_NAME,
+            type_name: T::TYPE_NAME,
+            publisher_count: matched_publications,
+            is_connected: matched_publications > 0,
+            tracked_orders: self.cached_orders(),
+            subscribers: self.subscriber_count(),
+            dead_letters: self.dead_letter_counts(),
+        }
+    }
+
+    pub async fn shutdown(&self) -> Result<()> {
+        info!("🔧 Initiating graceful shutdown of {} listener...", T::NAME);
+
+        // Process what already arrived before the reader goes away
+        self.poll_once().await?;
+
+        info!("✅ {} listener shutdown complete", T::NAME);
+        Ok(())
+    }
+}
+
+/// Connection status of a report listener for monitoring
+#[derive(Debug)]
+pub struct ReportListenerStatus {
+    pub domain_id: u16,
+    pub message_name: &'static str,
+    pub topic_name: &'static str,
+    pub type_name: &'static str,
+    pub publisher_count: usize,
+    pub is_connected: bool,
+    pub tracked_orders: usize,
+    pub subscribers: usize,
+    pub dead_letters: DeadLetterCounts,
+}
+
+impl std::fmt::Display for ReportListenerStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "🔗 {} Listener Status | Domain: {}\n\
+             📡 Topic: '{}' -> '{}' - {} ({} publishers)\n\
+             📊 Tracked Orders: {} | Subscribers: {}\n\
+             ☠️ Dead Letters: {} undecodable, {} callback panics",
+            self.message_name,
+            self.domain_id,
+            self.topic_name,
+            self.type_name,
+            if self.is_connected {
+                "✅ CONNECTED"
+            } else {
+                "❌ DISCONNECTED"
+            },
+            self.publisher_count,
+            self.tracked_orders,
+            self.subscribers,
+            self.dead_letters.undecodable,
+            self.dead_letters.callback_panics
+        )
+    }
+}
+
+/// RAII resource management for report listeners following OMS guidelines
+impl<T: ReportMessage> Drop for ReportListener<T> {
+    fn drop(&mut self) {
+        // RustDDS handles cleanup automatically through its Drop implementations
+        info!("✅ {} listener resources released", T::NAME);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::ExecutionReport;
+    use rustdds::{
+        policy, CDRSerializerAdapter, DomainParticipant, QosPolicies, QosPolicyBuilder, TopicKind,
+    };
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Duration;
+
+    // Domain of its own so the test does not see a running OMS
+    const TEST_DOMAIN_ID: u16 = 97;
+
+    fn report(exec_id: &str, ord_status: u8, cum_qty: i32) -> ExecutionReport {
+        ExecutionReport {
+            order_id: "ORD-1".to_string(),
+            orig_cl_ord_id: "CL-1".to_string(),
+            exec_id: exec_id.to_string(),
+            exec_type: ord_status,
+            ord_status,
+            order_qty: 10,
+            cum_qty,
+            leaves_qty: 10 - cum_qty,
+            ..ExecutionReport::default()
+        }
+    }
+
+    /// Poll until `processed` reports were read or the wait runs out
+    async fn poll_until<T: ReportMessage>(listener: &ReportListener<T>, processed: usize) -> usize {
+        let mut total = 0;
+        for _ in 0..100 {
+            total += listener.poll_once().await.unwrap();
+            if total >= processed {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        total
+    }
+
+    #[tokio::test]
+    async fn listener_caches_dispatches_and_skips_duplicates() {
+        let participant = DomainParticipant::new(TEST_DOMAIN_ID).unwrap();
+        // Samples written before the endpoints match are kept for the reader
+        let qos: QosPolicies = QosPolicyBuilder::new()
+            .durability(policy::Durability::TransientLocal)
+            .history(policy::History::KeepAll)
+            .reliability(policy::Reliability::Reliable {
+                max_blocking_time: rustdds::Duration::ZERO,
+            })
+            .build();
+        let topic = participant
+            .create_topic(
+                ExecutionReport::TOPIC_NAME.to_string(),
+                ExecutionReport::TYPE_NAME.to_string(),
+                &qos,
+                TopicKind::NoKey,
+            )
+            .unwrap();
+        let reader = participant
+            .create_subscriber(&qos)
+            .unwrap()
+            .create_datareader_no_key::<ExecutionReport, ReportDeserializerAdapter<ExecutionReport>>(
+                &topic, None,
+            )
+            .unwrap();
+        let writer = participant
+            .create_publisher(&qos)
+            .unwrap()
+            .create_datawriter_no_key::<ExecutionReport, CDRSerializerAdapter<ExecutionReport>>(
+                &topic, None,
+            )
+            .unwrap();
+
+        let listener = ReportListener::new(reader);
+        let calls = Arc::new(AtomicUsize::new(0));
+        let _guard = listener.register_callback({
+            let calls = Arc::clone(&calls);
+            move |_| {
+                calls.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        let mut stream = listener.subscribe();
+        for _ in 0..100 {
+            if listener.matched_publications() > 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        writer.write(report("E1", b'0', 0), None).unwrap();
+        writer.write(report("E1", b'0', 0), None).unwrap();
+        writer.write(report("E2", b'1', 4), None).unwrap();
+        assert_eq!(poll_until(&listener, 3).await, 3);
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(listener.get_order_status("CL-1").unwrap().exec_id, "E2");
+        assert_eq!(listener.get_order_status("ORD-1").unwrap().cum_qty, 4);
+        assert_eq!(listener.cached_orders(), 1);
+        assert_eq!(stream.next().await.unwrap().unwrap().exec_id, "E1");
+        assert_eq!(stream.next().await.unwrap().unwrap().exec_id, "E2");
+
+        let status = listener.get_connection_status();
+        assert_eq!(status.publisher_count, 1);
+        assert!(status.is_connected);
+        assert_eq!((status.tracked_orders, status.subscribers), (1, 1));
+    }
+}
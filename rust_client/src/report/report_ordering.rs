@@ -0,0 +1,135 @@
+// src/report/report_ordering.rs
+use crate::order_handle::OrderUpdate;
+use std::collections::HashSet;
+
+/// What a listener does with a report older than one it already processed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StaleReportPolicy {
+    /// Discard it silently
+    Drop,
+    /// Discard it with a warning
+    #[default]
+    Log,
+    /// Run callbacks and streams with the report flagged stale, without
+    /// touching the status cache
+    Deliver,
+}
+
+/// How a report relates to those already processed for its order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReportOrdering {
+    Fresh,
+    /// ExecID already seen for this order
+    Duplicate,
+    /// Older than the latest report, with the reason
+    Stale(String),
+}
+
+/// ExecIDs and high-water marks of one order's reports
+#[derive(Debug, Clone, Default)]
+pub(crate) struct OrderReportOrdering {
+    exec_ids: HashSet<String>,
+    transact_time: u64,
+    cum_qty: i32,
+}
+
+impl OrderReportOrdering {
+    /// Classify a report and remember its ExecID; only fresh reports move the
+    /// TransactTime and CumQty marks
+    pub(crate) fn check(&mut self, update: &OrderUpdate) -> ReportOrdering {
+        if !update.exec_id.is_empty() && !self.exec_ids.insert(update.exec_id.clone()) {
+            return ReportOrdering::Duplicate;
+        }
+
+        // TransactTime 0 means the sender did not set it
+        if update.transact_time != 0 && update.transact_time < self.transact_time {
+            return ReportOrdering::Stale(format!(
+                "TransactTime {} is before {}",
+                update.transact_time, self.transact_time
+            ));
+        }
+        if update.cum_qty < self.cum_qty {
+            return ReportOrdering::Stale(format!(
+                "CumQty {} is below {}",
+                update.cum_qty, self.cum_qty
+            ));
+        }
+
+        self.transact_time = self.transact_time.max(update.transact_time);
+        self.cum_qty = update.cum_qty;
+        ReportOrdering::Fresh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(exec_id: &str, transact_time: u64, cum_qty: i32) -> OrderUpdate {
+        OrderUpdate {
+            order_id: "ORD-1".to_string(),
+            orig_cl_ord_id: "CL-1".to_string(),
+            exec_id: exec_id.to_string(),
+            exec_type: b'F',
+            ord_status: b'1',
+            ord_rej_reason: 0,
+            user_id: "rust_trader_001".to_string(),
+            symbol: "BTC-USD".to_string(),
+            side: b'1',
+            order_qty: 10,
+            last_qty: 0,
+            last_px: 0.0,
+            leaves_qty: 10 - cum_qty,
+            cum_qty,
+            avg_px: 0.0,
+            transact_time,
+            text: String::new(),
+        }
+    }
+
+    #[test]
+    fn repeated_exec_id_is_a_duplicate() {
+        let mut ordering = OrderReportOrdering::default();
+        assert_eq!(ordering.check(&update("E1", 100, 0)), ReportOrdering::Fresh);
+        assert_eq!(
+            ordering.check(&update("E1", 100, 0)),
+            ReportOrdering::Duplicate
+        );
+
+        // Reports without an ExecID can't be deduplicated
+        assert_eq!(ordering.check(&update("", 100, 0)), ReportOrdering::Fresh);
+        assert_eq!(ordering.check(&update("", 100, 0)), ReportOrdering::Fresh);
+    }
+
+    #[test]
+    fn older_transact_time_or_lower_cum_qty_is_stale() {
+        let mut ordering = OrderReportOrdering::default();
+        assert_eq!(ordering.check(&update("E1", 200, 4)), ReportOrdering::Fresh);
+
+        assert!(matches!(
+            ordering.check(&update("E2", 100, 6)),
+            ReportOrdering::Stale(reason) if reason.contains("TransactTime 100")
+        ));
+        assert!(matches!(
+            ordering.check(&update("E3", 300, 2)),
+            ReportOrdering::Stale(reason) if reason.contains("CumQty 2")
+        ));
+        // A stale report's ExecID is remembered, its marks are not
+        assert_eq!(
+            ordering.check(&update("E2", 300, 6)),
+            ReportOrdering::Duplicate
+        );
+        assert_eq!(ordering.check(&update("E4", 200, 4)), ReportOrdering::Fresh);
+    }
+
+    #[test]
+    fn unset_transact_time_is_not_stale() {
+        let mut ordering = OrderReportOrdering::default();
+        assert_eq!(ordering.check(&update("E1", 200, 4)), ReportOrdering::Fresh);
+        assert_eq!(ordering.check(&update("E2", 0, 6)), ReportOrdering::Fresh);
+        assert!(matches!(
+            ordering.check(&update("E3", 150, 8)),
+            ReportOrdering::Stale(_)
+        ));
+    }
+}
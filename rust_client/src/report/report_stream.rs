@@ -0,0 +1,107 @@
+// src/report/report_stream.rs
+use futures::stream::{self, BoxStream};
+use futures::Stream;
+use log::warn;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+
+/// Predicate selecting which reports a subscription receives
+pub type ReportFilter<T> = Box<dyn Fn(&T) -> bool + Send + Sync>;
+
+/// Reports a slow subscriber missed because the channel overflowed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReportLagged(pub u64);
+
+impl std::fmt::Display for ReportLagged {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "subscriber lagged behind and missed {} reports", self.0)
+    }
+}
+
+impl std::error::Error for ReportLagged {}
+
+/// Reports received by a listener after `subscribe`, in order of receipt.
+/// Yields `Err(ReportLagged)` once for every gap a slow consumer caused, then
+/// carries on with the oldest report still buffered. Dropping the stream
+/// unsubscribes it.
+pub struct ReportStream<T> {
+    inner: BoxStream<'static, Result<T, ReportLagged>>,
+}
+
+impl<T: Clone + Send + 'static> ReportStream<T> {
+    pub(crate) fn new(
+        receiver: broadcast::Receiver<T>,
+        name: &'static str,
+        filter: Option<ReportFilter<T>>,
+    ) -> Self {
+        let inner = stream::unfold(
+            (receiver, filter),
+            move |(mut receiver, filter)| async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(report) => {
+                            if filter.as_ref().is_some_and(|keep| !keep(&report)) {
+                                continue;
+                            }
+                            return Some((Ok(report), (receiver, filter)));
+                        }
+                        Err(RecvError::Lagged(missed)) => {
+                            warn!("⚠️ {} subscriber lagged, {} reports dropped", name, missed);
+                            return Some((Err(ReportLagged(missed)), (receiver, filter)));
+                        }
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            },
+        );
+
+        Self {
+            inner: Box::pin(inner),
+        }
+    }
+}
+
+impl<T> Stream for ReportStream<T> {
+    type Item = Result<T, ReportLagged>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+
+    #[tokio::test]
+    async fn stream_yields_filtered_reports_in_order_until_closed() {
+        let (sender, receiver) = broadcast::channel(8);
+        let even: ReportFilter<i32> = Box::new(|report| report % 2 == 0);
+        let stream = ReportStream::new(receiver, "Test", Some(even));
+
+        for report in 1..=5 {
+            sender.send(report).unwrap();
+        }
+        drop(sender);
+
+        let received: Vec<_> = stream.collect().await;
+        assert_eq!(received, vec![Ok(2), Ok(4)]);
+    }
+
+    #[tokio::test]
+    async fn lagging_subscriber_reports_the_gap_once_then_carries_on() {
+        let (sender, receiver) = broadcast::channel(2);
+        let mut stream = ReportStream::new(receiver, "Test", None);
+
+        for report in 1..=5 {
+            sender.send(report).unwrap();
+        }
+
+        assert_eq!(stream.next().await, Some(Err(ReportLagged(3))));
+        assert_eq!(stream.next().await, Some(Ok(4)));
+        assert_eq!(stream.next().await, Some(Ok(5)));
+    }
+}
@@ -0,0 +1,451 @@
+// src/report/status_cache.rs
+use crate::order_state::{OrderLifecycle, TransitionPolicy};
+use crate::report::report_ordering::{OrderReportOrdering, ReportOrdering};
+use crate::report::ReportMessage;
+use chrono::{DateTime, Utc};
+use log::warn;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::ops::Bound;
+use std::time::{Duration, Instant};
+
+/// How many orders a listener keeps in memory and for how long
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheRetention {
+    /// Orders kept before the least recently updated one is evicted
+    pub max_entries: usize,
+    /// How long an order stays cached after reaching a terminal state
+    pub terminal_ttl: Duration,
+}
+
+impl Default for CacheRetention {
+    fn default() -> Self {
+        Self {
+            max_entries: 50_000,
+            terminal_ttl: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+impl CacheRetention {
+    /// Keep every order for the lifetime of the listener
+    pub fn unbounded() -> Self {
+        Self {
+            max_entries: usize::MAX,
+            terminal_ttl: Duration::MAX,
+        }
+    }
+}
+
+/// Why an order left the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ArchiveReason {
+    /// Terminal for longer than the TTL
+    Expired,
+    /// Least recently updated when the cache was full
+    Evicted,
+}
+
+/// Order dropped from a listener cache, as written to the journal
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchivedOrder<T> {
+    pub archived_at: DateTime<Utc>,
+    pub reason: ArchiveReason,
+    pub message: &'static str,
+    pub order_key: String,
+    pub state: Option<String>,
+    pub cum_qty: i32,
+    pub leaves_qty: i32,
+    pub reports_applied: usize,
+    pub report: T,
+}
+
+/// One page of cached reports ordered by order key
+#[derive(Debug, Clone)]
+pub struct StatusPage<T> {
+    pub items: Vec<(String, T)>,
+    /// Key to pass as `after` for the next page, `None` on the last page
+    pub next: Option<String>,
+}
+
+struct CacheEntry<T> {
+    report: Option<T>,
+    lifecycle: Option<OrderLifecycle>,
+    ordering: OrderReportOrdering,
+    // Other cache keys (OrderID, ClOrdID) resolving to this entry
+    aliases: Vec<String>,
+    touched: u64,
+    // When the order became terminal and its key in `StatusCache::terminal`
+    terminal_at: Option<(u64, Instant)>,
+}
+
+impl<T> CacheEntry<T> {
+    fn new() -> Self {
+        Self {
+            report: None,
+            lifecycle: None,
+            ordering: OrderReportOrdering::default(),
+            aliases: Vec::new(),
+            touched: 0,
+            terminal_at: None,
+        }
+    }
+}
+
+/// Latest report, lifecycle and ExecID history per order, bounded by a
+/// `CacheRetention`
+pub(crate) struct StatusCache<T> {
+    retention: CacheRetention,
+    entries: BTreeMap<String, CacheEntry<T>>,
+    aliases: HashMap<String, String>,
+    // Order keys by last update, oldest first, for eviction
+    by_touch: BTreeMap<u64, String>,
+    // Order keys in the order they became terminal, for expiry
+    terminal: BTreeMap<u64, (Instant, String)>,
+    // ExecIDs and marks of orders that left the cache, so late duplicates and
+    // stale reports are still caught; kept for the TTL, up to `max_entries`
+    tombstones: HashMap<String, (Instant, OrderReportOrdering)>,
+    tombstone_queue: VecDeque<(Instant, String)>,
+    next_touch: u64,
+}
+
+impl<T: ReportMessage> StatusCache<T> {
+    pub(crate) fn new(retention: CacheRetention) -> Self {
+        Self {
+            retention,
+            entries: BTreeMap::new(),
+            aliases: HashMap::new(),
+            by_touch: BTreeMap::new(),
+            terminal: BTreeMap::new(),
+            tombstones: HashMap::new(),
+            tombstone_queue: VecDeque::new(),
+            next_touch: 0,
+        }
+    }
+
+    /// Key an order is cached under: the lifecycle key for order updates,
+    /// otherwise the first non-empty cache key
+    pub(crate) fn order_key(report: &T) -> Option<String> {
+        match report.order_update() {
+            Some(update) => Some(update.order_key().to_string()),
+            None => report.cache_keys().into_iter().find(|k| !k.is_empty()),
+        }
+    }
+
+    fn resolve(&self, key: &str) -> Option<&CacheEntry<T>> {
+        match self.aliases.get(key) {
+            Some(order_key) => self.entries.get(order_key),
+            None => self.entries.get(key),
+        }
+    }
+
+    /// Dedup and ordering check of a report against those seen for its order
+    pub(crate) fn check_ordering(&mut self, report: &T) -> ReportOrdering {
+        let (Some(update), Some(order_key)) = (report.order_update(), Self::order_key(report))
+        else {
+            return ReportOrdering::Fresh;
+        };
+        if !self.entries.contains_key(&order_key) {
+            if let Some((_, ordering)) = self.tombstones.get_mut(&order_key) {
+                return ordering.check(&update);
+            }
+        }
+        self.entry(&order_key).ordering.check(&update)
+    }
+
+    /// Entry of an order, created as the most recently updated one with the
+    /// ordering state of its tombstone, if it left the cache before
+    fn entry(&mut self, order_key: &str) -> &mut CacheEntry<T> {
+        if !self.entries.contains_key(order_key) {
+            let touched = self.next_touch();
+            let mut entry = CacheEntry::new();
+            entry.touched = touched;
+            if let Some((_, ordering)) = self.tombstones.remove(order_key) {
+                entry.ordering = ordering;
+            }
+            self.by_touch.insert(touched, order_key.to_string());
+            self.entries.insert(order_key.to_string(), entry);
+        }
+        self.entries.get_mut(order_key).unwrap()
+    }
+
+    fn next_touch(&mut self) -> u64 {
+        let touched = self.next_touch;
+        self.next_touch += 1;
+        touched
+    }
+
+    /// Apply a report to its order's lifecycle and cache it, returning the
+    /// orders this pushed out of the cache
+    pub(crate) fn update(
+        &mut self,
+        report: &T,
+        transition_policy: TransitionPolicy,
+    ) -> Vec<ArchivedOrder<T>> {
+        let Some(order_key) = Self::order_key(report) else {
+            return Vec::new();
+        };
+        let touched = self.next_touch();
+        self.entry(&order_key);
+        let entry = self.entries.get_mut(&order_key).unwrap();
+
+        if let Some(update) = report.order_update() {
+            let lifecycle = entry
+                .lifecycle
+                .get_or_insert_with(|| OrderLifecycle::new(transition_policy));
+
+            // Never let a late or out-of-order report overwrite a newer state
+            if let Err(e) = lifecycle.apply(&update) {
+                warn!("⚠️ Report not applied to order {}: {}", order_key, e);
+                return self.evict_expired();
+            }
+        }
+
+        entry.report = Some(report.clone());
+        self.by_touch.remove(&entry.touched);
+        entry.touched = touched;
+        self.by_touch.insert(touched, order_key.clone());
+
+        // Informational messages without a lifecycle are final when received
+        let terminal = entry
+            .lifecycle
+            .as_ref()
+            .is_none_or(OrderLifecycle::is_terminal);
+        if terminal && entry.terminal_at.is_none() {
+            let now = Instant::now();
+            entry.terminal_at = Some((touched, now));
+            self.terminal.insert(touched, (now, order_key.clone()));
+        }
+
+        for key in report.cache_keys() {
+            if key.is_empty() || key == order_key {
+                continue;
+            }
+            if !entry.aliases.contains(&key) {
+                entry.aliases.push(key.clone());
+            }
+            self.aliases.insert(key, order_key.clone());
+        }
+
+        let mut archived = self.evict_expired();
+        archived.extend(self.evict_over_capacity());
+        archived
+    }
+
+    /// Drop orders terminal for longer than the TTL, and tombstones as old
+    pub(crate) fn evict_expired(&mut self) -> Vec<ArchivedOrder<T>> {
+        self.prune_tombstones();
+        let mut archived = Vec::new();
+        while let Some(entry) = self.terminal.first_entry() {
+            if entry.get().0.elapsed() < self.retention.terminal_ttl {
+                break;
+            }
+            let (_, order_key) = entry.remove();
+            archived.extend(self.remove(&order_key, ArchiveReason::Expired));
+        }
+        archived
+    }
+
+    fn evict_over_capacity(&mut self) -> Vec<ArchivedOrder<T>> {
+        let mut archived = Vec::new();
+        while self.entries.len() > self.retention.max_entries {
+            let Some((_, order_key)) = self.by_touch.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.entries.get(&order_key) {
+                if entry.terminal_at.is_none() {
+                    warn!(
+                        "⚠️ Status cache full, evicting open order {}; raise max_entries",
+                        order_key
+                    );
+                }
+            }
+            archived.extend(self.remove(&order_key, ArchiveReason::Evicted));
+        }
+        archived
+    }
+
+    fn remove(&mut self, order_key: &str, reason: ArchiveReason) -> Option<ArchivedOrder<T>> {
+        let entry = self.entries.remove(order_key)?;
+        self.bury(order_key, entry.ordering.clone());
+        self.by_touch.remove(&entry.touched);
+        if let Some((terminal_seq, _)) = entry.terminal_at {
+            self.terminal.remove(&terminal_seq);
+        }
+        for alias in &entry.aliases {
+            if self.aliases.get(alias).map(String::as_str) == Some(order_key) {
+                self.aliases.remove(alias);
+            }
+        }
+
+        let report = entry.report?;
+        let lifecycle = entry.lifecycle.as_ref();
+        Some(ArchivedOrder {
+            archived_at: Utc::now(),
+            reason,
+            message: T::NAME,
+            order_key: order_key.to_string(),
+            state: lifecycle
+                .and_then(OrderLifecycle::state)
+                .map(|s| s.to_string()),
+            cum_qty: lifecycle.map_or(0, OrderLifecycle::cum_qty),
+            leaves_qty: lifecycle.map_or(0, OrderLifecycle::leaves_qty),
+            reports_applied: lifecycle.map_or(0, |l| l.history().len()),
+            report,
+        })
+    }
+
+    /// Keep the ordering state of an order leaving the cache
+    fn bury(&mut self, order_key: &str, ordering: OrderReportOrdering) {
+        let now = Instant::now();
+        self.tombstones
+            .insert(order_key.to_string(), (now, ordering));
+        self.tombstone_queue.push_back((now, order_key.to_string()));
+        self.prune_tombstones();
+    }
+
+    /// Drop tombstones older than the TTL or beyond `max_entries`, oldest first
+    fn prune_tombstones(&mut self) {
+        while let Some((buried_at, order_key)) = self.tombstone_queue.front() {
+            let current = self.tombstones.get(order_key).map(|(at, _)| at) == Some(buried_at);
+            if current
+                && buried_at.elapsed() < self.retention.terminal_ttl
+                && self.tombstone_queue.len() <= self.retention.max_entries
+            {
+                break;
+            }
+            let (_, order_key) = self.tombstone_queue.pop_front().unwrap();
+            if current {
+                self.tombstones.remove(&order_key);
+            }
+        }
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<T> {
+        self.resolve(key)?.report.clone()
+    }
+
+    pub(crate) fn lifecycle(&self, key: &str) -> Option<OrderLifecycle> {
+        self.resolve(key)?.lifecycle.clone()
+    }
+
+    /// Orders with a cached report
+    pub(crate) fn len(&self) -> usize {
+        self.entries.values().filter(|e| e.report.is_some()).count()
+    }
+
+    /// Up to `limit` cached reports with order keys after `after`
+    pub(crate) fn page(&self, after: Option<&str>, limit: usize) -> StatusPage<T> {
+        let start = match after {
+            Some(key) => Bound::Excluded(key),
+            None => Bound::Unbounded,
+        };
+        let mut items = Vec::with_capacity(limit.min(self.entries.len()));
+        let mut next = None;
+        for (key, entry) in self
+            .entries
+            .range::<str, _>((start, Bound::Unbounded))
+            .filter(|(_, e)| e.report.is_some())
+        {
+            if items.len() == limit {
+                next = items.last().map(|(k, _): &(String, T)| k.clone());
+                break;
+            }
+            items.push((key.clone(), entry.report.clone().unwrap()));
+        }
+        StatusPage { items, next }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::ExecutionReport;
+
+    fn report(cl_ord_id: &str, exec_id: &str, ord_status: u8, cum_qty: i32) -> ExecutionReport {
+        ExecutionReport {
+            order_id: format!("ORD-{}", cl_ord_id),
+            orig_cl_ord_id: cl_ord_id.to_string(),
+            exec_id: exec_id.to_string(),
+            exec_type: ord_status,
+            ord_status,
+            order_qty: 10,
+            cum_qty,
+            leaves_qty: 10 - cum_qty,
+            ..ExecutionReport::default()
+        }
+    }
+
+    fn apply(cache: &mut StatusCache<ExecutionReport>, report: &ExecutionReport) -> ReportOrdering {
+        let ordering = cache.check_ordering(report);
+        if ordering == ReportOrdering::Fresh {
+            cache.update(report, TransitionPolicy::default());
+        }
+        ordering
+    }
+
+    fn retention(max_entries: usize, terminal_ttl: Duration) -> CacheRetention {
+        CacheRetention {
+            max_entries,
+            terminal_ttl,
+        }
+    }
+
+    #[test]
+    fn evicts_least_recently_updated_orders() {
+        let mut cache = StatusCache::new(retention(2, Duration::from_secs(60)));
+        apply(&mut cache, &report("CL-1", "E1", b'0', 0));
+        apply(&mut cache, &report("CL-2", "E2", b'0', 0));
+        apply(&mut cache, &report("CL-1", "E3", b'1', 4));
+        let archived = cache.update(&report("CL-3", "E4", b'0', 0), TransitionPolicy::default());
+
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].order_key, "CL-2");
+        assert_eq!(archived[0].reason, ArchiveReason::Evicted);
+        assert!(cache.get("CL-2").is_none());
+        assert_eq!(cache.get("ORD-CL-1").unwrap().cum_qty, 4);
+    }
+
+    #[test]
+    fn evicted_orders_keep_their_exec_ids() {
+        let mut cache = StatusCache::new(retention(1, Duration::from_secs(60)));
+        apply(&mut cache, &report("CL-1", "E1", b'0', 0));
+        apply(&mut cache, &report("CL-1", "E2", b'1', 4));
+        apply(&mut cache, &report("CL-2", "E3", b'0', 0));
+        assert!(cache.get("CL-1").is_none());
+
+        assert_eq!(
+            apply(&mut cache, &report("CL-1", "E2", b'1', 4)),
+            ReportOrdering::Duplicate
+        );
+        assert!(matches!(
+            apply(&mut cache, &report("CL-1", "E4", b'1', 2)),
+            ReportOrdering::Stale(_)
+        ));
+        assert!(cache.get("CL-1").is_none());
+
+        // A fresh report brings the order back with its history
+        assert_eq!(
+            apply(&mut cache, &report("CL-1", "E5", b'2', 10)),
+            ReportOrdering::Fresh
+        );
+        assert_eq!(
+            apply(&mut cache, &report("CL-1", "E2", b'1', 4)),
+            ReportOrdering::Duplicate
+        );
+    }
+
+    #[test]
+    fn expired_orders_and_tombstones_leave_after_the_ttl() {
+        let mut cache = StatusCache::new(retention(10, Duration::ZERO));
+        apply(&mut cache, &report("CL-1", "E1", b'0', 0));
+        let archived = cache.update(&report("CL-1", "E2", b'2', 10), TransitionPolicy::default());
+
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].reason, ArchiveReason::Expired);
+        assert_eq!(archived[0].state.as_deref(), Some("Filled"));
+        cache.evict_expired();
+        assert!(cache.tombstones.is_empty());
+        assert!(cache.tombstone_queue.is_empty());
+    }
+}
@@ -0,0 +1,536 @@
+// src/risk.rs
+use crate::new_order_single::NewOrderSingle;
+use crate::order_handle::OrderUpdate;
+use crate::order_message::{OrderRequest, OrderSide, OrderType};
+use crate::report::{ExecutionReport, OrderResponseReport};
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Mutex, RwLock};
+
+/// User limits matching C++ UserConfig in `config/users.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserConfig {
+    pub user_id: String,
+    pub max_position_size: f64,
+    pub max_daily_volume: f64,
+    pub available_balance: f64,
+    pub margin_requirement: f64,
+    pub is_active: bool,
+}
+
+/// Symbol limits matching C++ SymbolConfig in `config/symbols.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolConfig {
+    pub symbol: String,
+    pub min_quantity: f64,
+    pub max_quantity: f64,
+    pub tick_size: f64,
+    pub margin_rate: f64,
+    pub is_tradeable: bool,
+    pub max_order_value: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserConfigFile {
+    users: Vec<UserConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SymbolConfigFile {
+    symbols: Vec<SymbolConfig>,
+}
+
+/// Fields of an outgoing order that pre-trade risk looks at, for any send path
+#[derive(Debug, Clone)]
+pub struct RiskOrder {
+    pub order_id: String,
+    pub user_id: String,
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub quantity: f64,
+    pub price: f64,
+    pub stop_price: f64,
+}
+
+impl From<&OrderRequest> for RiskOrder {
+    fn from(order: &OrderRequest) -> Self {
+        Self {
+            order_id: order.order_id.clone(),
+            user_id: order.user_id.clone(),
+            symbol: order.symbol.clone(),
+            side: order.side,
+            order_type: order.order_type,
+            quantity: order.quantity,
+            price: order.price,
+            stop_price: order.stop_price,
+        }
+    }
+}
+
+impl From<&NewOrderSingle> for RiskOrder {
+    fn from(order: &NewOrderSingle) -> Self {
+        let order_type = match order.ord_type {
+            b'2' => OrderType::LIMIT,
+            b'3' => OrderType::STOP,
+            b'4' => OrderType::STOP_LIMIT,
+            _ => OrderType::MARKET,
+        };
+        let side = match order.side {
+            b'1' => OrderSide::BUY,
+            _ => OrderSide::SELL,
+        };
+
+        Self {
+            order_id: order.cl_ord_id.clone(),
+            user_id: order.dats_source_user.clone(),
+            symbol: order.symbol.clone(),
+            side,
+            order_type,
+            quantity: order.order_qty as f64,
+            price: order.price as f64,
+            stop_price: order.stop_px as f64,
+        }
+    }
+}
+
+impl RiskOrder {
+    /// Order value exactly as the C++ RiskValidator computes it
+    fn order_value(&self) -> f64 {
+        self.quantity
+            * if self.order_type == OrderType::MARKET {
+                1.0
+            } else {
+                self.price
+            }
+    }
+
+    fn signed_value(&self) -> f64 {
+        match self.side {
+            OrderSide::BUY => self.order_value(),
+            OrderSide::SELL => -self.order_value(),
+        }
+    }
+}
+
+/// Limits reserved by an accepted order until it is filled or finishes unfilled
+#[derive(Debug, Clone)]
+struct Reservation {
+    user_id: String,
+    // Daily volume is only given back on the day it was reserved
+    trading_day: NaiveDate,
+    quantity: f64,
+    signed_value: f64,
+    margin: f64,
+}
+
+/// Running usage of a user's limits during the trading day
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UserUsage {
+    pub daily_volume: f64,
+    /// Net signed order value of working and filled orders
+    pub position_value: f64,
+    pub margin_used: f64,
+}
+
+struct RiskState {
+    trading_day: NaiveDate,
+    usage: HashMap<String, UserUsage>,
+    reservations: HashMap<String, Reservation>,
+}
+
+/// Client-side pre-trade risk checks mirroring the C++ RiskValidator
+pub struct RiskEngine {
+    users: HashMap<String, UserConfig>,
+    /// Order users checked and reserved under another user's limits
+    aliases: RwLock<HashMap<String, String>>,
+    symbols: HashMap<String, SymbolConfig>,
+    state: Mutex<RiskState>,
+}
+
+impl RiskEngine {
+    /// Build an engine from already loaded configurations
+    pub fn new(users: Vec<UserConfig>, symbols: Vec<SymbolConfig>) -> Self {
+        Self {
+            users: users.into_iter().map(|u| (u.user_id.clone(), u)).collect(),
+            aliases: RwLock::new(HashMap::new()),
+            symbols: symbols.into_iter().map(|s| (s.symbol.clone(), s)).collect(),
+            state: Mutex::new(RiskState {
+                trading_day: Utc::now().date_naive(),
+                usage: HashMap::new(),
+                reservations: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Load the same user and symbol files the OMS RiskValidator uses
+    pub fn load<P: AsRef<Path>>(user_config_file: P, symbol_config_file: P) -> Result<Self> {
+        let users = load_user_configs(user_config_file)?;
+
+        let symbol_path = symbol_config_file.as_ref();
+        let symbols: SymbolConfigFile = serde_json::from_str(
+            &std::fs::read_to_string(symbol_path)
+                .with_context(|| format!("Cannot open symbol config file: {:?}", symbol_path))?,
+        )
+        .with_context(|| format!("Error loading symbol configs: {:?}", symbol_path))?;
+
+        info!(
+            "✅ RiskEngine initialized with {} users and {} symbols",
+            users.len(),
+            symbols.symbols.len()
+        );
+        Ok(Self::new(users, symbols.symbols))
+    }
+
+    /// Add client-only accounts the OMS configuration does not know, replacing
+    /// users with the same ID
+    pub fn with_users(mut self, users: Vec<UserConfig>) -> Self {
+        self.users
+            .extend(users.into_iter().map(|u| (u.user_id.clone(), u)));
+        self
+    }
+
+    /// Check and reserve orders of `alias` under the limits and usage of `user_id`,
+    /// e.g. the NewOrderSingle `DATS_SourceUser` of an account keyed by its OMS user
+    pub fn with_alias(self, alias: &str, user_id: &str) -> Self {
+        self.add_alias(alias, user_id);
+        self
+    }
+
+    /// Alias `alias` to `user_id` on a shared engine, e.g. when an account context
+    /// is created. A later alias of the same user replaces the earlier one.
+    pub fn add_alias(&self, alias: &str, user_id: &str) {
+        if alias == user_id {
+            return;
+        }
+        let previous = self
+            .aliases
+            .write()
+            .unwrap()
+            .insert(alias.to_string(), user_id.to_string());
+        if let Some(previous) = previous.filter(|previous| previous != user_id) {
+            warn!(
+                "⚠️ Risk alias {} moved from {} to {}",
+                alias, previous, user_id
+            );
+        }
+    }
+
+    /// User whose limits apply to orders of `user_id`
+    fn risk_user(&self, user_id: &str) -> String {
+        self.aliases
+            .read()
+            .unwrap()
+            .get(user_id)
+            .map_or_else(|| user_id.to_string(), String::clone)
+    }
+
+    /// Validate an order and, when it passes, reserve its usage against the user's limits.
+    /// Returns the OMS rejection reason otherwise. Checks run in the RiskValidator order
+    /// (parameters, user, symbol); the OMS does not check user limits, so they come last.
+    pub fn check_and_reserve(&self, order: &RiskOrder) -> std::result::Result<(), String> {
+        validate_order_parameters(order)?;
+
+        let user_id = self.risk_user(&order.user_id);
+        let user = self
+            .users
+            .get(&user_id)
+            .ok_or_else(|| "User not found or not configured".to_string())?;
+
+        let mut state = self.state.lock().unwrap();
+        let today = Utc::now().date_naive();
+        if state.trading_day != today {
+            info!("🔄 New trading day {}: resetting risk usage", today);
+            state.trading_day = today;
+            for usage in state.usage.values_mut() {
+                usage.daily_volume = 0.0;
+            }
+        }
+
+        let symbol = self
+            .symbols
+            .get(&order.symbol)
+            .ok_or_else(|| "Symbol not found or not configured".to_string())?;
+        validate_symbol_limits(order, symbol)?;
+
+        let usage = state.usage.get(&user_id).cloned().unwrap_or_default();
+        let margin = order.order_value() * user.margin_requirement;
+        validate_user_limits(order, user, &usage, margin)?;
+
+        let trading_day = state.trading_day;
+        let usage = state.usage.entry(user_id.clone()).or_default();
+        usage.daily_volume += order.quantity;
+        usage.position_value += order.signed_value();
+        usage.margin_used += margin;
+        state.reservations.insert(
+            order.order_id.clone(),
+            Reservation {
+                user_id,
+                trading_day,
+                quantity: order.quantity,
+                signed_value: order.signed_value(),
+                margin,
+            },
+        );
+        Ok(())
+    }
+
+    /// Give back the whole reservation of an order that never reached DDS
+    pub fn release(&self, order_id: &str) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(reservation) = state.reservations.remove(order_id) {
+            Self::release_fraction(&mut state, &reservation, 1.0);
+        }
+    }
+
+    fn release_fraction(state: &mut RiskState, reservation: &Reservation, fraction: f64) {
+        let same_day = reservation.trading_day == state.trading_day;
+        if let Some(usage) = state.usage.get_mut(&reservation.user_id) {
+            if same_day {
+                usage.daily_volume -= reservation.quantity * fraction;
+            }
+            usage.position_value -= reservation.signed_value * fraction;
+            usage.margin_used -= reservation.margin * fraction;
+        }
+    }
+
+    /// Release the unfilled part of orders that end Cancelled, Rejected or Expired
+    pub fn on_update(&self, update: &OrderUpdate) {
+        if !update.is_terminal() {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let Some(reservation) = state
+            .reservations
+            .remove(update.order_key())
+            .or_else(|| state.reservations.remove(&update.order_id))
+        else {
+            return;
+        };
+
+        if !update.is_filled() && reservation.quantity > 0.0 {
+            let unfilled = (reservation.quantity - update.cum_qty as f64).max(0.0);
+            Self::release_fraction(&mut state, &reservation, unfilled / reservation.quantity);
+        }
+    }
+
+    /// Track order outcomes from an OrderResponseReport
+    pub fn on_order_response(&self, report: &OrderResponseReport) {
+        self.on_update(&OrderUpdate::from(report));
+    }
+
+    /// Track order outcomes from an ExecutionReport
+    pub fn on_execution_report(&self, report: &ExecutionReport) {
+        self.on_update(&OrderUpdate::from(report));
+    }
+
+    /// Current usage of a user's limits, aliases included
+    pub fn usage(&self, user_id: &str) -> UserUsage {
+        let state = self.state.lock().unwrap();
+        state
+            .usage
+            .get(&self.risk_user(user_id))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Limits applying to a user's orders, following aliases
+    pub fn user_config(&self, user_id: &str) -> Option<&UserConfig> {
+        self.users.get(&self.risk_user(user_id))
+    }
+
+    pub fn symbol_config(&self, symbol: &str) -> Option<&SymbolConfig> {
+        self.symbols.get(symbol)
+    }
+}
+
+/// Load a user limits file in the `config/users.json` format
+pub fn load_user_configs<P: AsRef<Path>>(user_config_file: P) -> Result<Vec<UserConfig>> {
+    let user_path = user_config_file.as_ref();
+    let users: UserConfigFile = serde_json::from_str(
+        &std::fs::read_to_string(user_path)
+            .with_context(|| format!("Cannot open user config file: {:?}", user_path))?,
+    )
+    .with_context(|| format!("Error loading user configs: {:?}", user_path))?;
+    Ok(users.users)
+}
+
+/// Basic order parameter validation, same checks and reasons as the OMS
+pub fn validate_order_parameters(order: &RiskOrder) -> std::result::Result<(), String> {
+    if order.order_id.is_empty() {
+        return Err("Order ID cannot be empty".to_string());
+    }
+
+    if order.symbol.is_empty() {
+        return Err("Symbol cannot be empty".to_string());
+    }
+
+    if order.quantity <= 0.0 {
+        return Err("Quantity must be positive".to_string());
+    }
+
+    if order.order_type == OrderType::LIMIT && order.price <= 0.0 {
+        return Err("Limit orders must have positive price".to_string());
+    }
+
+    if matches!(order.order_type, OrderType::STOP | OrderType::STOP_LIMIT)
+        && order.stop_price <= 0.0
+    {
+        return Err("Stop orders must have positive stop price".to_string());
+    }
+
+    Ok(())
+}
+
+fn validate_user_limits(
+    order: &RiskOrder,
+    user: &UserConfig,
+    usage: &UserUsage,
+    margin: f64,
+) -> std::result::Result<(), String> {
+    if !user.is_active {
+        return Err("User account is inactive".to_string());
+    }
+
+    if (usage.position_value + order.signed_value()).abs() > user.max_position_size {
+        return Err("Order value exceeds maximum position size limit".to_string());
+    }
+
+    if usage.daily_volume + order.quantity > user.max_daily_volume {
+        return Err("Order quantity exceeds daily volume limit".to_string());
+    }
+
+    if usage.margin_used + margin > user.available_balance {
+        return Err("Insufficient margin/balance for order".to_string());
+    }
+
+    Ok(())
+}
+
+fn validate_symbol_limits(
+    order: &RiskOrder,
+    symbol: &SymbolConfig,
+) -> std::result::Result<(), String> {
+    if !symbol.is_tradeable {
+        return Err("Symbol is not tradeable".to_string());
+    }
+
+    if order.quantity < symbol.min_quantity {
+        return Err("Order quantity below minimum allowed".to_string());
+    }
+
+    if order.quantity > symbol.max_quantity {
+        return Err("Order quantity exceeds maximum allowed".to_string());
+    }
+
+    if order.order_value() > symbol.max_order_value {
+        return Err("Order value exceeds maximum allowed for symbol".to_string());
+    }
+
+    // Validate price tick size for limit orders
+    if order.order_type == OrderType::LIMIT && symbol.tick_size > 0.0 {
+        let remainder = order.price % symbol.tick_size;
+        if remainder > 1e-8 {
+            warn!(
+                "⚠️ Price {} of order {} is off tick {}",
+                order.price, order.order_id, symbol.tick_size
+            );
+            return Err("Order price does not conform to tick size".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine(max_daily_volume: f64) -> RiskEngine {
+        RiskEngine::new(
+            vec![UserConfig {
+                user_id: "user001".to_string(),
+                max_position_size: 1_000_000.0,
+                max_daily_volume,
+                available_balance: 1_000_000.0,
+                margin_requirement: 0.0,
+                is_active: true,
+            }],
+            vec![SymbolConfig {
+                symbol: "BTCUSD".to_string(),
+                min_quantity: 1.0,
+                max_quantity: 1000.0,
+                tick_size: 0.0,
+                margin_rate: 0.0,
+                is_tradeable: true,
+                max_order_value: 1_000_000.0,
+            }],
+        )
+    }
+
+    fn order(order_id: &str, user_id: &str, quantity: f64) -> RiskOrder {
+        RiskOrder {
+            order_id: order_id.to_string(),
+            user_id: user_id.to_string(),
+            symbol: "BTCUSD".to_string(),
+            side: OrderSide::BUY,
+            order_type: OrderType::MARKET,
+            quantity,
+            price: 0.0,
+            stop_price: 0.0,
+        }
+    }
+
+    #[test]
+    fn aliased_orders_share_the_user_limits() {
+        let risk = engine(100.0).with_alias("rust_trader_001", "user001");
+
+        risk.check_and_reserve(&order("A", "user001", 60.0))
+            .unwrap();
+        risk.check_and_reserve(&order("B", "rust_trader_001", 30.0))
+            .unwrap();
+        assert_eq!(risk.usage("user001").daily_volume, 90.0);
+        assert_eq!(risk.usage("rust_trader_001"), risk.usage("user001"));
+        assert_eq!(
+            risk.check_and_reserve(&order("C", "rust_trader_001", 20.0)),
+            Err("Order quantity exceeds daily volume limit".to_string())
+        );
+
+        risk.release("B");
+        assert_eq!(risk.usage("user001").daily_volume, 60.0);
+    }
+
+    #[test]
+    fn aliases_can_be_added_to_a_shared_engine() {
+        let risk = std::sync::Arc::new(engine(100.0));
+        assert!(risk
+            .check_and_reserve(&order("A", "desk_trader_002", 10.0))
+            .is_err());
+
+        risk.add_alias("desk_trader_002", "user001");
+        risk.check_and_reserve(&order("B", "desk_trader_002", 10.0))
+            .unwrap();
+        assert_eq!(risk.usage("user001").daily_volume, 10.0);
+        assert_eq!(
+            risk.user_config("desk_trader_002")
+                .map(|user| user.user_id.as_str()),
+            Some("user001")
+        );
+    }
+
+    #[test]
+    fn unknown_users_are_rejected_before_symbols() {
+        let risk = engine(100.0);
+        let mut unknown = order("A", "rust_trader_001", 10.0);
+        unknown.symbol = "ETHUSD".to_string();
+        assert_eq!(
+            risk.check_and_reserve(&unknown),
+            Err("User not found or not configured".to_string())
+        );
+    }
+}
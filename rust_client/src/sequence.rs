@@ -0,0 +1,321 @@
+// src/sequence.rs
+use crate::report::ExecutionReport;
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveTime, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// FIX session identified by our SenderCompID and the counterparty's TargetCompID
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SessionId {
+    pub sender_comp_id: String,
+    pub target_comp_id: String,
+}
+
+impl SessionId {
+    pub fn new(sender_comp_id: &str, target_comp_id: &str) -> Self {
+        Self {
+            sender_comp_id: sender_comp_id.to_string(),
+            target_comp_id: target_comp_id.to_string(),
+        }
+    }
+
+    /// Session of an inbound report, seen from our side
+    pub fn for_inbound(report: &ExecutionReport) -> Self {
+        Self::new(
+            &report.fix_header.target_comp_id,
+            &report.fix_header.sender_comp_id,
+        )
+    }
+
+    fn file_name(&self) -> String {
+        let clean = |s: &str| -> String {
+            s.chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect()
+        };
+        format!(
+            "{}-{}.seq.json",
+            clean(&self.sender_comp_id),
+            clean(&self.target_comp_id)
+        )
+    }
+}
+
+impl std::fmt::Display for SessionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}->{}", self.sender_comp_id, self.target_comp_id)
+    }
+}
+
+/// When sequence numbers start again from 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResetSchedule {
+    /// Only reset explicitly with `SequenceStore::reset`
+    #[default]
+    Never,
+    /// Reset once a day at the given UTC time
+    DailyAt(NaiveTime),
+}
+
+impl ResetSchedule {
+    /// Most recent reset boundary at or before `now`
+    fn last_boundary(self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Never => None,
+            Self::DailyAt(time) => {
+                let today = now.date_naive().and_time(time).and_utc();
+                Some(if today <= now {
+                    today
+                } else {
+                    today - chrono::Duration::days(1)
+                })
+            }
+        }
+    }
+}
+
+/// Sequence numbers of one session, persisted as JSON
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionSequence {
+    pub session: SessionId,
+    /// MsgSeqNum of the next outbound message
+    pub next_outbound: i32,
+    /// MsgSeqNum expected on the next inbound message
+    pub next_inbound: i32,
+    pub last_reset: DateTime<Utc>,
+}
+
+impl SessionSequence {
+    fn new(session: SessionId) -> Self {
+        Self {
+            session,
+            next_outbound: 1,
+            next_inbound: 1,
+            last_reset: Utc::now(),
+        }
+    }
+}
+
+/// Result of checking an inbound MsgSeqNum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InboundSequence {
+    InOrder,
+    /// Messages from `expected` to `received - 1` were not seen
+    Gap {
+        expected: i32,
+        received: i32,
+    },
+    /// Lower than expected, e.g. a PossDup retransmission
+    Duplicate {
+        expected: i32,
+        received: i32,
+    },
+}
+
+/// Missing inbound range detected on a session
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequenceGap {
+    pub session: SessionId,
+    pub expected: i32,
+    pub received: i32,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Outbound and inbound FIX sequence numbers per session
+pub struct SequenceStore {
+    store_dir: Option<PathBuf>,
+    reset_schedule: ResetSchedule,
+    sessions: Mutex<HashMap<SessionId, SessionSequence>>,
+    gaps: Mutex<Vec<SequenceGap>>,
+}
+
+impl Default for SequenceStore {
+    fn default() -> Self {
+        Self::in_memory(ResetSchedule::Never)
+    }
+}
+
+impl SequenceStore {
+    /// Store that starts every session at 1 after a restart
+    pub fn in_memory(reset_schedule: ResetSchedule) -> Self {
+        Self {
+            store_dir: None,
+            reset_schedule,
+            sessions: Mutex::new(HashMap::new()),
+            gaps: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Store persisting one file per session in `store_dir`, loading existing sessions
+    pub fn open<P: AsRef<Path>>(store_dir: P, reset_schedule: ResetSchedule) -> Result<Self> {
+        let store_dir = store_dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&store_dir)
+            .with_context(|| format!("Failed to create sequence store {:?}", store_dir))?;
+
+        let mut sessions = HashMap::new();
+        for entry in std::fs::read_dir(&store_dir)
+            .with_context(|| format!("Failed to list sequence store {:?}", store_dir))?
+        {
+            let path = entry?.path();
+            if !path.to_string_lossy().ends_with(".seq.json") {
+                continue;
+            }
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read sequence file {:?}", path))?;
+            let sequence: SessionSequence = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse sequence file {:?}", path))?;
+            info!(
+                "✅ Session {} resumes at outbound {} / inbound {}",
+                sequence.session, sequence.next_outbound, sequence.next_inbound
+            );
+            sessions.insert(sequence.session.clone(), sequence);
+        }
+
+        Ok(Self {
+            store_dir: Some(store_dir),
+            reset_schedule,
+            sessions: Mutex::new(sessions),
+            gaps: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Look up a session, applying a scheduled reset if one is due
+    fn session<'a>(
+        &self,
+        sessions: &'a mut HashMap<SessionId, SessionSequence>,
+        session: &SessionId,
+    ) -> &'a mut SessionSequence {
+        let sequence = sessions
+            .entry(session.clone())
+            .or_insert_with(|| SessionSequence::new(session.clone()));
+
+        let now = Utc::now();
+        if let Some(boundary) = self.reset_schedule.last_boundary(now) {
+            if sequence.last_reset < boundary {
+                info!("🔄 Scheduled sequence reset for session {}", session);
+                *sequence = SessionSequence::new(session.clone());
+            }
+        }
+        sequence
+    }
+
+    fn persist(&self, sequence: &SessionSequence) -> Result<()> {
+        let Some(store_dir) = &self.store_dir else {
+            return Ok(());
+        };
+
+        let path = store_dir.join(sequence.session.file_name());
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_string(sequence)?)
+            .with_context(|| format!("Failed to write sequence file {:?}", tmp))?;
+        std::fs::rename(&tmp, &path)
+            .with_context(|| format!("Failed to replace sequence file {:?}", path))?;
+        Ok(())
+    }
+
+    /// Allocate the MsgSeqNum of the next outbound message on a session
+    pub fn next_outbound(&self, session: &SessionId) -> Result<i32> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let sequence = self.session(&mut sessions, session);
+
+        let seq_num = sequence.next_outbound;
+        sequence.next_outbound += 1;
+        let snapshot = sequence.clone();
+        self.persist(&snapshot)?;
+        Ok(seq_num)
+    }
+
+    /// Check an inbound MsgSeqNum against the expected one and record gaps
+    pub fn on_inbound(&self, session: &SessionId, msg_seq_num: i32) -> InboundSequence {
+        let mut sessions = self.sessions.lock().unwrap();
+        let sequence = self.session(&mut sessions, session);
+        let expected = sequence.next_inbound;
+
+        let result = if msg_seq_num == expected {
+            InboundSequence::InOrder
+        } else if msg_seq_num > expected {
+            InboundSequence::Gap {
+                expected,
+                received: msg_seq_num,
+            }
+        } else {
+            InboundSequence::Duplicate {
+                expected,
+                received: msg_seq_num,
+            }
+        };
+
+        if msg_seq_num >= expected {
+            sequence.next_inbound = msg_seq_num + 1;
+            let snapshot = sequence.clone();
+            if let Err(e) = self.persist(&snapshot) {
+                warn!(
+                    "⚠️ Failed to persist inbound sequence of {}: {:#}",
+                    session, e
+                );
+            }
+        }
+        drop(sessions);
+
+        match result {
+            InboundSequence::Gap { expected, received } => {
+                warn!(
+                    "⚠️ SEQUENCE GAP on session {}: expected {}, received {} ({} missing)",
+                    session,
+                    expected,
+                    received,
+                    received - expected
+                );
+                self.gaps.lock().unwrap().push(SequenceGap {
+                    session: session.clone(),
+                    expected,
+                    received,
+                    detected_at: Utc::now(),
+                });
+            }
+            InboundSequence::Duplicate { expected, received } => {
+                warn!(
+                    "⚠️ Inbound MsgSeqNum {} on session {} is below expected {}",
+                    received, session, expected
+                );
+            }
+            InboundSequence::InOrder => {}
+        }
+        result
+    }
+
+    /// Track the MsgSeqNum of an ExecutionReport; reports without a header are ignored
+    pub fn on_execution_report(&self, report: &ExecutionReport) -> Option<InboundSequence> {
+        if report.fix_header.msg_seq_num <= 0 {
+            return None;
+        }
+        Some(self.on_inbound(
+            &SessionId::for_inbound(report),
+            report.fix_header.msg_seq_num,
+        ))
+    }
+
+    /// Start a session again from 1 in both directions
+    pub fn reset(&self, session: &SessionId) -> Result<()> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let sequence = SessionSequence::new(session.clone());
+        self.persist(&sequence)?;
+        sessions.insert(session.clone(), sequence);
+        info!("🔄 Sequence numbers of session {} reset", session);
+        Ok(())
+    }
+
+    /// Current sequence numbers of every known session
+    pub fn sessions(&self) -> Vec<SessionSequence> {
+        self.sessions.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Inbound gaps detected so far, oldest first
+    pub fn gaps(&self) -> Vec<SequenceGap> {
+        self.gaps.lock().unwrap().clone()
+    }
+}
@@ -0,0 +1,377 @@
+// src/shutdown.rs
+use crate::blotter::BlotterQuery;
+use crate::dds_client::OrderDdsClient;
+use crate::order_handle::OrderHandle;
+use crate::order_state::OrderState;
+use crate::supervisor::CancellationToken;
+use crate::utils::panics;
+use anyhow::{Context, Result};
+use futures::future::join_all;
+use log::{error, info, warn};
+use std::panic;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+/// What started the shutdown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownTrigger {
+    Command,
+    Signal(&'static str),
+    Panic,
+}
+
+impl std::fmt::Display for ShutdownTrigger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Command => write!(f, "exit command"),
+            Self::Signal(name) => write!(f, "{}", name),
+            Self::Panic => write!(f, "uncaught panic"),
+        }
+    }
+}
+
+/// Process-wide shutdown request, raised once by the CLI, a signal or a panic
+#[derive(Debug, Default)]
+pub struct Shutdown {
+    token: CancellationToken,
+    trigger: Mutex<Option<ShutdownTrigger>>,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request shutdown; only the first trigger is kept. Returns whether this
+    /// call started the shutdown.
+    pub fn trigger(&self, trigger: ShutdownTrigger) -> bool {
+        let mut current = self.trigger.lock().unwrap();
+        if current.is_some() {
+            return false;
+        }
+        info!("🛑 Shutdown requested by {}", trigger);
+        *current = Some(trigger);
+        self.token.cancel();
+        true
+    }
+
+    /// Trigger of the shutdown, if one was requested
+    pub fn reason(&self) -> Option<ShutdownTrigger> {
+        *self.trigger.lock().unwrap()
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Token cancelled when shutdown is requested
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    /// Resolves with the trigger once shutdown is requested
+    pub async fn triggered(&self) -> ShutdownTrigger {
+        self.token.cancelled().await;
+        self.reason().unwrap_or(ShutdownTrigger::Command)
+    }
+
+    /// Request shutdown on SIGINT or SIGTERM. A second signal while shutting
+    /// down exits the process straight away.
+    pub fn listen_for_signals(self: &Arc<Self>) -> Result<JoinHandle<()>> {
+        let mut interrupt =
+            signal(SignalKind::interrupt()).context("Failed to listen for SIGINT")?;
+        let mut terminate =
+            signal(SignalKind::terminate()).context("Failed to listen for SIGTERM")?;
+        let shutdown = Arc::clone(self);
+
+        Ok(tokio::spawn(async move {
+            loop {
+                let name = tokio::select! {
+                    _ = interrupt.recv() => "SIGINT",
+                    _ = terminate.recv() => "SIGTERM",
+                };
+                if !shutdown.trigger(ShutdownTrigger::Signal(name)) {
+                    warn!("⚠️ {} received while shutting down, exiting now", name);
+                    log::logger().flush();
+                    std::process::exit(130);
+                }
+            }
+        }))
+    }
+
+    /// Request shutdown when a panic is not caught by `utils::panics`, e.g. one
+    /// killing a detached task. The previous hook still runs first.
+    pub fn install_panic_hook(self: &Arc<Self>) {
+        let shutdown = Arc::clone(self);
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            previous(info);
+            if panics::is_caught() {
+                return;
+            }
+            error!("🚨 ALERT: Uncaught panic, shutting down: {}", info);
+            log::logger().flush();
+            shutdown.trigger(ShutdownTrigger::Panic);
+        }));
+    }
+}
+
+/// How open orders are wound down on shutdown
+#[derive(Debug, Clone)]
+pub struct ShutdownOptions {
+    /// Mass-cancel open orders and wait for them to be done, instead of only
+    /// waiting for unacknowledged orders to be acknowledged
+    pub cancel_open_orders: bool,
+    /// Longest wait for acknowledgements or cancels
+    pub ack_deadline: Duration,
+    /// Text of the Logout sent once orders are settled
+    pub logout_text: String,
+}
+
+impl Default for ShutdownOptions {
+    fn default() -> Self {
+        Self {
+            cancel_open_orders: false,
+            ack_deadline: Duration::from_secs(5),
+            logout_text: "Client shutdown".to_string(),
+        }
+    }
+}
+
+/// Outcome of `drain_orders`
+#[derive(Debug, Clone, Default)]
+pub struct OrderDrain {
+    /// Orders acknowledged, or done when cancelling, before the deadline
+    pub settled: usize,
+    /// ClOrdIDs of the orders still waiting at the deadline
+    pub unsettled: Vec<String>,
+    /// Sessions logged out of
+    pub logouts: usize,
+}
+
+/// Stop taking new orders, optionally cancel the open ones, wait for their
+/// reports up to the deadline and log out. Failures are logged so the rest of
+/// the shutdown still runs.
+pub async fn drain_orders(client: &OrderDdsClient, options: &ShutdownOptions) -> OrderDrain {
+    client.stop_accepting();
+    let deadline = Instant::now() + options.ack_deadline;
+
+    let handles: Vec<OrderHandle> = if options.cancel_open_orders {
+        client.cancel_open_orders().await.unwrap_or_else(|e| {
+            warn!("⚠️ Failed to cancel open orders: {:#}", e);
+            Vec::new()
+        })
+    } else {
+        let tracker = client.order_tracker();
+        client
+            .order_blotter()
+            .query(&BlotterQuery {
+                states: Some(vec![OrderState::PendingNew]),
+                ..Default::default()
+            })
+            .iter()
+            .map(|record| tracker.watch(&record.cl_ord_id))
+            .collect()
+    };
+
+    info!(
+        "⏳ Waiting up to {:?} for {} orders to {}",
+        options.ack_deadline,
+        handles.len(),
+        if options.cancel_open_orders {
+            "be cancelled"
+        } else {
+            "be acknowledged"
+        }
+    );
+    let wait = deadline.saturating_duration_since(Instant::now());
+    join_all(handles.iter().map(|handle| async move {
+        // Rejections and fills settle the order as well; the outcome is read below
+        if options.cancel_open_orders {
+            let _ = handle.done(wait).await;
+        } else {
+            let _ = handle.accepted(wait).await;
+        }
+    }))
+    .await;
+
+    let mut drain = OrderDrain::default();
+    for handle in &handles {
+        let settled = handle.latest().is_some_and(|update| {
+            update.is_terminal() || (!options.cancel_open_orders && update.is_accepted())
+        });
+        if settled {
+            drain.settled += 1;
+        } else {
+            drain.unsettled.push(handle.cl_ord_id().to_string());
+        }
+    }
+    if !drain.unsettled.is_empty() {
+        warn!(
+            "⚠️ {} orders unsettled at the shutdown deadline: {}",
+            drain.unsettled.len(),
+            drain.unsettled.join(", ")
+        );
+    }
+
+    match client.logout(&options.logout_text) {
+        Ok(sessions) => drain.logouts = sessions,
+        Err(e) => warn!("⚠️ Failed to log out: {:#}", e),
+    }
+    drain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::new_order_single::NewOrderSingle;
+    use crate::report::ExecutionReport;
+    use rustdds::no_key::DataWriter;
+    use rustdds::{
+        CDRSerializerAdapter, DomainParticipant, Publisher, QosPolicyBuilder, TopicKind,
+    };
+    use serde::Serialize;
+
+    // Domain of its own so test orders never reach a running OMS
+    const TEST_DOMAIN_ID: u16 = 99;
+
+    fn writer<T: Serialize>(
+        participant: &DomainParticipant,
+        publisher: &Publisher,
+        topic_name: &str,
+    ) -> DataWriter<T> {
+        let qos = QosPolicyBuilder::new().build();
+        let topic = participant
+            .create_topic(
+                format!("SHUTDOWN_TEST_{}", topic_name),
+                topic_name.to_string(),
+                &qos,
+                TopicKind::NoKey,
+            )
+            .unwrap();
+        publisher
+            .create_datawriter_no_key::<T, CDRSerializerAdapter<T>>(&topic, None)
+            .unwrap()
+    }
+
+    /// Client writing to a private participant, returned to keep it alive
+    async fn client() -> (OrderDdsClient, DomainParticipant) {
+        let participant = DomainParticipant::new(TEST_DOMAIN_ID).unwrap();
+        let publisher = participant
+            .create_publisher(&QosPolicyBuilder::new().build())
+            .unwrap();
+        let client = OrderDdsClient::new(
+            writer(&participant, &publisher, "ORDER_REQUEST"),
+            writer(&participant, &publisher, "NEW_ORDER_SINGLE"),
+            writer(&participant, &publisher, "ORDER_CANCEL_REQUEST"),
+            writer(&participant, &publisher, "ORDER_CANCEL_REPLACE_REQUEST"),
+        )
+        .await
+        .unwrap()
+        .with_mass_cancel_writer(writer(
+            &participant,
+            &publisher,
+            "ORDER_MASS_CANCEL_REQUEST",
+        ))
+        .with_logout_writer(writer(&participant, &publisher, "LOGOUT"));
+        (client, participant)
+    }
+
+    fn order(cl_ord_id: &str) -> NewOrderSingle {
+        NewOrderSingle::limit_order(
+            cl_ord_id.to_string(),
+            "BTC-USD".to_string(),
+            "1",
+            10,
+            100.0,
+            "rust_trader_001".to_string(),
+        )
+    }
+
+    async fn send(client: &OrderDdsClient, cl_ord_id: &str) {
+        client
+            .send_new_order_single(order(cl_ord_id))
+            .await
+            .unwrap();
+    }
+
+    /// Report an order's status as the matching engine would
+    fn report(client: &OrderDdsClient, cl_ord_id: &str, ord_status: u8) {
+        let report = ExecutionReport {
+            order_id: format!("ORD-{}", cl_ord_id),
+            orig_cl_ord_id: cl_ord_id.to_string(),
+            exec_id: format!("EXEC-{}-{}", cl_ord_id, ord_status as char),
+            exec_type: ord_status,
+            ord_status,
+            order_qty: 10,
+            leaves_qty: 10,
+            ..ExecutionReport::default()
+        };
+        client.order_blotter().on_execution_report(&report);
+        client.order_tracker().on_execution_report(&report);
+    }
+
+    #[tokio::test]
+    async fn first_trigger_wins() {
+        let shutdown = Shutdown::new();
+        assert!(!shutdown.is_triggered());
+
+        assert!(shutdown.trigger(ShutdownTrigger::Signal("SIGTERM")));
+        assert!(!shutdown.trigger(ShutdownTrigger::Command));
+        assert!(shutdown.token().is_cancelled());
+        assert_eq!(
+            shutdown.triggered().await,
+            ShutdownTrigger::Signal("SIGTERM")
+        );
+    }
+
+    #[tokio::test]
+    async fn drain_waits_for_acknowledgements_and_logs_out() {
+        let (client, _participant) = client().await;
+        send(&client, "CL-1").await;
+        send(&client, "CL-2").await;
+
+        let options = ShutdownOptions {
+            ack_deadline: Duration::from_millis(200),
+            ..Default::default()
+        };
+        let (drain, _) = tokio::join!(drain_orders(&client, &options), async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            report(&client, "CL-1", b'0');
+        });
+
+        assert!(!client.is_accepting());
+        assert_eq!(drain.settled, 1);
+        assert_eq!(drain.unsettled, vec!["CL-2".to_string()]);
+        assert_eq!(drain.logouts, 1);
+    }
+
+    #[tokio::test]
+    async fn drain_cancels_open_orders_and_waits_until_they_are_done() {
+        let (client, _participant) = client().await;
+        for cl_ord_id in ["CL-1", "CL-2", "CL-3"] {
+            send(&client, cl_ord_id).await;
+            report(&client, cl_ord_id, b'0');
+        }
+        report(&client, "CL-3", b'2');
+
+        let options = ShutdownOptions {
+            cancel_open_orders: true,
+            ack_deadline: Duration::from_millis(200),
+            ..Default::default()
+        };
+        let (drain, _) = tokio::join!(drain_orders(&client, &options), async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            report(&client, "CL-1", b'4');
+        });
+
+        // The filled order was not open, CL-2 never got its cancel confirmed
+        assert_eq!(drain.settled, 1);
+        assert_eq!(drain.unsettled, vec!["CL-2".to_string()]);
+        assert_eq!(drain.logouts, 1);
+        assert!(client.send_new_order_single(order("CL-4")).await.is_err());
+    }
+}
@@ -0,0 +1,487 @@
+// src/supervisor.rs
+use crate::utils::panics::{catch_unwind_async, panic_message};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use log::{error, info, warn};
+use metrics::counter;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// Signal telling supervised tasks to finish their work and return
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    cancelled: Arc<watch::Sender<bool>>,
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self {
+            cancelled: Arc::new(watch::channel(false).0),
+        }
+    }
+
+    /// Cancel every clone of this token
+    pub fn cancel(&self) {
+        self.cancelled.send_replace(true);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancelled.borrow()
+    }
+
+    /// Resolves once the token is cancelled
+    pub async fn cancelled(&self) {
+        let mut cancelled = self.cancelled.subscribe();
+        // The sender lives as long as `self`, so this only returns once cancelled
+        let _ = cancelled.wait_for(|cancelled| *cancelled).await;
+    }
+}
+
+/// Delay before restarting a failed task, doubling per consecutive failure
+#[derive(Debug, Clone, Copy)]
+pub struct RestartBackoff {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    /// A task running this long before failing starts again from `initial_delay`
+    pub reset_after: Duration,
+    /// Consecutive failures before the task is given up, `None` to retry forever
+    pub max_restarts: Option<u32>,
+}
+
+impl Default for RestartBackoff {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(30),
+            reset_after: Duration::from_secs(60),
+            max_restarts: None,
+        }
+    }
+}
+
+impl RestartBackoff {
+    fn delay(&self, failures: u32) -> Duration {
+        let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+        self.initial_delay
+            .saturating_mul(factor)
+            .min(self.max_delay)
+    }
+}
+
+/// Where a supervised task is in its life
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskHealth {
+    /// Registered, waiting for `Supervisor::start`
+    Pending,
+    Running,
+    /// Failed and waiting to be restarted
+    Restarting {
+        failures: u32,
+        retry_at: DateTime<Utc>,
+    },
+    /// Failed more often than `RestartBackoff::max_restarts` allows
+    Failed,
+    /// Returned after cancellation or was aborted at the drain deadline
+    Stopped,
+}
+
+impl std::fmt::Display for TaskHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pending => write!(f, "pending"),
+            Self::Running => write!(f, "running"),
+            Self::Restarting { failures, retry_at } => write!(
+                f,
+                "restarting at {} after {} failures",
+                retry_at.format("%H:%M:%S%.3f"),
+                failures
+            ),
+            Self::Failed => write!(f, "failed"),
+            Self::Stopped => write!(f, "stopped"),
+        }
+    }
+}
+
+/// Health of one supervised task
+#[derive(Debug, Clone)]
+pub struct TaskStatus {
+    pub name: String,
+    pub health: TaskHealth,
+    /// When the current run started
+    pub started_at: Option<DateTime<Utc>>,
+    pub restarts: u32,
+    pub last_error: Option<String>,
+}
+
+impl TaskStatus {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            health: TaskHealth::Pending,
+            started_at: None,
+            restarts: 0,
+            last_error: None,
+        }
+    }
+}
+
+type TaskFactory = Arc<dyn Fn(CancellationToken) -> BoxFuture<'static, Result<()>> + Send + Sync>;
+
+struct SupervisedTask {
+    status: Arc<Mutex<TaskStatus>>,
+    factory: TaskFactory,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// Owns long-running tasks such as report listeners: starts them together,
+/// restarts failed ones with backoff and stops them on one cancellation token
+pub struct Supervisor {
+    token: CancellationToken,
+    backoff: RestartBackoff,
+    drain_deadline: Duration,
+    tasks: Mutex<Vec<SupervisedTask>>,
+    started: AtomicBool,
+}
+
+impl Default for Supervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Supervisor {
+    pub fn new() -> Self {
+        Self {
+            token: CancellationToken::new(),
+            backoff: RestartBackoff::default(),
+            drain_deadline: Duration::from_secs(5),
+            tasks: Mutex::new(Vec::new()),
+            started: AtomicBool::new(false),
+        }
+    }
+
+    pub fn with_backoff(mut self, backoff: RestartBackoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// How long `shutdown` waits for tasks to return before aborting them
+    pub fn with_drain_deadline(mut self, deadline: Duration) -> Self {
+        self.drain_deadline = deadline;
+        self
+    }
+
+    /// Token cancelled by `shutdown`
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    /// Supervise a task built by `factory`, called again for every restart.
+    /// The task should return once its token is cancelled; returning earlier,
+    /// failing or panicking counts as a failure. Tasks added after `start`
+    /// start straight away.
+    pub fn supervise<F, Fut>(&self, name: &str, factory: F)
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let mut task = SupervisedTask {
+            status: Arc::new(Mutex::new(TaskStatus::new(name))),
+            factory: Arc::new(move |token| Box::pin(factory(token))),
+            handle: None,
+        };
+        let mut tasks = self.tasks.lock().unwrap();
+        if self.started.load(Ordering::SeqCst) {
+            task.handle = Some(self.spawn(&task));
+        }
+        tasks.push(task);
+    }
+
+    /// Start every task supervised so far
+    pub fn start(&self) {
+        let mut tasks = self.tasks.lock().unwrap();
+        self.started.store(true, Ordering::SeqCst);
+        for task in tasks.iter_mut().filter(|t| t.handle.is_none()) {
+            task.handle = Some(self.spawn(task));
+        }
+        info!("🚀 Supervisor started {} tasks", tasks.len());
+    }
+
+    fn spawn(&self, task: &SupervisedTask) -> JoinHandle<()> {
+        tokio::spawn(supervise(
+            Arc::clone(&task.factory),
+            Arc::clone(&task.status),
+            self.token.clone(),
+            self.backoff,
+        ))
+    }
+
+    /// Health of every supervised task, in the order they were added
+    pub fn health(&self) -> Vec<TaskStatus> {
+        self.tasks
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|t| t.status.lock().unwrap().clone())
+            .collect()
+    }
+
+    /// Whether every task is running
+    pub fn is_healthy(&self) -> bool {
+        self.health()
+            .iter()
+            .all(|status| status.health == TaskHealth::Running)
+    }
+
+    /// Cancel every task and wait up to the drain deadline for them to return,
+    /// aborting the ones still running after it
+    pub async fn shutdown(&self) -> Result<()> {
+        let tasks: Vec<_> = self
+            .tasks
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .filter_map(|t| Some((Arc::clone(&t.status), t.handle.take()?)))
+            .collect();
+        info!(
+            "🛑 Stopping {} supervised tasks, drain deadline {:?}",
+            tasks.len(),
+            self.drain_deadline
+        );
+        self.token.cancel();
+
+        let deadline = tokio::time::Instant::now() + self.drain_deadline;
+        let mut aborted = Vec::new();
+        for (status, mut handle) in tasks {
+            if tokio::time::timeout_at(deadline, &mut handle).await.is_ok() {
+                continue;
+            }
+            handle.abort();
+            let mut status = status.lock().unwrap();
+            warn!("⚠️ Task {} did not drain in time, aborted", status.name);
+            status.health = TaskHealth::Stopped;
+            status.last_error = Some("aborted at drain deadline".to_string());
+            aborted.push(status.name.clone());
+        }
+
+        if !aborted.is_empty() {
+            bail!(
+                "Tasks aborted after the {:?} drain deadline: {}",
+                self.drain_deadline,
+                aborted.join(", ")
+            );
+        }
+        info!("✅ All supervised tasks stopped");
+        Ok(())
+    }
+}
+
+/// Run a task until it returns after cancellation, restarting it on failure
+async fn supervise(
+    factory: TaskFactory,
+    status: Arc<Mutex<TaskStatus>>,
+    token: CancellationToken,
+    backoff: RestartBackoff,
+) {
+    let name = status.lock().unwrap().name.clone();
+    let mut failures = 0u32;
+    loop {
+        {
+            let mut status = status.lock().unwrap();
+            status.health = TaskHealth::Running;
+            status.started_at = Some(Utc::now());
+        }
+        let started = Instant::now();
+
+        let error = match catch_unwind_async(factory(token.clone())).await {
+            Ok(Ok(())) if token.is_cancelled() => {
+                info!("✅ Task {} stopped", name);
+                status.lock().unwrap().health = TaskHealth::Stopped;
+                return;
+            }
+            Ok(Ok(())) => "returned before cancellation".to_string(),
+            Ok(Err(e)) => format!("{:#}", e),
+            Err(panic) => format!("panicked: {}", panic_message(panic.as_ref())),
+        };
+
+        let delay = {
+            let mut current = status.lock().unwrap();
+            current.last_error = Some(error.clone());
+            if token.is_cancelled() {
+                warn!("⚠️ Task {} failed while stopping: {}", name, error);
+                current.health = TaskHealth::Stopped;
+                return;
+            }
+
+            if started.elapsed() >= backoff.reset_after {
+                failures = 0;
+            }
+            failures += 1;
+            if backoff.max_restarts.is_some_and(|max| failures > max) {
+                error!(
+                    "🚨 ALERT: Task {} failed {} times in a row, giving up: {}",
+                    name, failures, error
+                );
+                current.health = TaskHealth::Failed;
+                return;
+            }
+
+            let delay = backoff.delay(failures);
+            current.health = TaskHealth::Restarting {
+                failures,
+                retry_at: Utc::now() + chrono::Duration::from_std(delay).unwrap_or_default(),
+            };
+            delay
+        };
+        warn!(
+            "🔄 Task {} failed: {}; restarting in {:?} (failure {})",
+            name, error, delay, failures
+        );
+        counter!("process.task_restart", "task" => name.clone()).increment(1);
+
+        tokio::select! {
+            _ = token.cancelled() => {
+                status.lock().unwrap().health = TaskHealth::Stopped;
+                return;
+            }
+            _ = tokio::time::sleep(delay) => {}
+        }
+        status.lock().unwrap().restarts += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    fn backoff(max_restarts: Option<u32>) -> RestartBackoff {
+        RestartBackoff {
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(4),
+            reset_after: Duration::from_secs(60),
+            max_restarts,
+        }
+    }
+
+    /// Wait until the first task's status satisfies `predicate`
+    async fn wait_for_status<F>(supervisor: &Supervisor, predicate: F) -> TaskStatus
+    where
+        F: Fn(&TaskStatus) -> bool,
+    {
+        for _ in 0..200 {
+            let status = supervisor.health().remove(0);
+            if predicate(&status) {
+                return status;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        panic!("task status never matched: {:?}", supervisor.health());
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_max_delay() {
+        let backoff = backoff(None);
+        let delays: Vec<_> = (1..=5).map(|failures| backoff.delay(failures)).collect();
+        assert_eq!(delays, [1, 2, 4, 4, 4].map(Duration::from_millis).to_vec());
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_millis(4));
+    }
+
+    #[tokio::test]
+    async fn failing_task_is_restarted_until_max_restarts() {
+        let supervisor = Supervisor::new().with_backoff(backoff(Some(2)));
+        let runs = Arc::new(AtomicU32::new(0));
+        supervisor.supervise("listener", {
+            let runs = Arc::clone(&runs);
+            move |_| {
+                let run = runs.fetch_add(1, Ordering::SeqCst) + 1;
+                async move { bail!("sample stream ended on run {}", run) }
+            }
+        });
+        assert_eq!(supervisor.health()[0].health, TaskHealth::Pending);
+
+        supervisor.start();
+        let status = wait_for_status(&supervisor, |s| s.health == TaskHealth::Failed).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+        assert_eq!(status.restarts, 2);
+        assert_eq!(
+            status.last_error.as_deref(),
+            Some("sample stream ended on run 3")
+        );
+        assert!(!supervisor.is_healthy());
+    }
+
+    #[tokio::test]
+    async fn panicking_task_is_restarted_and_stops_on_shutdown() {
+        let supervisor = Supervisor::new().with_backoff(backoff(None));
+        let runs = Arc::new(AtomicU32::new(0));
+        supervisor.supervise("listener", {
+            let runs = Arc::clone(&runs);
+            move |token| {
+                let run = runs.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if run == 0 {
+                        panic!("callback bug");
+                    }
+                    token.cancelled().await;
+                    Ok(())
+                }
+            }
+        });
+        supervisor.start();
+
+        let status = wait_for_status(&supervisor, |s| {
+            s.health == TaskHealth::Running && s.restarts == 1
+        })
+        .await;
+        assert!(supervisor.is_healthy());
+        assert_eq!(status.last_error.as_deref(), Some("panicked: callback bug"));
+
+        supervisor.shutdown().await.unwrap();
+        assert!(supervisor.token().is_cancelled());
+        assert_eq!(supervisor.health()[0].health, TaskHealth::Stopped);
+    }
+
+    #[tokio::test]
+    async fn task_ignoring_cancellation_is_aborted_at_the_drain_deadline() {
+        let supervisor = Supervisor::new().with_drain_deadline(Duration::from_millis(20));
+        supervisor.supervise("stuck", |_| async {
+            std::future::pending::<()>().await;
+            Ok(())
+        });
+        supervisor.start();
+        wait_for_status(&supervisor, |s| s.health == TaskHealth::Running).await;
+
+        let error = supervisor.shutdown().await.unwrap_err();
+        assert!(error.to_string().contains("stuck"));
+        let status = supervisor.health().remove(0);
+        assert_eq!(status.health, TaskHealth::Stopped);
+        assert_eq!(
+            status.last_error.as_deref(),
+            Some("aborted at drain deadline")
+        );
+    }
+
+    #[tokio::test]
+    async fn tasks_added_after_start_start_straight_away() {
+        let supervisor = Supervisor::new();
+        supervisor.start();
+        supervisor.supervise("late", |token| async move {
+            token.cancelled().await;
+            Ok(())
+        });
+        wait_for_status(&supervisor, |s| s.health == TaskHealth::Running).await;
+        supervisor.shutdown().await.unwrap();
+    }
+}
@@ -0,0 +1,456 @@
+// src/throttle.rs
+use log::warn;
+use metrics::{counter, histogram};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// What happens to a message that finds its token bucket empty
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottlePolicy {
+    /// Wait for a token, but no longer than `max_wait`
+    Queue { max_wait: Duration },
+    /// Fail the send immediately
+    Reject,
+}
+
+/// Token bucket limit: `burst` messages at once, refilled at `rate_per_sec`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub rate_per_sec: f64,
+    pub burst: f64,
+    pub policy: ThrottlePolicy,
+}
+
+impl RateLimit {
+    pub fn new(rate_per_sec: f64, burst: f64, policy: ThrottlePolicy) -> Self {
+        Self {
+            rate_per_sec,
+            burst,
+            policy,
+        }
+    }
+}
+
+/// Limits applied to one kind of message
+#[derive(Debug, Clone, Default)]
+pub struct RateLimits {
+    /// Shared by every message of this kind
+    pub global: Option<RateLimit>,
+    /// Applied to each user separately
+    pub per_user: Option<RateLimit>,
+    /// Applied to each symbol separately
+    pub per_symbol: Option<RateLimit>,
+    /// Replace `per_user` for specific users
+    pub user_overrides: HashMap<String, RateLimit>,
+    /// Replace `per_symbol` for specific symbols
+    pub symbol_overrides: HashMap<String, RateLimit>,
+}
+
+/// Outbound throttling configuration; cancels have their own budget
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiterConfig {
+    pub orders: RateLimits,
+    pub cancels: RateLimits,
+}
+
+/// Kind of outbound message being throttled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageKind {
+    Order,
+    Cancel,
+}
+
+impl MessageKind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Order => "order",
+            Self::Cancel => "cancel",
+        }
+    }
+}
+
+/// Which limit a bucket enforces
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LimitScope {
+    Global,
+    User(String),
+    Symbol(String),
+}
+
+impl LimitScope {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Global => "global",
+            Self::User(_) => "user",
+            Self::Symbol(_) => "symbol",
+        }
+    }
+}
+
+impl std::fmt::Display for LimitScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Global => write!(f, "global"),
+            Self::User(user_id) => write!(f, "user {}", user_id),
+            Self::Symbol(symbol) => write!(f, "symbol {}", symbol),
+        }
+    }
+}
+
+/// Error returned when a message is not allowed out
+#[derive(Debug, Clone, PartialEq)]
+pub struct Throttled {
+    pub kind: MessageKind,
+    pub scope: LimitScope,
+    /// True when the message was queued but no token came in time
+    pub timed_out: bool,
+}
+
+impl std::fmt::Display for Throttled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.timed_out {
+            write!(
+                f,
+                "{} rate limit exceeded for {} (queue wait timed out)",
+                self.kind.label(),
+                self.scope
+            )
+        } else {
+            write!(
+                f,
+                "{} rate limit exceeded for {}",
+                self.kind.label(),
+                self.scope
+            )
+        }
+    }
+}
+
+impl std::error::Error for Throttled {}
+
+/// Counts of throttled messages since start
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ThrottleStats {
+    pub passed: u64,
+    pub queued: u64,
+    pub rejected: u64,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    limit: RateLimit,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit, now: Instant) -> Self {
+        Self {
+            limit,
+            tokens: limit.burst,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.limit.rate_per_sec).min(self.limit.burst);
+        self.last_refill = now;
+    }
+
+    /// Time until one token is available
+    fn wait_time(&self) -> Duration {
+        if self.tokens >= 1.0 {
+            return Duration::ZERO;
+        }
+        if self.limit.rate_per_sec <= 0.0 {
+            return Duration::MAX;
+        }
+        Duration::from_secs_f64((1.0 - self.tokens) / self.limit.rate_per_sec)
+    }
+}
+
+/// Token-bucket rate limiter for outbound messages, per user, per symbol and global
+pub struct RateLimiter {
+    config: RateLimiterConfig,
+    buckets: Mutex<HashMap<(MessageKind, LimitScope), TokenBucket>>,
+    passed: AtomicU64,
+    queued: AtomicU64,
+    rejected: AtomicU64,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimiterConfig) -> Self {
+        Self {
+            config,
+            buckets: Mutex::new(HashMap::new()),
+            passed: AtomicU64::new(0),
+            queued: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+        }
+    }
+
+    pub fn config(&self) -> &RateLimiterConfig {
+        &self.config
+    }
+
+    /// Limits that apply to a message, in global, user, symbol order
+    fn applicable(
+        &self,
+        kind: MessageKind,
+        user_id: &str,
+        symbol: &str,
+    ) -> Vec<(LimitScope, RateLimit)> {
+        let limits = match kind {
+            MessageKind::Order => &self.config.orders,
+            MessageKind::Cancel => &self.config.cancels,
+        };
+
+        let mut applicable = Vec::new();
+        if let Some(limit) = limits.global {
+            applicable.push((LimitScope::Global, limit));
+        }
+        if let Some(limit) = limits
+            .user_overrides
+            .get(user_id)
+            .copied()
+            .or(limits.per_user)
+        {
+            applicable.push((LimitScope::User(user_id.to_string()), limit));
+        }
+        if let Some(limit) = limits
+            .symbol_overrides
+            .get(symbol)
+            .copied()
+            .or(limits.per_symbol)
+        {
+            applicable.push((LimitScope::Symbol(symbol.to_string()), limit));
+        }
+        applicable
+    }
+
+    /// Take a token from every applicable bucket, waiting on queueing limits
+    /// and failing on rejecting ones
+    pub async fn acquire(
+        &self,
+        kind: MessageKind,
+        user_id: &str,
+        symbol: &str,
+    ) -> Result<(), Throttled> {
+        let limits = self.applicable(kind, user_id, symbol);
+        if limits.is_empty() {
+            return Ok(());
+        }
+
+        let start = Instant::now();
+        let mut deadline: Option<Instant> = None;
+
+        loop {
+            let (wait, scope, policy) = {
+                let mut buckets = self.buckets.lock().unwrap();
+                let now = Instant::now();
+
+                // Find the bucket that keeps the message waiting the longest
+                let mut longest: Option<(Duration, LimitScope, ThrottlePolicy)> = None;
+                for (scope, limit) in &limits {
+                    let bucket = buckets
+                        .entry((kind, scope.clone()))
+                        .or_insert_with(|| TokenBucket::new(*limit, now));
+                    bucket.refill(now);
+
+                    let wait = bucket.wait_time();
+                    if wait.is_zero() {
+                        continue;
+                    }
+                    if limit.policy == ThrottlePolicy::Reject {
+                        drop(buckets);
+                        return Err(self.throttled(kind, scope.clone(), false));
+                    }
+                    if longest.as_ref().is_none_or(|(w, _, _)| wait > *w) {
+                        longest = Some((wait, scope.clone(), limit.policy));
+                    }
+                }
+
+                match longest {
+                    None => {
+                        for (scope, _) in &limits {
+                            if let Some(bucket) = buckets.get_mut(&(kind, scope.clone())) {
+                                bucket.tokens -= 1.0;
+                            }
+                        }
+                        break;
+                    }
+                    Some(blocking) => blocking,
+                }
+            };
+
+            let ThrottlePolicy::Queue { max_wait } = policy else {
+                unreachable!("rejecting limits return before queueing");
+            };
+
+            if deadline.is_none() {
+                self.queued.fetch_add(1, Ordering::Relaxed);
+                counter!("process.order_throttled", "kind" => kind.label(), "scope" => scope.label(), "outcome" => "queued").increment(1);
+            }
+            let deadline = *deadline.get_or_insert(start + max_wait);
+            if Instant::now()
+                .checked_add(wait)
+                .is_none_or(|ready| ready > deadline)
+            {
+                return Err(self.throttled(kind, scope, true));
+            }
+
+            tokio::time::sleep(wait).await;
+        }
+
+        self.passed.fetch_add(1, Ordering::Relaxed);
+        if deadline.is_some() {
+            histogram!("process.order_throttle_wait", "kind" => kind.label())
+                .record(start.elapsed());
+        }
+        Ok(())
+    }
+
+    fn throttled(&self, kind: MessageKind, scope: LimitScope, timed_out: bool) -> Throttled {
+        self.rejected.fetch_add(1, Ordering::Relaxed);
+        counter!("process.order_throttled", "kind" => kind.label(), "scope" => scope.label(), "outcome" => "rejected").increment(1);
+
+        let throttled = Throttled {
+            kind,
+            scope,
+            timed_out,
+        };
+        warn!("🚦 Throttled: {}", throttled);
+        throttled
+    }
+
+    /// Counts of messages passed, queued and rejected so far
+    pub fn stats(&self) -> ThrottleStats {
+        ThrottleStats {
+            passed: self.passed.load(Ordering::Relaxed),
+            queued: self.queued.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(orders: RateLimits) -> RateLimiter {
+        RateLimiter::new(RateLimiterConfig {
+            orders,
+            ..RateLimiterConfig::default()
+        })
+    }
+
+    #[test]
+    fn bucket_refills_at_rate_up_to_burst() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(RateLimit::new(10.0, 5.0, ThrottlePolicy::Reject), start);
+        bucket.tokens = 0.0;
+        assert_eq!(bucket.wait_time(), Duration::from_millis(100));
+
+        bucket.refill(start + Duration::from_millis(250));
+        assert!((bucket.tokens - 2.5).abs() < 1e-9);
+        assert!(bucket.wait_time().is_zero());
+
+        bucket.refill(start + Duration::from_secs(10));
+        assert_eq!(bucket.tokens, 5.0);
+    }
+
+    #[tokio::test]
+    async fn rejecting_limit_fails_once_burst_is_spent() {
+        let limiter = limiter(RateLimits {
+            per_symbol: Some(RateLimit::new(0.0, 2.0, ThrottlePolicy::Reject)),
+            ..RateLimits::default()
+        });
+
+        for _ in 0..2 {
+            limiter
+                .acquire(MessageKind::Order, "u1", "AAPL")
+                .await
+                .unwrap();
+        }
+        let throttled = limiter
+            .acquire(MessageKind::Order, "u1", "AAPL")
+            .await
+            .unwrap_err();
+        assert_eq!(throttled.scope, LimitScope::Symbol("AAPL".to_string()));
+        assert!(!throttled.timed_out);
+
+        // Other symbols have their own buckets; unlimited cancels are not counted
+        limiter
+            .acquire(MessageKind::Order, "u1", "MSFT")
+            .await
+            .unwrap();
+        limiter
+            .acquire(MessageKind::Cancel, "u1", "AAPL")
+            .await
+            .unwrap();
+        assert_eq!(
+            limiter.stats(),
+            ThrottleStats {
+                passed: 3,
+                queued: 0,
+                rejected: 1
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn queueing_limit_waits_for_a_token() {
+        let limiter = limiter(RateLimits {
+            per_user: Some(RateLimit::new(
+                50.0,
+                1.0,
+                ThrottlePolicy::Queue {
+                    max_wait: Duration::from_secs(1),
+                },
+            )),
+            ..RateLimits::default()
+        });
+
+        limiter
+            .acquire(MessageKind::Order, "u1", "AAPL")
+            .await
+            .unwrap();
+        let start = Instant::now();
+        limiter
+            .acquire(MessageKind::Order, "u1", "AAPL")
+            .await
+            .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(15));
+
+        let stats = limiter.stats();
+        assert_eq!((stats.passed, stats.queued), (2, 1));
+    }
+
+    #[tokio::test]
+    async fn queueing_limit_times_out_past_max_wait() {
+        let limiter = limiter(RateLimits {
+            global: Some(RateLimit::new(
+                1.0,
+                1.0,
+                ThrottlePolicy::Queue {
+                    max_wait: Duration::from_millis(10),
+                },
+            )),
+            ..RateLimits::default()
+        });
+
+        limiter
+            .acquire(MessageKind::Order, "u1", "AAPL")
+            .await
+            .unwrap();
+        let throttled = limiter
+            .acquire(MessageKind::Order, "u2", "MSFT")
+            .await
+            .unwrap_err();
+        assert_eq!(throttled.scope, LimitScope::Global);
+        assert!(throttled.timed_out);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod order_id_generator;
+pub mod panics;
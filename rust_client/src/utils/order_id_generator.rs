@@ -0,0 +1,426 @@
+// src/utils/order_id_generator.rs
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+/// Counter values reserved per write of the state file
+const COUNTER_BLOCK: u64 = 1000;
+/// Milliseconds of timestamps reserved per write of the state file
+const TIMESTAMP_LEASE_MS: u64 = 1000;
+/// Custom Snowflake epoch: 2024-01-01T00:00:00Z
+const SNOWFLAKE_EPOCH_MS: u64 = 1_704_067_200_000;
+const SNOWFLAKE_NODE_BITS: u32 = 10;
+const SNOWFLAKE_SEQUENCE_BITS: u32 = 12;
+/// Digits of the zero-padded session counter
+const COUNTER_WIDTH: usize = 10;
+/// Crockford base32 alphabet used by ULIDs
+const CROCKFORD_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Source of unique ClOrdIDs and OrderIDs
+pub trait IdGenerator: Send + Sync {
+    /// Next ID, strictly ordered after every ID generated before it
+    fn next_id(&self) -> Result<String>;
+
+    /// Longest ID this generator produces, checked against venue limits up front
+    fn max_id_length(&self) -> usize;
+}
+
+/// Which ID scheme to use
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdStrategy {
+    /// Session prefix followed by a zero-padded monotonic counter, e.g. `RC1-0000000042`
+    SessionCounter { prefix: String },
+    /// 26 character ULID: millisecond timestamp plus 80 random bits
+    Ulid,
+    /// 64-bit Snowflake ID: timestamp, 10-bit node ID and 12-bit sequence
+    Snowflake { node_id: u16 },
+}
+
+/// ID generator configuration
+#[derive(Debug, Clone)]
+pub struct IdGeneratorConfig {
+    pub strategy: IdStrategy,
+    /// File persisting generator state so IDs stay unique across restarts
+    pub state_file: Option<PathBuf>,
+    /// Longest ClOrdID the venue accepts
+    pub max_length: usize,
+}
+
+/// Persisted high-water mark of a generator
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+struct PersistedIdState {
+    high_water_mark: u64,
+}
+
+/// Value no ID has reached yet, persisted ahead of use in blocks so the
+/// state file is only written once per block
+struct HighWaterMark {
+    state_file: Option<PathBuf>,
+    reserved: u64,
+    block: u64,
+}
+
+impl HighWaterMark {
+    fn load(state_file: Option<PathBuf>, block: u64) -> Result<Self> {
+        let mut reserved = 0;
+        if let Some(path) = state_file.as_deref().filter(|p| p.exists()) {
+            let content = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read ID generator state {:?}", path))?;
+            let state: PersistedIdState = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse ID generator state {:?}", path))?;
+            reserved = state.high_water_mark;
+            info!(
+                "✅ ID generator resuming after high-water mark {} from {:?}",
+                reserved, path
+            );
+        }
+
+        Ok(Self {
+            state_file,
+            reserved,
+            block,
+        })
+    }
+
+    /// Value every ID generated from now on must be at or above
+    fn floor(&self) -> u64 {
+        self.reserved
+    }
+
+    /// Make sure `value` is covered by the persisted reservation
+    fn reserve(&mut self, value: u64) -> Result<()> {
+        if value < self.reserved {
+            return Ok(());
+        }
+
+        let reserved = value + self.block;
+        if let Some(path) = &self.state_file {
+            save_state(
+                path,
+                PersistedIdState {
+                    high_water_mark: reserved,
+                },
+            )?;
+        }
+        self.reserved = reserved;
+        Ok(())
+    }
+}
+
+/// Write the state through a temporary file so a crash cannot leave it truncated
+fn save_state(path: &Path, state: PersistedIdState) -> Result<()> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create ID generator state dir {:?}", dir))?;
+    }
+
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, serde_json::to_string(&state)?)
+        .with_context(|| format!("Failed to write ID generator state {:?}", tmp))?;
+    std::fs::rename(&tmp, path)
+        .with_context(|| format!("Failed to replace ID generator state {:?}", path))?;
+    Ok(())
+}
+
+fn now_ms() -> u64 {
+    Utc::now().timestamp_millis() as u64
+}
+
+/// Session prefix followed by a monotonic counter
+pub struct SessionCounterGenerator {
+    prefix: String,
+    state: Mutex<(u64, HighWaterMark)>,
+}
+
+impl SessionCounterGenerator {
+    pub fn new(prefix: &str, state_file: Option<PathBuf>) -> Result<Self> {
+        let mark = HighWaterMark::load(state_file, COUNTER_BLOCK)?;
+        Ok(Self {
+            prefix: prefix.to_string(),
+            state: Mutex::new((mark.floor().max(1), mark)),
+        })
+    }
+}
+
+impl IdGenerator for SessionCounterGenerator {
+    fn next_id(&self) -> Result<String> {
+        let mut state = self.state.lock().unwrap();
+        let (next, mark) = &mut *state;
+
+        mark.reserve(*next)?;
+        let id = format!("{}{:0width$}", self.prefix, next, width = COUNTER_WIDTH);
+        *next += 1;
+        Ok(id)
+    }
+
+    fn max_id_length(&self) -> usize {
+        self.prefix.len() + COUNTER_WIDTH
+    }
+}
+
+/// Timestamp and per-millisecond sequence shared by the time-based generators.
+/// The clock never runs backwards, also across restarts: timestamps already
+/// leased in the state file are not reused.
+struct LogicalClock {
+    last_ms: u64,
+    sequence: u128,
+    mark: HighWaterMark,
+}
+
+impl LogicalClock {
+    fn load(state_file: Option<PathBuf>) -> Result<Self> {
+        let mark = HighWaterMark::load(state_file, TIMESTAMP_LEASE_MS)?;
+        Ok(Self {
+            last_ms: mark.floor(),
+            sequence: 0,
+            mark,
+        })
+    }
+
+    /// Next (timestamp, sequence) pair; moves to the next millisecond once
+    /// `max_sequence` is used up, and starts each millisecond at `first_sequence`
+    fn tick(
+        &mut self,
+        max_sequence: u128,
+        first_sequence: impl Fn() -> u128,
+    ) -> Result<(u64, u128)> {
+        let now = now_ms();
+        if now > self.last_ms {
+            self.last_ms = now;
+            self.sequence = first_sequence();
+        } else if self.sequence < max_sequence {
+            self.sequence += 1;
+        } else {
+            self.last_ms += 1;
+            self.sequence = first_sequence();
+        }
+
+        self.mark.reserve(self.last_ms)?;
+        Ok((self.last_ms, self.sequence))
+    }
+}
+
+/// Monotonic ULID generator
+pub struct UlidGenerator {
+    clock: Mutex<LogicalClock>,
+}
+
+impl UlidGenerator {
+    pub fn new(state_file: Option<PathBuf>) -> Result<Self> {
+        Ok(Self {
+            clock: Mutex::new(LogicalClock::load(state_file)?),
+        })
+    }
+
+    fn random_bits() -> u128 {
+        // Leave headroom so increments within a millisecond do not overflow
+        uuid::Uuid::new_v4().as_u128() & ((1 << 79) - 1)
+    }
+}
+
+impl IdGenerator for UlidGenerator {
+    fn next_id(&self) -> Result<String> {
+        let (ms, random) = self
+            .clock
+            .lock()
+            .unwrap()
+            .tick((1 << 80) - 1, Self::random_bits)?;
+
+        let value = ((ms as u128) << 80) | random;
+        let id = (0..26)
+            .map(|i| CROCKFORD_ALPHABET[((value >> (125 - i * 5)) & 0x1f) as usize] as char)
+            .collect();
+        Ok(id)
+    }
+
+    fn max_id_length(&self) -> usize {
+        26
+    }
+}
+
+/// Snowflake-style generator for one node of a cluster
+pub struct SnowflakeGenerator {
+    node_id: u16,
+    clock: Mutex<LogicalClock>,
+}
+
+impl SnowflakeGenerator {
+    pub fn new(node_id: u16, state_file: Option<PathBuf>) -> Result<Self> {
+        if node_id >= 1 << SNOWFLAKE_NODE_BITS {
+            bail!(
+                "Snowflake node ID {} out of range (0-{})",
+                node_id,
+                (1 << SNOWFLAKE_NODE_BITS) - 1
+            );
+        }
+
+        Ok(Self {
+            node_id,
+            clock: Mutex::new(LogicalClock::load(state_file)?),
+        })
+    }
+}
+
+impl IdGenerator for SnowflakeGenerator {
+    fn next_id(&self) -> Result<String> {
+        let (ms, sequence) = self
+            .clock
+            .lock()
+            .unwrap()
+            .tick((1 << SNOWFLAKE_SEQUENCE_BITS) - 1, || 0)?;
+
+        let elapsed = ms.saturating_sub(SNOWFLAKE_EPOCH_MS);
+        let id = (elapsed << (SNOWFLAKE_NODE_BITS + SNOWFLAKE_SEQUENCE_BITS))
+            | ((self.node_id as u64) << SNOWFLAKE_SEQUENCE_BITS)
+            | sequence as u64;
+        Ok(id.to_string())
+    }
+
+    fn max_id_length(&self) -> usize {
+        // 63 significant bits
+        19
+    }
+}
+
+/// ID generator enforcing the venue's ClOrdID length limit
+pub struct OrderIdGenerator {
+    generator: Box<dyn IdGenerator>,
+    max_length: usize,
+}
+
+impl OrderIdGenerator {
+    /// Wrap a generator, failing if it can produce IDs longer than `max_length`
+    pub fn new(generator: Box<dyn IdGenerator>, max_length: usize) -> Result<Self> {
+        if generator.max_id_length() > max_length {
+            bail!(
+                "ID generator produces IDs of up to {} characters, venue limit is {}",
+                generator.max_id_length(),
+                max_length
+            );
+        }
+
+        Ok(Self {
+            generator,
+            max_length,
+        })
+    }
+
+    /// Build the generator described by a configuration
+    pub fn from_config(config: &IdGeneratorConfig) -> Result<Self> {
+        let state_file = config.state_file.clone();
+        let generator: Box<dyn IdGenerator> = match &config.strategy {
+            IdStrategy::SessionCounter { prefix } => {
+                Box::new(SessionCounterGenerator::new(prefix, state_file)?)
+            }
+            IdStrategy::Ulid => Box::new(UlidGenerator::new(state_file)?),
+            IdStrategy::Snowflake { node_id } => {
+                Box::new(SnowflakeGenerator::new(*node_id, state_file)?)
+            }
+        };
+
+        info!("✅ ID generator initialized: {:?}", config.strategy);
+        Self::new(generator, config.max_length)
+    }
+
+    /// Next ID, checked against the length limit
+    pub fn next_id(&self) -> Result<String> {
+        let id = self.generator.next_id()?;
+        if id.len() > self.max_length {
+            bail!(
+                "Generated ID {} exceeds the venue limit of {} characters",
+                id,
+                self.max_length
+            );
+        }
+        Ok(id)
+    }
+
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+}
+
+impl Default for OrderIdGenerator {
+    /// In-memory ULIDs, unique across restarts through their random part
+    fn default() -> Self {
+        Self {
+            generator: Box::new(UlidGenerator::new(None).expect("in-memory ULID generator")),
+            max_length: 26,
+        }
+    }
+}
+
+/// Next ULID from a process-wide in-memory generator
+pub fn next_ulid() -> String {
+    static GENERATOR: OnceLock<UlidGenerator> = OnceLock::new();
+    GENERATOR
+        .get_or_init(|| UlidGenerator::new(None).expect("in-memory ULID generator"))
+        .next_id()
+        .expect("in-memory ULID generation cannot fail")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_file() -> PathBuf {
+        std::env::temp_dir().join(format!("order_id_generator_{}.json", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn session_counter_is_padded_and_ordered() {
+        let generator = SessionCounterGenerator::new("RC1-", None).unwrap();
+        assert_eq!(generator.next_id().unwrap(), "RC1-0000000001");
+        assert_eq!(generator.next_id().unwrap(), "RC1-0000000002");
+        assert_eq!(generator.max_id_length(), 14);
+    }
+
+    #[test]
+    fn session_counter_resumes_past_the_persisted_mark() {
+        let path = state_file();
+        let first = SessionCounterGenerator::new("RC1-", Some(path.clone())).unwrap();
+        let last = (0..3).map(|_| first.next_id().unwrap()).last().unwrap();
+        drop(first);
+
+        let resumed = SessionCounterGenerator::new("RC1-", Some(path.clone())).unwrap();
+        assert!(resumed.next_id().unwrap() > last);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn ulids_are_strictly_ordered() {
+        let generator = UlidGenerator::new(None).unwrap();
+        let ids: Vec<String> = (0..1000).map(|_| generator.next_id().unwrap()).collect();
+        assert!(ids.iter().all(|id| id.len() == 26));
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn snowflakes_are_ordered_and_carry_the_node_id() {
+        let generator = SnowflakeGenerator::new(42, None).unwrap();
+        let ids: Vec<u64> = (0..5000)
+            .map(|_| generator.next_id().unwrap().parse().unwrap())
+            .collect();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ids
+            .iter()
+            .all(|id| (id >> SNOWFLAKE_SEQUENCE_BITS) & ((1 << SNOWFLAKE_NODE_BITS) - 1) == 42));
+        assert!(SnowflakeGenerator::new(1 << SNOWFLAKE_NODE_BITS, None).is_err());
+    }
+
+    #[test]
+    fn order_id_generator_enforces_the_venue_limit() {
+        assert!(OrderIdGenerator::new(Box::new(UlidGenerator::new(None).unwrap()), 20).is_err());
+
+        let generator = OrderIdGenerator::from_config(&IdGeneratorConfig {
+            strategy: IdStrategy::Snowflake { node_id: 1 },
+            state_file: None,
+            max_length: 20,
+        })
+        .unwrap();
+        assert!(generator.next_id().unwrap().len() <= 20);
+    }
+}
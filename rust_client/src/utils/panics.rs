@@ -0,0 +1,62 @@
+// src/utils/panics.rs
+use std::any::Any;
+use std::cell::Cell;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+thread_local! {
+    // Depth of `catch_unwind` sections on this thread
+    static CATCHING: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Run `f`, turning a panic into an error the caller handles. Panic hooks can
+/// tell such panics apart with `is_caught`.
+pub fn catch_unwind<R>(f: impl FnOnce() -> R) -> std::thread::Result<R> {
+    CATCHING.with(|depth| depth.set(depth.get() + 1));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.with(|depth| depth.set(depth.get() - 1));
+    result
+}
+
+/// Future resolving to `Err` with the panic payload if polling `future` panics
+pub fn catch_unwind_async<F: Future>(future: F) -> CatchUnwind<F> {
+    CatchUnwind {
+        future: Box::pin(future),
+    }
+}
+
+/// Whether a panic raised now on this thread will be caught by `catch_unwind`
+/// or `catch_unwind_async`
+pub fn is_caught() -> bool {
+    CATCHING.with(|depth| depth.get() > 0)
+}
+
+/// Message of a caught panic, when it carries one
+pub fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message
+    } else {
+        "non-string panic payload"
+    }
+}
+
+/// Future returned by `catch_unwind_async`
+pub struct CatchUnwind<F> {
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = std::thread::Result<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match catch_unwind(|| self.future.as_mut().poll(cx)) {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(panic) => Poll::Ready(Err(panic)),
+        }
+    }
+}